@@ -0,0 +1,487 @@
+//! Derive macros for [cli-parser](https://github.com/gzarpapis/cli-parser), see `cliparser::CliArgs` and `cliparser::FromCliValue`.
+//!
+//! Written against the bare `proc_macro` API, so that the parser keeps having no dependencies.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+
+/// Settings of a single field, from its `#[cli(...)]` and doc attributes.
+#[derive(Default)]
+struct Attrs {
+	name: Option<String>,
+	short: Option<String>,
+	default: Option<String>,
+	env: Option<String>,
+	help: Option<String>,
+	doc: Vec<String>,
+	required: bool,
+	positional: bool,
+	hidden: bool,
+}
+
+
+/// How the type of a field maps onto the parsed arguments.
+enum Shape {
+	/// `bool`, a flag.
+	Flag,
+	/// `Option<T>`, a value that may be missing.
+	Optional(String),
+	/// `Vec<T>`, any number of values.
+	List(String),
+	/// Any other `T`, a value that must be there.
+	Plain(String),
+}
+
+
+struct Field {
+	ident: String,
+	shape: Shape,
+	attrs: Attrs,
+}
+
+
+/// Maps the named fields of a struct onto the flags, pairs and positional arguments of a `cliparser::Schema`,
+/// implementing the `cliparser::CliArgs` trait.
+///
+/// A `bool` field is a flag, an `Option<T>` field an optional pair, a `Vec<T>` field a pair read with `CLIParser::get_list`,
+/// and any other field a required pair. Values are read with `FromStr`. Doc comments become the help text.
+/// Fields take these settings in `#[cli(...)]` attributes:
+///
+/// - `name = "..."`: the argument name, the field name by default.
+/// - `short = "..."`: a single-dash name.
+/// - `default = "..."`: a default value, which makes a pair optional.
+/// - `env = "..."`: an environment variable to fall back to.
+/// - `help = "..."`: the help text, instead of the doc comment.
+/// - `positional`: a positional argument instead of a pair. It may be a `Vec<T>` to take any number of values.
+/// - `required`, `hidden`: see `Schema::required` and `Schema::hidden`.
+#[proc_macro_derive(CliArgs, attributes(cli))]
+pub fn derive_cli_args(input: TokenStream) -> TokenStream {
+	let generated = parse_struct(input).and_then(|(name, fields)| generate(&name, &fields));
+
+	match generated {
+		Ok(code) => code.parse().expect("generated code must be valid Rust"),
+		Err(message) => format!("compile_error!({:?});", message).parse().unwrap(),
+	}
+}
+
+
+/// Maps the variants of an enum without fields onto the values of a pair, implementing the `cliparser::FromCliValue` trait.
+///
+/// Variant names are written in kebab case, unless renamed with `#[cli(name = "...")]`.
+#[proc_macro_derive(FromCliValue, attributes(cli))]
+pub fn derive_from_cli_value(input: TokenStream) -> TokenStream {
+	let generated = parse_enum(input).map(|(name, variants)| generate_value_enum(&name, &variants));
+
+	match generated {
+		Ok(code) => code.parse().expect("generated code must be valid Rust"),
+		Err(message) => format!("compile_error!({:?});", message).parse().unwrap(),
+	}
+}
+
+
+/// Reads the name and named fields of the struct.
+fn parse_struct(input: TokenStream) -> Result<(String, Vec<Field>), String> {
+	let mut tokens = input.into_iter();
+
+	// Outer attributes and visibility come first
+	loop {
+		match tokens.next() {
+			Some(TokenTree::Ident(ident)) if ident.to_string() == "struct" => break,
+			Some(TokenTree::Ident(ident)) if matches!(ident.to_string().as_str(), "enum" | "union") => {
+				return Err("CliArgs can only be derived for structs".to_string());
+			}
+			Some(_) => continue,
+			None => return Err("CliArgs can only be derived for structs".to_string()),
+		}
+	}
+
+	let name = match tokens.next() {
+		Some(TokenTree::Ident(ident)) => ident.to_string(),
+		_ => return Err("expected the struct name".to_string()),
+	};
+
+	match tokens.next() {
+		Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => Ok((name, parse_fields(group.stream())?)),
+		Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => Err("CliArgs cannot be derived for generic structs".to_string()),
+		_ => Err("CliArgs needs a struct with named fields".to_string()),
+	}
+}
+
+
+/// Reads the name of the enum, and the names and values of its variants.
+fn parse_enum(input: TokenStream) -> Result<(String, Vec<(String, String)>), String> {
+	let mut tokens = input.into_iter();
+
+	loop {
+		match tokens.next() {
+			Some(TokenTree::Ident(ident)) if ident.to_string() == "enum" => break,
+			Some(TokenTree::Ident(ident)) if matches!(ident.to_string().as_str(), "struct" | "union") => {
+				return Err("FromCliValue can only be derived for enums".to_string());
+			}
+			Some(_) => continue,
+			None => return Err("FromCliValue can only be derived for enums".to_string()),
+		}
+	}
+
+	let name = match tokens.next() {
+		Some(TokenTree::Ident(ident)) => ident.to_string(),
+		_ => return Err("expected the enum name".to_string()),
+	};
+	let body = match tokens.next() {
+		Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => group.stream(),
+		Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => return Err("FromCliValue cannot be derived for generic enums".to_string()),
+		_ => return Err("expected the enum variants".to_string()),
+	};
+
+	let mut variants: Vec<(String, String)> = Vec::new();
+	let mut tokens = body.into_iter().peekable();
+
+	while tokens.peek().is_some() {
+		let mut attrs = Attrs::default();
+		while matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '#') {
+			tokens.next();
+			match tokens.next() {
+				Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => parse_attr(group.stream(), &mut attrs)?,
+				_ => return Err("malformed attribute".to_string()),
+			}
+		}
+
+		let ident = match tokens.next() {
+			Some(TokenTree::Ident(ident)) => ident.to_string(),
+			_ => return Err("expected a variant name".to_string()),
+		};
+		if attrs.short.is_some() || attrs.default.is_some() || attrs.env.is_some() || attrs.help.is_some()
+			|| attrs.required || attrs.positional || attrs.hidden {
+			return Err(format!("variant `{}` only takes a `name` setting", ident));
+		}
+
+		// Skip an explicit discriminant, up to the comma
+		loop {
+			match tokens.next() {
+				Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => break,
+				Some(TokenTree::Group(_)) => return Err(format!("variant `{}` cannot have fields", ident)),
+				Some(_) => continue,
+				None => break,
+			}
+		}
+
+		let value = attrs.name.unwrap_or_else(|| kebab_case(&ident));
+		variants.push((ident, value));
+	}
+
+	Ok((name, variants))
+}
+
+
+/// Writes `JsonLines` as `json-lines`.
+fn kebab_case(ident: &str) -> String {
+	let mut kebab = String::new();
+	for (i, c) in ident.chars().enumerate() {
+		if c.is_uppercase() && i > 0 {
+			kebab.push('-');
+		}
+		kebab.extend(c.to_lowercase());
+	}
+	kebab
+}
+
+
+/// Splits the struct body at the commas between fields, which generic arguments may contain too.
+fn parse_fields(body: TokenStream) -> Result<Vec<Field>, String> {
+	let mut fields: Vec<Field> = Vec::new();
+	let mut current: Vec<TokenTree> = Vec::new();
+	let mut depth = 0usize;
+	let mut after_dash = false;
+
+	for token in body {
+		if let TokenTree::Punct(punct) = &token {
+			match punct.as_char() {
+				',' if depth == 0 => {
+					fields.push(parse_field(std::mem::take(&mut current))?);
+					after_dash = false;
+					continue;
+				}
+				'<' => depth += 1,
+				// Not the arrow of a function type
+				'>' if !after_dash => depth = depth.saturating_sub(1),
+				_ => (),
+			}
+			after_dash = punct.as_char() == '-';
+		} else {
+			after_dash = false;
+		}
+		current.push(token);
+	}
+
+	if !current.is_empty() {
+		fields.push(parse_field(current)?);
+	}
+
+	Ok(fields)
+}
+
+
+fn parse_field(tokens: Vec<TokenTree>) -> Result<Field, String> {
+	let mut attrs = Attrs::default();
+	let mut tokens = tokens.into_iter().peekable();
+
+	// Attributes
+	while matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '#') {
+		tokens.next();
+		match tokens.next() {
+			Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Bracket => parse_attr(group.stream(), &mut attrs)?,
+			_ => return Err("malformed attribute".to_string()),
+		}
+	}
+
+	// Visibility
+	if matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident.to_string() == "pub") {
+		tokens.next();
+		if matches!(tokens.peek(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis) {
+			tokens.next();
+		}
+	}
+
+	let ident = match tokens.next() {
+		Some(TokenTree::Ident(ident)) => ident.to_string(),
+		_ => return Err("expected a field name".to_string()),
+	};
+	match tokens.next() {
+		Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => (),
+		_ => return Err(format!("expected the type of field `{}`", ident)),
+	}
+
+	let ty: Vec<TokenTree> = tokens.collect();
+	Ok(Field { shape: shape_of(&ty), ident, attrs })
+}
+
+
+/// Tells flags, optional values and lists apart by the outer type of the field.
+fn shape_of(ty: &[TokenTree]) -> Shape {
+	let inner = || TokenStream::from_iter(ty[2..ty.len() - 1].iter().cloned()).to_string();
+	let generic = ty.len() > 3 && matches!(&ty[1], TokenTree::Punct(punct) if punct.as_char() == '<');
+
+	match ty.first() {
+		Some(TokenTree::Ident(ident)) if ty.len() == 1 && ident.to_string() == "bool" => Shape::Flag,
+		Some(TokenTree::Ident(ident)) if generic && ident.to_string() == "Option" => Shape::Optional(inner()),
+		Some(TokenTree::Ident(ident)) if generic && ident.to_string() == "Vec" => Shape::List(inner()),
+		_ => Shape::Plain(TokenStream::from_iter(ty.iter().cloned()).to_string()),
+	}
+}
+
+
+/// Reads the contents of one `#[...]` attribute, ignoring those of other macros.
+fn parse_attr(attr: TokenStream, attrs: &mut Attrs) -> Result<(), String> {
+	let mut tokens = attr.into_iter();
+
+	match tokens.next() {
+		Some(TokenTree::Ident(ident)) if ident.to_string() == "doc" => {
+			if let (Some(TokenTree::Punct(_)), Some(TokenTree::Literal(literal))) = (tokens.next(), tokens.next()) {
+				let line = unquote(&literal.to_string())?;
+				attrs.doc.push(line.trim().to_string());
+			}
+			Ok(())
+		}
+		Some(TokenTree::Ident(ident)) if ident.to_string() == "cli" => match tokens.next() {
+			Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => parse_cli_attr(group.stream(), attrs),
+			_ => Err("expected `#[cli(...)]`".to_string()),
+		},
+		_ => Ok(()),
+	}
+}
+
+
+/// Reads the comma separated settings of a `#[cli(...)]` attribute.
+fn parse_cli_attr(settings: TokenStream, attrs: &mut Attrs) -> Result<(), String> {
+	let mut tokens = settings.into_iter().peekable();
+
+	while let Some(token) = tokens.next() {
+		let key = match token {
+			TokenTree::Ident(ident) => ident.to_string(),
+			_ => return Err("expected a `cli` setting".to_string()),
+		};
+
+		let value = match tokens.peek() {
+			Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+				tokens.next();
+				match tokens.next() {
+					Some(TokenTree::Literal(literal)) => Some(unquote(&literal.to_string())?),
+					_ => return Err(format!("expected a string for `{}`", key)),
+				}
+			}
+			_ => None,
+		};
+
+		match (key.as_str(), value) {
+			("name", Some(value)) => attrs.name = Some(value),
+			("short", Some(value)) => attrs.short = Some(value),
+			("default", Some(value)) => attrs.default = Some(value),
+			("env", Some(value)) => attrs.env = Some(value),
+			("help", Some(value)) => attrs.help = Some(value),
+			("required", None) => attrs.required = true,
+			("positional", None) => attrs.positional = true,
+			("hidden", None) => attrs.hidden = true,
+			("name" | "short" | "default" | "env" | "help", None) => return Err(format!("`{}` needs a value: `{} = \"...\"`", key, key)),
+			(_, _) => return Err(format!("unknown `cli` setting `{}`", key)),
+		}
+
+		match tokens.next() {
+			Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => (),
+			None => break,
+			_ => return Err("expected a comma between `cli` settings".to_string()),
+		}
+	}
+
+	Ok(())
+}
+
+
+/// Reads a string literal, raw or not.
+fn unquote(literal: &str) -> Result<String, String> {
+	if let Some(raw) = literal.strip_prefix('r') {
+		let hashes = raw.len() - raw.trim_start_matches('#').len();
+		return Ok(raw[hashes + 1..raw.len() - hashes - 1].to_string());
+	}
+
+	let body = match literal.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+		Some(body) => body,
+		None => return Err(format!("expected a string literal, found `{}`", literal)),
+	};
+
+	let mut text = String::new();
+	let mut chars = body.chars();
+	while let Some(c) = chars.next() {
+		if c != '\\' {
+			text.push(c);
+			continue;
+		}
+		match chars.next() {
+			Some('n') => text.push('\n'),
+			Some('t') => text.push('\t'),
+			Some('r') => text.push('\r'),
+			Some('0') => text.push('\0'),
+			Some('u') => {
+				let code: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+				let c = u32::from_str_radix(&code, 16).ok().and_then(char::from_u32).ok_or("invalid unicode escape")?;
+				text.push(c);
+			}
+			// Line continuation
+			Some('\n') => {
+				let rest: String = chars.by_ref().collect();
+				text.push_str(&unquote(&format!("\"{}\"", rest.trim_start()))?);
+				break;
+			}
+			Some(c) => text.push(c),
+			None => return Err("unterminated escape".to_string()),
+		}
+	}
+
+	Ok(text)
+}
+
+
+/// Writes the `CliArgs` implementation.
+fn generate(name: &str, fields: &[Field]) -> Result<String, String> {
+	let mut schema = String::from("::cliparser::Schema::new()");
+	let mut values = String::new();
+
+	for field in fields {
+		let attrs = &field.attrs;
+		let arg = attrs.name.clone().unwrap_or_else(|| field.ident.clone());
+		let lit = format!("{:?}", arg);
+
+		let (declaration, value) = match (&field.shape, attrs.positional) {
+			(Shape::Flag, true) => return Err(format!("flag `{}` cannot be positional", field.ident)),
+			(Shape::Flag, false) if attrs.default.is_some() => return Err(format!("flag `{}` cannot have a default", field.ident)),
+			(_, true) if attrs.default.is_some() => return Err(format!("positional `{}` cannot have a default", field.ident)),
+
+			(Shape::Flag, false) => (
+				format!(".flag({})", lit),
+				format!("parser.flags.contains({})", lit),
+			),
+			(Shape::Optional(ty), false) => (
+				format!(".pair({})", lit),
+				format!("parser.get::<{}>({})?", ty, lit),
+			),
+			(Shape::List(ty), false) => (
+				format!(".pair({})", lit),
+				format!("::cliparser::__derive::list::<{}>(parser, {})?", ty, lit),
+			),
+			(Shape::Plain(ty), false) => (
+				format!(".pair({}){}", lit, if attrs.default.is_none() { ".required()" } else { "" }),
+				format!("::cliparser::__derive::required(parser.get::<{}>({})?, {})?", ty, lit, lit),
+			),
+			(Shape::Optional(ty), true) => (
+				format!(".positional({})", lit),
+				format!("::cliparser::__derive::positional::<{}>(parser, {})?", ty, lit),
+			),
+			(Shape::List(ty), true) => (
+				format!(".positional({}).variadic()", lit),
+				format!("::cliparser::__derive::positionals::<{}>(parser, {})?", ty, lit),
+			),
+			(Shape::Plain(ty), true) => (
+				format!(".positional({}).required()", lit),
+				format!("::cliparser::__derive::required(::cliparser::__derive::positional::<{}>(parser, {})?, {})?", ty, lit, lit),
+			),
+		};
+
+		schema.push_str(&declaration);
+		if let Some(short) = &attrs.short {
+			schema.push_str(&format!(".short({:?})", short));
+		}
+		if let Some(default) = &attrs.default {
+			schema.push_str(&format!(".default_value({:?})", default));
+		}
+		if let Some(env) = &attrs.env {
+			schema.push_str(&format!(".env({:?})", env));
+		}
+		let help = attrs.help.clone().or_else(|| Some(attrs.doc.join(" ").trim().to_string()).filter(|doc| !doc.is_empty()));
+		if let Some(help) = help {
+			schema.push_str(&format!(".help({:?})", help));
+		}
+		if attrs.required {
+			schema.push_str(".required()");
+		}
+		if attrs.hidden {
+			schema.push_str(".hidden()");
+		}
+
+		values.push_str(&format!("{}: {},\n", field.ident, value));
+	}
+
+	Ok(format!("
+		impl ::cliparser::CliArgs for {name} {{
+			fn schema() -> ::cliparser::Schema {{
+				{schema}
+			}}
+
+			fn from_parser(parser: &::cliparser::CLIParser) -> ::std::result::Result<Self, ::cliparser::CLIError> {{
+				::std::result::Result::Ok(Self {{
+					{values}
+				}})
+			}}
+		}}
+	"))
+}
+
+
+/// Writes the `FromCliValue` implementation.
+fn generate_value_enum(name: &str, variants: &[(String, String)]) -> String {
+	let values: Vec<String> = variants.iter().map(|(_, value)| format!("{:?}", value)).collect();
+	let arms: String = variants.iter().map(|(ident, value)| format!("{:?} => ::std::option::Option::Some(Self::{}),\n", value, ident)).collect();
+
+	format!("
+		impl ::cliparser::FromCliValue for {name} {{
+			fn variants() -> &'static [&'static str] {{
+				&[{values}]
+			}}
+
+			fn from_cli_value(value: &str) -> ::std::option::Option<Self> {{
+				match value {{
+					{arms}
+					_ => ::std::option::Option::None,
+				}}
+			}}
+		}}
+	", values = values.join(", "))
+}
@@ -0,0 +1,177 @@
+//! Typed argument structs, filled in from the parsed data structures.
+
+use crate::{CLIParser, CLIError, Schema};
+
+
+/// **Typed arguments**, declared by a struct instead of read out of [`CLIParser::flags`], [`CLIParser::pairs`] and [`CLIParser::posits`].
+///
+/// Usually derived with the `derive` feature, which maps `bool` fields to flags, `Option<T>` fields to optional pairs,
+/// `Vec<T>` fields to [lists](CLIParser::get_list) and other fields to required pairs. Fields marked `#[cli(positional)]`
+/// are positional arguments instead. Values are read with [`FromStr`](std::str::FromStr), and doc comments become the help text.
+/// The settings of `#[cli(...)]` attributes are `name`, `short`, `default`, `env`, `help`, `positional`, `required` and `hidden`.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// use cliparser::CliArgs;
+///
+/// #[derive(CliArgs)]
+/// struct Args {
+///     /// Print more details
+///     #[cli(short = "v")]
+///     verbose: bool,
+///     #[cli(default = "4")]
+///     threads: u16,
+///     output: Option<String>,
+///     #[cli(positional)]
+///     inputs: Vec<String>,
+/// }
+///
+/// let args = Args::parse_from(["-v", "a.csv", "b.csv"].map(String::from)).unwrap();
+///
+/// assert!(args.verbose);
+/// assert_eq!(args.threads, 4);
+/// assert_eq!(args.output, None);
+/// assert_eq!(args.inputs, ["a.csv", "b.csv"]);
+/// ```
+pub trait CliArgs: Sized {
+
+	/// Declarations of the arguments.
+	fn schema() -> Schema;
+
+	/// Reads the arguments out of a parser, which used the [`CliArgs::schema`].
+	fn from_parser(parser: &CLIParser) -> Result<Self, CLIError>;
+
+	/// Parses and [validates](CLIParser::validate) the `std::env::args()`, see [`CLIParser::init_with`].
+	fn parse() -> Result<Self, CLIError> {
+		Self::from_parser(&CLIParser::new().init_with(Self::schema())?)
+	}
+
+	/// Parses and [validates](CLIParser::validate) the given arguments, which leave out the program path.
+	fn parse_from<I: IntoIterator<Item = String>>(args: I) -> Result<Self, CLIError> {
+		let parser = CLIParser::new().schema(Self::schema()).parse_from(args)?;
+		parser.validate()?;
+		Self::from_parser(&parser)
+	}
+
+}
+
+
+/// **Anything read out of a parsed command line**, for passing a [`CLIParser`] across crate boundaries.
+///
+/// Implemented by every [`CliArgs`] struct, and by tuples of them for layered applications
+/// where each layer reads its own arguments. See [`CLIParser::extract`].
+///
+/// ```
+/// use cliparser::{CLIError, CLIParser, FromCli};
+///
+/// // In a logging library
+/// struct LogOptions { verbose: bool }
+///
+/// impl FromCli for LogOptions {
+///     fn from_cli(parser: &CLIParser) -> Result<Self, CLIError> {
+///         Ok(LogOptions { verbose: parser.flag("verbose") })
+///     }
+/// }
+///
+/// struct Listen { port: u16 }
+///
+/// impl FromCli for Listen {
+///     fn from_cli(parser: &CLIParser) -> Result<Self, CLIError> {
+///         Ok(Listen { port: parser.get_or("port", 8080)? })
+///     }
+/// }
+///
+/// let parser = CLIParser::new().parse_str("-verbose --port=9000").unwrap();
+/// let (log, listen): (LogOptions, Listen) = parser.extract().unwrap();
+///
+/// assert!(log.verbose);
+/// assert_eq!(listen.port, 9000);
+/// ```
+pub trait FromCli: Sized {
+
+	/// Reads the value out of a parser.
+	fn from_cli(parser: &CLIParser) -> Result<Self, CLIError>;
+
+}
+
+
+impl<T: CliArgs> FromCli for T {
+	fn from_cli(parser: &CLIParser) -> Result<Self, CLIError> {
+		T::from_parser(parser)
+	}
+}
+
+
+impl<A: FromCli, B: FromCli> FromCli for (A, B) {
+	fn from_cli(parser: &CLIParser) -> Result<Self, CLIError> {
+		Ok((A::from_cli(parser)?, B::from_cli(parser)?))
+	}
+}
+
+
+impl<A: FromCli, B: FromCli, C: FromCli> FromCli for (A, B, C) {
+	fn from_cli(parser: &CLIParser) -> Result<Self, CLIError> {
+		Ok((A::from_cli(parser)?, B::from_cli(parser)?, C::from_cli(parser)?))
+	}
+}
+
+
+impl CLIParser {
+
+	/// Reads any [`FromCli`] value out of the parsed arguments.
+	pub fn extract<T: FromCli>(&self) -> Result<T, CLIError> {
+		T::from_cli(self)
+	}
+
+}
+
+
+/// Helpers of the code generated by the derive macro, not meant to be used directly.
+#[doc(hidden)]
+pub mod support {
+
+	use std::fmt::Display;
+	use std::str::FromStr;
+
+	use crate::{CLIParser, CLIError};
+	use crate::values::short_type_name;
+
+
+	fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T, CLIError>
+	where
+		T::Err: Display,
+	{
+		value.parse().map_err(|error: T::Err| CLIError::ValueParse {
+			key: name.to_string(),
+			value: value.to_string(),
+			type_name: short_type_name::<T>(),
+			reason: error.to_string(),
+		})
+	}
+
+	pub fn required<T>(value: Option<T>, name: &str) -> Result<T, CLIError> {
+		value.ok_or_else(|| CLIError::MissingRequired(vec![name.to_string()]))
+	}
+
+	pub fn list<T: FromStr>(parser: &CLIParser, name: &str) -> Result<Vec<T>, CLIError>
+	where
+		T::Err: Display,
+	{
+		Ok(parser.get_list(name)?.unwrap_or_default())
+	}
+
+	pub fn positional<T: FromStr>(parser: &CLIParser, name: &str) -> Result<Option<T>, CLIError>
+	where
+		T::Err: Display,
+	{
+		parser.positional(name).map(|value| parse_value(name, value)).transpose()
+	}
+
+	pub fn positionals<T: FromStr>(parser: &CLIParser, name: &str) -> Result<Vec<T>, CLIError>
+	where
+		T::Err: Display,
+	{
+		parser.positional_values(name).iter().map(|value| parse_value(name, value)).collect()
+	}
+
+}
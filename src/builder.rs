@@ -0,0 +1,119 @@
+//! Arguments declared one at a time, as an alternative to the chained [`Schema`](crate::Schema) modifiers.
+
+use crate::{ArgKind, ArgSpec, ValueType};
+
+
+/// **Argument builder**, for declaring arguments code-first and adding them with [`Schema::arg`](crate::Schema::arg).
+///
+/// An argument is a flag until it [takes a value](Arg::takes_value), which makes it a pair.
+/// The name given to [`Arg::new`] is what it is stored under.
+///
+/// ```
+/// use cliparser::{Arg, CLIParser, Schema};
+///
+/// let schema = Schema::new()
+///     .arg(Arg::new("output").short('o').takes_value(true).default("out.bin"))
+///     .arg(Arg::new("verbose").short('v').help("Print more details"))
+///     .arg(Arg::new("dry_run").long("dry-run"))
+///     .arg(Arg::new("input").positional().required(true));
+///
+/// let parser = CLIParser::new().schema(schema).parse_str("-v --dry-run data.csv").unwrap();
+///
+/// assert!(parser.flags.contains("verbose"));
+/// assert!(parser.flags.contains("dry_run"));
+/// assert_eq!(parser.pairs["output"], "out.bin");
+/// assert_eq!(parser.positional("input"), Some("data.csv"));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Arg(pub(crate) ArgSpec);
+
+
+impl Arg {
+
+	/// Starts the declaration of a flag stored under `name`.
+	pub fn new(name: &str) -> Self {
+		Self(ArgSpec::new(name, ArgKind::Flag))
+	}
+
+	/// Sets the name given on the command line, when it differs from the one the argument is stored under.
+	pub fn long(mut self, name: &str) -> Self {
+		if name != self.0.name {
+			self.0.aliases.push(name.to_string());
+		}
+		self
+	}
+
+	/// Gives the argument a single-dash name.
+	pub fn short(mut self, name: char) -> Self {
+		self.0.short = Some(name.to_string());
+		self
+	}
+
+	/// Makes the argument a pair when `true`, or a flag when `false`.
+	pub fn takes_value(mut self, takes_value: bool) -> Self {
+		self.0.kind = match takes_value {
+			true => ArgKind::Pair,
+			false => ArgKind::Flag,
+		};
+		self
+	}
+
+	/// Makes the argument a positional one, see [`Schema::positional`](crate::Schema::positional).
+	pub fn positional(mut self) -> Self {
+		self.0.kind = ArgKind::Positional;
+		self
+	}
+
+	/// Sets the value of a pair for when nothing else does, which also makes a flag take a value.
+	pub fn default(mut self, value: &str) -> Self {
+		self.take_value();
+		self.0.default = Some(value.to_string());
+		self
+	}
+
+	/// See [`Schema::value_type`](crate::Schema::value_type), which also makes a flag take a value.
+	/// 
+	/// ```
+	/// use cliparser::{Arg, CLIParser, Schema, ValueType};
+	/// 
+	/// let schema = Schema::new()
+	///     .arg(Arg::new("count").positional().value_type(ValueType::Int))
+	///     .arg(Arg::new("ratio").value_type(ValueType::Float));
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("--ratio=0.5 3").unwrap();
+	/// 
+	/// assert_eq!(parser.positional("count"), Some("3"));
+	/// assert_eq!(parser.pairs["ratio"], "0.5");
+	/// ```
+	pub fn value_type(mut self, value_type: ValueType) -> Self {
+		self.take_value();
+		self.0.value_type = value_type;
+		self
+	}
+
+	/// See [`Schema::required`](crate::Schema::required).
+	pub fn required(mut self, required: bool) -> Self {
+		self.0.required = required;
+		self
+	}
+
+	/// See [`Schema::help`](crate::Schema::help).
+	pub fn help(mut self, text: &str) -> Self {
+		self.0.help = Some(text.to_string());
+		self
+	}
+
+	/// See [`Schema::hidden`](crate::Schema::hidden).
+	pub fn hidden(mut self, hidden: bool) -> Self {
+		self.0.hidden = hidden;
+		self
+	}
+
+	/// Turns a flag into a pair, for the modifiers that only make sense with a value. Pairs and positional arguments keep their kind.
+	fn take_value(&mut self) {
+		if self.0.kind == ArgKind::Flag {
+			self.0.kind = ArgKind::Pair;
+		}
+	}
+
+}
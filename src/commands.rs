@@ -0,0 +1,459 @@
+//! Subcommands, each with its own [`Schema`], for programs with several verbs such as `tool build` and `tool deploy`.
+
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use crate::{CLIParser, CLIError, Rest, Schema};
+use crate::suggest;
+
+
+/// **Subcommand handler**, the code that runs a subcommand, see [`Schema::handler`] and [`CLIParser::dispatch`].
+/// 
+/// Implemented by closures taking the parser of the subcommand, for small tools.
+pub trait CommandHandler {
+
+	/// Runs the subcommand, with its `args` parsed.
+	fn run(&self, args: &CLIParser) -> ExitCode;
+
+}
+
+
+impl<F: Fn(&CLIParser) -> ExitCode> CommandHandler for F {
+	fn run(&self, args: &CLIParser) -> ExitCode {
+		self(args)
+	}
+}
+
+
+/// Handler registered for a subcommand, shared between the clones of its schema.
+#[derive(Clone)]
+pub(crate) struct Handler(Arc<dyn CommandHandler + Send + Sync>);
+
+
+impl std::fmt::Debug for Handler {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("Handler(..)")
+	}
+}
+
+
+// Handlers are only equal to their own clones, like validators
+impl PartialEq for Handler {
+	fn eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+}
+
+impl Eq for Handler {}
+
+
+/// A declared subcommand, see [`Schema::command`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Command {
+	pub(crate) name: String,
+	pub(crate) schema: Schema,
+	pub(crate) aliases: Vec<String>,
+	pub(crate) handler: Option<Handler>,
+}
+
+
+impl Schema {
+
+	/// Declares a subcommand `name`, whose arguments follow their own `schema`.
+	/// 
+	/// The first positional argument naming a subcommand selects it, and every argument after it is parsed against its schema
+	/// into a [nested parser](CLIParser::subcommand). Flags and pairs before it are global, they stay with the main parser.
+	/// The [description](Schema::about) of the subcommand schema is listed in the help text,
+	/// and its own subcommands make a [command tree](CLIParser::command_path).
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("verbose")
+	///     .command("build", Schema::new().about("Compile the project").flag("release"))
+	///     .command("deploy", Schema::new().about("Ship it").pair("target").required());
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("-verbose build -release").unwrap();
+	/// 
+	/// let (name, build) = parser.subcommand().unwrap();
+	/// assert_eq!(name, "build");
+	/// assert!(build.flag("release"));
+	/// assert!(parser.flag("verbose"));
+	/// assert!(!build.flag("verbose"));
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("biuld").unwrap();
+	/// assert_eq!(parser.validate(), Err(CLIError::UnknownCommand { given: "biuld".to_string(), suggestion: Some("build".to_string()) }));
+	/// ```
+	pub fn command(mut self, name: &str, schema: Schema) -> Self {
+		self.commands.push(Command { name: name.to_string(), schema, aliases: Vec::new(), handler: None });
+		self
+	}
+
+	/// Gives the last declared subcommand another name, such as `rm` for `remove`.
+	/// 
+	/// The parser reports the subcommand under its declared name. Panics if no subcommand has been declared yet.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, ParserConfig, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .command("remove", Schema::new()).command_alias("rm")
+	///     .command("checkout", Schema::new())
+	///     .command("cherry-pick", Schema::new());
+	/// let config = ParserConfig { command_abbreviations: true, ..ParserConfig::default() };
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("rm").unwrap();
+	/// assert_eq!(parser.command_path(), ["remove"]);
+	/// 
+	/// let parser = CLIParser::new().config(config.clone()).schema(schema.clone()).parse_str("chec").unwrap();
+	/// assert_eq!(parser.command_path(), ["checkout"]);
+	/// 
+	/// let error = CLIParser::new().config(config).schema(schema).parse_str("che").unwrap_err();
+	/// assert_eq!(error, CLIError::AmbiguousCommand("che".to_string(), vec!["checkout".to_string(), "cherry-pick".to_string()]));
+	/// ```
+	pub fn command_alias(mut self, alias: &str) -> Self {
+		self.commands.last_mut().expect("no subcommand declared yet").aliases.push(alias.to_string());
+		self
+	}
+
+	/// Selects the subcommand from the name the program was run as, for BusyBox-style tools installed under several names.
+	/// 
+	/// With `multicall` set, a [program path](CLIParser::program) whose [stem](CLIParser::program_stem) names a subcommand selects it
+	/// before any argument, as if linked with `ln -s mytool gzip`. Run under any other name, subcommands are matched as usual.
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .program("mytool")
+	///     .command("gzip", Schema::new().flag("d").positional("file"))
+	///     .command("ls", Schema::new())
+	///     .multicall(true);
+	/// 
+	/// let mut parser = CLIParser::new().schema(schema.clone());
+	/// parser.program = Some("/usr/local/bin/gzip".to_string());
+	/// let parser = parser.parse_str("-d notes.txt.gz").unwrap();
+	/// 
+	/// assert_eq!(parser.command_path(), ["gzip"]);
+	/// assert_eq!(parser.leaf().positional("file"), Some("notes.txt.gz"));
+	/// assert_eq!(parser.leaf().usage(), Some("gzip [OPTIONS] [file]".to_string()));
+	/// 
+	/// let mut parser = CLIParser::new().schema(schema);
+	/// parser.program = Some("/usr/local/bin/mytool".to_string());
+	/// let parser = parser.parse_str("gzip -d notes.txt.gz").unwrap();
+	/// assert_eq!(parser.command_path(), ["gzip"]);
+	/// ```
+	pub fn multicall(mut self, multicall: bool) -> Self {
+		self.multicall = multicall;
+		self
+	}
+
+	/// Registers the code that runs the last declared subcommand, called by [`CLIParser::dispatch`].
+	/// 
+	/// Panics if no subcommand has been declared yet.
+	/// 
+	/// ```
+	/// use std::process::ExitCode;
+	/// use std::sync::atomic::{AtomicBool, Ordering};
+	/// use cliparser::{CLIParser, CommandHandler, Schema};
+	/// 
+	/// static BUILT_RELEASE: AtomicBool = AtomicBool::new(false);
+	/// 
+	/// struct Build;
+	/// 
+	/// impl CommandHandler for Build {
+	///     fn run(&self, args: &CLIParser) -> ExitCode {
+	///         BUILT_RELEASE.store(args.flag("release"), Ordering::SeqCst);
+	///         ExitCode::SUCCESS
+	///     }
+	/// }
+	/// 
+	/// let schema = Schema::new()
+	///     .command("build", Schema::new().flag("release")).handler(Build)
+	///     .command("clean", Schema::new()).handler(|_: &CLIParser| ExitCode::FAILURE);
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("build -release").unwrap();
+	/// assert!(parser.dispatch().is_some());
+	/// assert!(BUILT_RELEASE.load(Ordering::SeqCst));
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("").unwrap();
+	/// assert!(parser.dispatch().is_none());
+	/// ```
+	pub fn handler<H: CommandHandler + Send + Sync + 'static>(mut self, handler: H) -> Self {
+		self.commands.last_mut().expect("no subcommand declared yet").handler = Some(Handler(Arc::new(handler)));
+		self
+	}
+
+	/// Makes the subcommand `name` the default one, parsing the arguments when none is given.
+	/// 
+	/// A first positional argument that names no subcommand then goes to the default one, so that `tool file.txt` behaves like `tool run file.txt`.
+	/// This keeps a flat command line working once it is split into subcommands.
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("verbose")
+	///     .command("run", Schema::new().positional("file").flag("watch"))
+	///     .command("check", Schema::new())
+	///     .default_command("run");
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("-verbose file.txt -watch").unwrap();
+	/// assert_eq!(parser.command_path(), ["run"]);
+	/// assert_eq!(parser.leaf().positional("file"), Some("file.txt"));
+	/// assert!(parser.leaf().flag("watch"));
+	/// assert!(parser.flag("verbose"));
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("").unwrap();
+	/// assert_eq!(parser.command_path(), ["run"]);
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("check").unwrap();
+	/// assert_eq!(parser.command_path(), ["check"]);
+	/// ```
+	pub fn default_command(mut self, name: &str) -> Self {
+		self.default_command = Some(name.to_string());
+		self
+	}
+
+	/// The declared subcommand named or aliased `name`, if there is one.
+	pub(crate) fn find_command(&self, name: &str) -> Option<&Command> {
+		self.commands.iter().find(|command| command.name == name || command.aliases.iter().any(|alias| alias == name))
+	}
+
+	/// The declared subcommand `name` abbreviates, if only one starts with it.
+	/// 
+	/// Will throw a [`CLIError::AmbiguousCommand`] error if several do.
+	fn complete_command(&self, name: &str) -> Result<Option<&Command>, CLIError> {
+		let candidates: Vec<&Command> = self.commands.iter().filter(|command| command.name.starts_with(name)).collect();
+		match candidates.len() {
+			0 => Ok(None),
+			1 => Ok(Some(candidates[0])),
+			_ => Err(CLIError::AmbiguousCommand(name.to_string(), candidates.iter().map(|command| command.name.clone()).collect())),
+		}
+	}
+
+}
+
+
+impl CLIParser {
+
+	/// Name and parser of the subcommand given on the command line, if any. See [`Schema::command`].
+	/// 
+	/// The nested parser holds the arguments after the subcommand, and is the one to read them from.
+	pub fn subcommand(&self) -> Option<(&str, &CLIParser)> {
+		self.command.as_ref().map(|(name, parser)| (name.as_str(), parser.as_ref()))
+	}
+
+	/// Names of the subcommands given, from the outermost to the innermost, for command trees such as `tool remote add <url>`.
+	/// 
+	/// Subcommands nest when their schema declares [subcommands](Schema::command) of its own, and are matched level by level.
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	/// 
+	/// let remote = Schema::new()
+	///     .command("add", Schema::new().positional("url").required())
+	///     .command("remove", Schema::new().positional("name").required());
+	/// let schema = Schema::new().program("tool").command("remote", remote);
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("remote add https://example.com/repo.git").unwrap();
+	/// 
+	/// assert_eq!(parser.command_path(), ["remote", "add"]);
+	/// assert_eq!(parser.leaf().positional("url"), Some("https://example.com/repo.git"));
+	/// assert_eq!(parser.leaf().usage(), Some("tool remote add <url>".to_string()));
+	/// ```
+	pub fn command_path(&self) -> Vec<&str> {
+		let mut path = Vec::new();
+		let mut parser = self;
+		while let Some((name, command)) = parser.subcommand() {
+			path.push(name);
+			parser = command;
+		}
+		path
+	}
+
+	/// Parser of the innermost subcommand given, or this one without subcommands. See [`CLIParser::command_path`].
+	pub fn leaf(&self) -> &CLIParser {
+		match self.subcommand() {
+			Some((_, command)) => command.leaf(),
+			None => self,
+		}
+	}
+
+	/// Runs the [handler](Schema::handler) of the innermost subcommand given that has one, with the parser of that subcommand.
+	/// 
+	/// Returns its exit code, or `None` without such a subcommand, typically to print the help text instead.
+	pub fn dispatch(&self) -> Option<ExitCode> {
+		let mut handled = None;
+		let mut parser = self;
+		while let Some((name, command)) = parser.subcommand() {
+			let handler = parser.schema.as_ref()
+				.and_then(|schema| schema.find_command(name))
+				.and_then(|declared| declared.handler.clone());
+			if let Some(handler) = handler {
+				handled = Some((handler, command));
+			}
+			parser = command;
+		}
+
+		handled.map(|(Handler(handler), parser)| handler.run(parser))
+	}
+
+	/// Starts parsing the subcommand named by the positional `argument`, if it names or [abbreviates](crate::ParserConfig::command_abbreviations) one
+	/// and none was given yet. Otherwise an [external subcommand](Schema::external_commands) or the [default subcommand](Schema::default_command) starts, with `argument` as its first one.
+	pub(crate) fn enter_command(&mut self, argument: &str, rest: &mut Rest) -> Result<bool, CLIError> {
+		let schema = match &self.schema {
+			Some(schema) if self.command.is_none() && self.external.is_none() && self.operands().is_empty() => schema,
+			_ => return Ok(false),
+		};
+		let command = match schema.find_command(argument) {
+			Some(command) => Some(command),
+			None if self.config.command_abbreviations && !argument.is_empty() => schema.complete_command(argument)?,
+			None => None,
+		};
+
+		if let Some(command) = command.cloned() {
+			self.start_command(command);
+			return Ok(true);
+		}
+		if self.enter_external_command(argument) {
+			return Ok(true);
+		}
+		match self.default_command() {
+			Some(command) => {
+				self.start_command(command);
+				self.forward_to_command(argument.to_string(), rest)?;
+				Ok(true)
+			}
+			None => Ok(false),
+		}
+	}
+
+	/// Starts the subcommand named by the program stem, for [multicall](Schema::multicall) schemas.
+	pub(crate) fn enter_multicall(&mut self) {
+		let schema = match &self.schema {
+			Some(schema) if schema.multicall && self.command.is_none() => schema,
+			_ => return,
+		};
+		let command = match self.program_stem().and_then(|stem| schema.find_command(stem)) {
+			Some(command) => command,
+			None => return,
+		};
+
+		let mut command = command.clone();
+		if command.schema.program.is_none() {
+			command.schema.program = self.program_stem().map(String::from);
+		}
+		self.start_command(command);
+	}
+
+	/// The [default subcommand](Schema::default_command), if one is declared.
+	fn default_command(&self) -> Option<Command> {
+		let schema = self.schema.as_ref()?;
+		schema.find_command(schema.default_command.as_ref()?).cloned()
+	}
+
+	/// Starts parsing the arguments that follow into a nested parser for `command`.
+	/// 
+	/// The nested parser inherits the configuration, except for the environment prefix, which only applies to the main parser.
+	fn start_command(&mut self, command: Command) {
+		let mut schema = command.schema;
+		if let Some(parent) = &self.schema {
+			schema.inherit_globals(parent);
+		}
+		if schema.program.is_none() {
+			schema.program = Some(format!("{} {}", self.program_name(), command.name));
+		}
+
+		let mut config = self.config.clone();
+		config.env_prefix = None;
+
+		let parser = CLIParser::new().config(config).schema(schema);
+		self.command = Some((command.name, Box::new(parser)));
+	}
+
+	/// Passes `argument` on to the subcommand being parsed, if there is one, or gives it back.
+	pub(crate) fn forward_to_command(&mut self, argument: String, rest: &mut Rest) -> Result<Option<String>, CLIError> {
+		if let Some(external) = &mut self.external {
+			external.args.push(argument);
+			return Ok(None);
+		}
+
+		let arg_index = self.arg_index;
+		match &mut self.command {
+			Some((_, parser)) => {
+				parser.arg_index = arg_index;
+				parser.classify(argument, rest)?;
+				Ok(None)
+			}
+			None => Ok(Some(argument)),
+		}
+	}
+
+	/// Fills in the [global](Schema::global) values and the environment variables declared by the subcommand, once every argument is classified.
+	/// 
+	/// Without any positional argument, this starts the [default subcommand](Schema::default_command) first.
+	pub(crate) fn finish_command(&mut self) -> Result<(), CLIError> {
+		if self.command.is_none() && self.external.is_none() && self.operands().is_empty() {
+			if let Some(command) = self.default_command() {
+				self.start_command(command);
+			}
+		}
+		if let Some((name, command)) = self.command.take() {
+			let mut command = *command;
+			command.inherit_global_values(self);
+			self.command = Some((name, Box::new(command.parse_from(std::iter::empty())?)));
+		}
+		Ok(())
+	}
+
+	/// Fills in the [global](Schema::global) flags and pairs the `parent` parser has and this subcommand parser was not given.
+	fn inherit_global_values(&mut self, parent: &CLIParser) {
+		let globals = match &parent.schema {
+			Some(schema) => schema.args().iter().filter(|spec| spec.global),
+			None => return,
+		};
+
+		for spec in globals {
+			let name = &spec.name;
+			if parent.flags.contains(name) && !self.flags.contains(name) && !self.negated.contains(name) {
+				self.flags.insert(name.clone());
+				self.counts.insert(name.clone(), parent.counts.get(name).copied().unwrap_or(1));
+			} else if parent.pairs.contains_key(name) && !parent.is_default(name) && (!self.pairs.contains_key(name) || self.is_default(name)) {
+				self.pairs.insert(name.clone(), parent.pairs[name].clone());
+				if let Some(values) = parent.pairs_multi.get(name) {
+					self.pairs_multi.insert(name.clone(), values.clone());
+				}
+			} else {
+				continue;
+			}
+			if let Some(source) = parent.sources.get(name) {
+				self.sources.insert(name.clone(), *source);
+			}
+		}
+	}
+
+	/// Checks the subcommand arguments against its schema, or that a positional argument meant as a subcommand names one.
+	/// 
+	/// Without declared positional arguments, the first one must be a subcommand,
+	/// or this will throw a [`CLIError::UnknownCommand`] error suggesting the closest one.
+	pub(crate) fn validate_command(&self) -> Result<(), CLIError> {
+		if let Some((_, parser)) = &self.command {
+			return parser.validate();
+		}
+
+		let schema = match &self.schema {
+			Some(schema) if !schema.commands.is_empty() && schema.positionals().next().is_none() => schema,
+			_ => return Ok(()),
+		};
+		match self.operands().first() {
+			Some(given) => Err(CLIError::UnknownCommand {
+				given: given.clone(),
+				suggestion: suggest::closest(given, schema.commands.iter().map(|command| command.name.as_str())).map(String::from),
+			}),
+			None => Ok(()),
+		}
+	}
+
+}
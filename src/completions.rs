@@ -0,0 +1,952 @@
+//! Shell completion scripts generated from the [`Schema`].
+
+use crate::{ArgKind, ArgSpec, Schema, ValueType};
+use crate::commands::Command;
+use crate::help::placeholder;
+use crate::schema::PathCheck;
+
+
+/// Shells that [`Schema::completions`] writes scripts for.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Shell {
+	/// Bash, through `complete -F`. Source the script, or install it as `/usr/share/bash-completion/completions/<program>`.
+	Bash,
+	/// Zsh, through `_arguments`. Install the script as `_<program>` in a directory of the `fpath`.
+	Zsh,
+	/// Fish, through `complete -c`. Install the script as `<program>.fish` in `~/.config/fish/completions`.
+	Fish,
+	/// PowerShell, through `Register-ArgumentCompleter`. Dot source the script from the profile.
+	PowerShell,
+	/// Nushell, through `extern` definitions. Source the script from the configuration.
+	Nushell,
+	/// Elvish, through `edit:completion:arg-completer`. Evaluate the script from `rc.elv`.
+	Elvish,
+}
+
+
+/// What the value of an argument may be completed with.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Hint {
+	/// Nothing to offer.
+	Anything,
+	Files,
+	Dirs,
+	/// One of a fixed set of words.
+	Words(Vec<String>),
+}
+
+
+fn hint(spec: &ArgSpec) -> Hint {
+	if !spec.choices.is_empty() {
+		return Hint::Words(spec.choices.clone());
+	}
+
+	match spec.value_type {
+		ValueType::Path if spec.path_checks.contains(&PathCheck::Dir) => Hint::Dirs,
+		ValueType::Path => Hint::Files,
+		ValueType::Bool => Hint::Words(vec!["true".to_string(), "false".to_string()]),
+		_ if spec.kind == ArgKind::Positional && spec.value_parser.is_none() && spec.value_type == ValueType::String => Hint::Files,
+		_ => Hint::Anything,
+	}
+}
+
+
+/// Quotes `text` for POSIX shells.
+fn quote(text: &str) -> String {
+	format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+
+/// Leaves words of letters, digits, dashes and underscores unquoted, for readable scripts.
+fn quote_word(word: &str) -> String {
+	match word.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') && !word.is_empty() {
+		true => word.to_string(),
+		false => quote(word),
+	}
+}
+
+
+/// Name of a shell function for the program, with anything but letters, digits and underscores replaced.
+fn function_name(program: &str) -> String {
+	program.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect()
+}
+
+
+/// A flag or pair as it is typed on the command line, with the names it goes by.
+struct Completion<'a> {
+	spec: Option<&'a ArgSpec>,
+	/// `-name` of flags, `--name` of pairs.
+	long: String,
+	short: Option<String>,
+	help: String,
+}
+
+
+impl Completion<'_> {
+
+	/// The help text, or the placeholder of the value of pairs without one.
+	fn description(&self) -> String {
+		match self.spec.filter(|spec| spec.kind == ArgKind::Pair) {
+			Some(spec) if self.help.is_empty() => placeholder(spec),
+			_ => self.help.clone(),
+		}
+	}
+
+}
+
+
+impl Schema {
+
+	/// The visible flags and pairs, plus `--help` and `--version` when the parser handles them.
+	fn completion_options(&self) -> Vec<Completion<'_>> {
+		let mut options: Vec<Completion> = self.options()
+			.filter(|spec| !spec.hidden)
+			.map(|spec| Completion {
+				spec: Some(spec),
+				long: match spec.kind {
+					ArgKind::Flag => format!("-{}", spec.name),
+					_ => format!("--{}", spec.name),
+				},
+				short: spec.short.as_ref().map(|short| format!("-{}", short)),
+				help: spec.help.clone().unwrap_or_default(),
+			})
+			.collect();
+
+		if self.is_help_request("--help") {
+			let short = Some("-h".to_string()).filter(|_| self.is_help_request("-h"));
+			options.push(Completion { spec: None, long: "--help".to_string(), short, help: "Print help".to_string() });
+		}
+		if self.is_version_request("--version") {
+			let short = Some("-V".to_string()).filter(|_| self.is_version_request("-V"));
+			options.push(Completion { spec: None, long: "--version".to_string(), short, help: "Print version".to_string() });
+		}
+
+		options
+	}
+
+	/// The schema of the program and of each of its nested subcommands, parents first, with the subcommands leading to it.
+	fn command_schemas(&self) -> Vec<(Vec<&Command>, &Schema)> {
+		let mut schemas: Vec<(Vec<&Command>, &Schema)> = vec![(Vec::new(), self)];
+		let mut i = 0;
+		while i < schemas.len() {
+			let (path, schema) = schemas[i].clone();
+			for command in &schema.commands {
+				let mut path = path.clone();
+				path.push(command);
+				schemas.push((path, &command.schema));
+			}
+			i += 1;
+		}
+		schemas
+	}
+
+	/// Completion script of the program for `shell`, offering the names of the visible flags and pairs,
+	/// choices and booleans for values, and files for paths and positional arguments. Shells that show descriptions get the help texts.
+	/// [Subcommands](Schema::command) are offered in place of the first positional argument,
+	/// and the arguments after a subcommand are completed from its own schema.
+	///
+	/// The program name is the [declared](Schema::program) one, or the name of the running executable.
+	/// Meant to be written to a file at build time, or printed by a `completions` command of the program.
+	///
+	/// ```
+	/// use cliparser::{Schema, Shell, ValueType};
+	///
+	/// let schema = Schema::new()
+	///     .program("mytool")
+	///     .flag("verbose").short("v")
+	///     .pair("format").one_of(["json", "yaml"])
+	///     .pair("output").short("o").value_type(ValueType::Path);
+	///
+	/// let script = schema.completions(Shell::Bash);
+	///
+	/// assert!(script.contains("complete -F _mytool mytool"));
+	/// assert!(script.contains("compgen -W '-verbose -v --format= --output= -o --help -h' -- \"$cur\""));
+	/// assert!(script.contains("compgen -P \"$prefix\" -W 'json yaml'"));
+	///
+	/// let script = schema.completions(Shell::Zsh);
+	///
+	/// assert!(script.starts_with("#compdef mytool\n"));
+	/// assert!(script.contains("'--format=:format:(json yaml)'"));
+	/// assert!(script.contains("'(-o --output)'{-o+,--output=}':output:_files'"));
+	///
+	/// let script = schema.completions(Shell::Fish);
+	///
+	/// assert!(script.contains("complete -c mytool -s v -o verbose\n"));
+	/// assert!(script.contains("complete -c mytool -n 'string match -q -- \"-*\" (commandline -ct)' -f -a '--format=json --format=yaml'\n"));
+	/// assert!(script.contains("complete -c mytool -n 'string match -q -- \"-*\" (commandline -ct)' -f -a '--output='\n"));
+	/// assert!(script.contains("complete -c mytool -s o -r -F\n"));
+	///
+	/// let script = schema.completions(Shell::PowerShell);
+	///
+	/// assert!(script.starts_with("Register-ArgumentCompleter -Native -CommandName 'mytool' -ScriptBlock {"));
+	/// assert!(script.contains("'--format=' = @('json', 'yaml')"));
+	///
+	/// let script = schema.completions(Shell::Nushell);
+	///
+	/// assert!(script.contains("export extern \"mytool\" [\n\t...args: string@\"nu-complete mytool\"\n]\n"));
+	/// assert!(script.contains("\t\t\t{ value: \"--format=json\" }\n"));
+	/// assert!(script.contains("\t\t\t{ value: \"-verbose\" }\n"));
+	///
+	/// let script = schema.completions(Shell::Elvish);
+	///
+	/// assert!(script.contains("set edit:completion:arg-completer['mytool'] = {|@words|"));
+	/// assert!(script.contains("&'--format='=['json' 'yaml']"));
+	/// ```
+	///
+	/// With subcommands:
+	///
+	/// ```
+	/// use cliparser::{Schema, Shell};
+	///
+	/// let schema = Schema::new()
+	///     .program("mytool")
+	///     .flag("verbose")
+	///     .command("build", Schema::new().about("Compile the project").flag("release"))
+	///     .command("deploy", Schema::new().pair("target")).command_alias("d");
+	///
+	/// let script = schema.completions(Shell::Bash);
+	///
+	/// assert!(script.contains("\t\t\t'deploy'|'d')\n\t\t\t\t_mytool__deploy $((i + 1))\n"));
+	/// assert!(script.contains("COMPREPLY=($(compgen -P \"$prefix\" -W 'build deploy' -- \"$cur\"))"));
+	/// assert!(script.contains("_mytool__build() {"));
+	/// assert!(script.contains("compgen -W '-release --help -h' -- \"$cur\""));
+	///
+	/// let script = schema.completions(Shell::Zsh);
+	///
+	/// assert!(script.contains("'build:Compile the project'"));
+	/// assert!(script.contains("'deploy'|'d')\n\t\t\t\t\t_mytool__deploy\n"));
+	///
+	/// let script = schema.completions(Shell::Fish);
+	///
+	/// assert!(script.contains("complete -c mytool -n 'not __fish_seen_subcommand_from build deploy d' -f -a build -d 'Compile the project'\n"));
+	/// assert!(script.contains("complete -c mytool -n 'not __fish_seen_subcommand_from build deploy d' -o verbose\n"));
+	/// assert!(script.contains("complete -c mytool -n '__fish_seen_subcommand_from build' -o release\n"));
+	/// assert!(script.contains("complete -c mytool -n '__fish_seen_subcommand_from deploy d; and string match -q -- \"-*\" (commandline -ct)' -f -a '--target='\n"));
+	///
+	/// let script = schema.completions(Shell::PowerShell);
+	///
+	/// assert!(script.contains("@{ Name = 'deploy'; Path = 'deploy'; Help = '' }"));
+	/// assert!(script.contains("\t\t'build' = @(\n\t\t\t@{ Name = '-release'; Help = '' }\n"));
+	/// assert!(script.contains("\t\t'deploy' = @(\n\t\t\t@{ Name = '--target='; Help = '<value>' }\n"));
+	///
+	/// let script = schema.completions(Shell::Nushell);
+	///
+	/// assert!(script.contains("export extern \"mytool build\" [\n"));
+	/// assert!(script.contains("\t\t\t{ value: \"build\", description: \"Compile the project\" }\n"));
+	/// assert!(script.contains("\t\t\t{ value: \"-release\" }\n"));
+	///
+	/// let script = schema.completions(Shell::Elvish);
+	///
+	/// assert!(script.contains("&''=[&'build'='build' &'deploy'='deploy' &'d'='deploy']"));
+	/// ```
+	pub fn completions(&self, shell: Shell) -> String {
+		let program = self.display_name();
+		match shell {
+			Shell::Bash => self.bash_completions(&program),
+			Shell::Zsh => self.zsh_completions(&program),
+			Shell::Fish => self.fish_completions(&program),
+			Shell::PowerShell => self.powershell_completions(&program),
+			Shell::Nushell => self.nushell_completions(&program),
+			Shell::Elvish => self.elvish_completions(&program),
+		}
+	}
+
+	fn bash_completions(&self, program: &str) -> String {
+		let mut script = String::new();
+
+		// Each subcommand has a function of its own, called by the function of its parent with the index of the word after the subcommand
+		for (path, schema) in self.command_schemas() {
+			let function = std::iter::once(program).chain(path.iter().map(|command| command.name.as_str()))
+				.map(function_name)
+				.collect::<Vec<String>>()
+				.join("__");
+			let first = if path.is_empty() { "1" } else { "$1" };
+			script.push_str(&schema.bash_function(&function, first));
+			script.push('\n');
+		}
+
+		script.push_str(&format!("complete -F _{} {}\n", function_name(program), program));
+		script
+	}
+
+	/// Bash function `_<function>` completing the words from index `first` on against the schema.
+	fn bash_function(&self, function: &str, first: &str) -> String {
+		let options = self.completion_options();
+
+		let reply = |hint: Hint, word: &str| match hint {
+			Hint::Anything => "COMPREPLY=()".to_string(),
+			Hint::Files => format!("COMPREPLY=($(compgen -P \"$prefix\" -f -- {}))", word),
+			Hint::Dirs => format!("COMPREPLY=($(compgen -P \"$prefix\" -d -- {}))", word),
+			Hint::Words(words) => format!("COMPREPLY=($(compgen -P \"$prefix\" -W {} -- {}))", quote(&words.join(" ")), word),
+		};
+
+		// Words after a subcommand are left to its function
+		let mut commands = String::new();
+		for command in &self.commands {
+			let names: Vec<String> = std::iter::once(&command.name).chain(&command.aliases).map(|name| quote(name)).collect();
+			commands.push_str(&format!("\t\t\t{})\n\t\t\t\t_{}__{} $((i + 1))\n\t\t\t\treturn 0\n\t\t\t\t;;\n", names.join("|"), function, function_name(&command.name)));
+		}
+		let dispatch = match commands.is_empty() {
+			true => String::new(),
+			false => format!("\tlocal i\n\tfor ((i = {}; i < COMP_CWORD; i++)); do\n\t\tcase \"${{COMP_WORDS[i]}}\" in\n{}\t\tesac\n\tdone\n\n", first, commands),
+		};
+
+		// Values after an equal sign, and after the single-dash name of a pair
+		let mut attached = String::new();
+		let mut separate = String::new();
+		for option in &options {
+			let spec = match option.spec {
+				Some(spec) if spec.kind == ArgKind::Pair => spec,
+				_ => continue,
+			};
+			attached.push_str(&format!("\t\t{}=*)\n\t\t\t{}\n\t\t\treturn 0\n\t\t\t;;\n", quote(&option.long), reply(hint(spec), "\"${word#*=}\"")));
+			if let Some(short) = &option.short {
+				separate.push_str(&format!("\t\t{})\n\t\t\t{}\n\t\t\treturn 0\n\t\t\t;;\n", quote(short), reply(hint(spec), "\"$cur\"")));
+			}
+		}
+
+		let words: Vec<String> = options.iter()
+			.flat_map(|option| {
+				let long = match option.spec {
+					Some(spec) if spec.kind == ArgKind::Pair => format!("{}=", option.long),
+					_ => option.long.clone(),
+				};
+				std::iter::once(long).chain(option.short.clone())
+			})
+			.collect();
+
+		let positional = match self.commands.is_empty() {
+			true => self.positionals().find(|spec| !spec.hidden).map_or(Hint::Files, hint),
+			false => Hint::Words(self.commands.iter().map(|command| command.name.clone()).collect()),
+		};
+
+		format!(
+"_{function}() {{
+{dispatch}	local cur prev word prefix
+	cur=\"${{COMP_WORDS[COMP_CWORD]}}\"
+	prev=\"${{COMP_WORDS[COMP_CWORD-1]}}\"
+
+	# The word up to the cursor, joined back together where `=` split it
+	word=\"${{COMP_LINE:0:COMP_POINT}}\"
+	word=\"${{word##*[[:space:]]}}\"
+
+	# Unless `=` splits words, values replace the whole `--key=value` word
+	[[ \"$COMP_WORDBREAKS\" != *=* ]] && prefix=\"${{word%%=*}}=\"
+	case \"$word\" in
+{attached}	esac
+	prefix=\"\"
+
+	case \"$prev\" in
+{separate}	esac
+
+	if [[ \"$cur\" == -* ]]; then
+		COMPREPLY=($(compgen -W {words} -- \"$cur\"))
+		[[ ${{#COMPREPLY[@]}} -eq 1 && \"${{COMPREPLY[0]}}\" == *= ]] && compopt -o nospace
+		return 0
+	fi
+
+	{positional}
+}}
+",
+			function = function,
+			dispatch = dispatch,
+			attached = attached,
+			separate = separate,
+			words = quote(&words.join(" ")),
+			positional = reply(positional, "\"$cur\""),
+		)
+	}
+
+	fn zsh_completions(&self, program: &str) -> String {
+		let mut script = format!("#compdef {}\n\n", program);
+
+		// As in bash, each subcommand has a function of its own
+		for (path, schema) in self.command_schemas() {
+			let function = std::iter::once(program).chain(path.iter().map(|command| command.name.as_str()))
+				.map(function_name)
+				.collect::<Vec<String>>()
+				.join("__");
+			script.push_str(&schema.zsh_function(&function));
+			script.push('\n');
+		}
+
+		script.push_str(&format!(
+"if [[ \"$funcstack[1]\" == \"_{function}\" ]]; then
+	_{function} \"$@\"
+else
+	compdef _{function} {program}
+fi
+",
+			function = function_name(program),
+			program = program,
+		));
+		script
+	}
+
+	/// Zsh function `_<function>` completing the arguments against the schema.
+	fn zsh_function(&self, function: &str) -> String {
+		// Brackets end the description of an `_arguments` spec, and colons its other parts
+		let describe = |text: &str| match text.is_empty() {
+			true => String::new(),
+			false => format!("[{}]", text.replace('\\', "\\\\").replace('[', "\\[").replace(']', "\\]")),
+		};
+		let escape = |text: &str| text.replace(':', "\\:");
+		let action = |hint: Hint| match hint {
+			Hint::Anything => " ".to_string(),
+			Hint::Files => "_files".to_string(),
+			Hint::Dirs => "_files -/".to_string(),
+			Hint::Words(words) => format!("({})", words.iter().map(|word| word.replace(' ', "\\ ")).collect::<Vec<String>>().join(" ")),
+		};
+
+		let mut specs: Vec<String> = Vec::new();
+
+		for option in self.completion_options() {
+			let description = describe(&option.help);
+			let value = match option.spec {
+				Some(spec) if spec.kind == ArgKind::Pair => format!(":{}:{}", escape(&spec.name), action(hint(spec))),
+				_ => String::new(),
+			};
+			// `=` takes the value attached or as the next argument, `+` does the same for single-dash names
+			let (long_suffix, short_suffix) = if value.is_empty() { ("", "") } else { ("=", "+") };
+
+			match &option.short {
+				// Both names exclude each other, and brace expansion gives each of them the spec
+				Some(short) => specs.push(format!("{}{{{}{},{}{}}}{}",
+					quote(&format!("({} {})", short, option.long)), short, short_suffix, option.long, long_suffix, quote(&format!("{}{}", description, value)))),
+				None => specs.push(quote(&format!("{}{}{}{}", option.long, long_suffix, description, value))),
+			}
+		}
+
+		if !self.commands.is_empty() {
+			// The first positional argument is a subcommand, and the ones after it are left to its function
+			specs.push(quote("1:command:->commands"));
+			specs.push(quote("*::argument:->arguments"));
+
+			let mut described = String::new();
+			let mut dispatch = String::new();
+			for command in &self.commands {
+				let about = command.schema.about.as_deref().unwrap_or_default();
+				let entry = match about.is_empty() {
+					true => escape(&command.name),
+					false => format!("{}:{}", escape(&command.name), about),
+				};
+				described.push_str(&format!("\t\t\t\t{}\n", quote(&entry)));
+
+				let names: Vec<String> = std::iter::once(&command.name).chain(&command.aliases).map(|name| quote(name)).collect();
+				dispatch.push_str(&format!("\t\t\t\t{})\n\t\t\t\t\t_{}__{}\n\t\t\t\t\t;;\n", names.join("|"), function, function_name(&command.name)));
+			}
+
+			return format!(
+"_{function}() {{
+	local context state state_descr line
+	typeset -A opt_args
+
+	_arguments -C \\
+		{specs}
+
+	case $state in
+		commands)
+			local commands=(
+{described}			)
+			_describe command commands
+			;;
+		arguments)
+			case $words[1] in
+{dispatch}			esac
+			;;
+	esac
+}}
+",
+				function = function,
+				specs = specs.join(" \\\n\t\t"),
+				described = described,
+				dispatch = dispatch,
+			);
+		}
+
+		let positionals: Vec<&ArgSpec> = self.positionals().filter(|spec| !spec.hidden).collect();
+		for (i, spec) in positionals.iter().enumerate() {
+			let position = if spec.variadic { "*".to_string() } else { (i + 1).to_string() };
+			let optional = if spec.required || spec.variadic { "" } else { ":" };
+			let description = escape(spec.help.as_deref().unwrap_or(&spec.name));
+			specs.push(quote(&format!("{}:{}{}:{}", position, optional, description, action(hint(spec)))));
+		}
+		if positionals.is_empty() {
+			specs.push(quote("*:file:_files"));
+		}
+
+		format!(
+"_{function}() {{
+	_arguments \\
+		{specs}
+}}
+",
+			function = function,
+			specs = specs.join(" \\\n\t\t"),
+		)
+	}
+
+	fn fish_completions(&self, program: &str) -> String {
+		let quote = |text: &str| format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"));
+		let values = |hint: Hint| match hint {
+			Hint::Anything => " -f".to_string(),
+			Hint::Files => " -F".to_string(),
+			Hint::Dirs => " -f -a '(__fish_complete_directories)'".to_string(),
+			Hint::Words(words) => format!(" -f -a {}", quote(&words.join(" "))),
+		};
+		let names = |command: &Command| std::iter::once(&command.name).chain(&command.aliases).map(|name| quote_word(name)).collect::<Vec<String>>().join(" ");
+
+		let program = quote_word(program);
+		let mut script = String::new();
+
+		for (path, schema) in self.command_schemas() {
+			// Lines apply once the subcommands leading to the schema are given, and until one of its own is
+			let mut conditions: Vec<String> = path.iter().map(|command| format!("__fish_seen_subcommand_from {}", names(command))).collect();
+			if !schema.commands.is_empty() {
+				let commands: Vec<String> = schema.commands.iter().map(names).collect();
+				conditions.push(format!("not __fish_seen_subcommand_from {}", commands.join(" ")));
+			}
+			let complete = match conditions.is_empty() {
+				true => format!("complete -c {}", program),
+				false => format!("complete -c {} -n {}", program, quote(&conditions.join("; and "))),
+			};
+
+			for command in &schema.commands {
+				let mut line = format!("{} -f -a {}", complete, quote_word(&command.name));
+				if let Some(about) = &command.schema.about {
+					line.push_str(&format!(" -d {}", quote(about)));
+				}
+				script.push_str(&line);
+				script.push('\n');
+			}
+
+			for option in schema.completion_options() {
+				let help = match option.help.is_empty() {
+					true => String::new(),
+					false => format!(" -d {}", quote(&option.help)),
+				};
+
+				let spec = match option.spec.filter(|spec| spec.kind == ArgKind::Pair) {
+					Some(spec) => spec,
+					None => {
+						let mut line = complete.clone();
+						if let Some(short) = &option.short {
+							line.push_str(&format!(" -s {}", quote_word(&short[1..])));
+						}
+						// Single-dash names are old style options to fish
+						match option.long.strip_prefix("--") {
+							Some(long) => line.push_str(&format!(" -l {}", quote_word(long))),
+							None => line.push_str(&format!(" -o {}", quote_word(&option.long[1..]))),
+						}
+						script.push_str(&format!("{}{}\n", line, help));
+						continue;
+					}
+				};
+
+				// Fish would send long options and their values as separate words, which the parser rejects,
+				// so the names are offered with the value attached
+				let long = format!("{}=", option.long);
+				let typing = |start: &str| {
+					let mut conditions = conditions.clone();
+					conditions.push(format!("string match -q -- \"{}*\" (commandline -ct)", start));
+					format!("complete -c {} -n {}", program, quote(&conditions.join("; and ")))
+				};
+				let paths = |function: &str| format!(
+					"{} -f -a {}\n",
+					typing(&long),
+					quote(&format!("({} (string replace -- \"{}\" \"\" (commandline -ct)) | string replace -r \"^\" \"{}\")", function, long, long)),
+				);
+				match hint(spec) {
+					Hint::Words(words) => {
+						let words: Vec<String> = words.iter().map(|word| format!("{}{}", long, word)).collect();
+						script.push_str(&format!("{} -f -a {}{}\n", typing("-"), quote(&words.join(" ")), help));
+					}
+					hint => {
+						script.push_str(&format!("{} -f -a {}{}\n", typing("-"), quote(&long), help));
+						match hint {
+							Hint::Files => script.push_str(&paths("__fish_complete_path")),
+							Hint::Dirs => script.push_str(&paths("__fish_complete_directories")),
+							_ => {},
+						}
+					}
+				}
+
+				// Single-dash names read their value from the next word
+				if let Some(short) = &option.short {
+					script.push_str(&format!("{} -s {} -r{}{}\n", complete, quote_word(&short[1..]), values(hint(spec)), help));
+				}
+			}
+
+			// Files are what fish offers by default
+			if schema.commands.is_empty() {
+				let positional = schema.positionals().find(|spec| !spec.hidden).map_or(Hint::Files, hint);
+				if positional != Hint::Files {
+					script.push_str(&format!("{}{}\n", complete, values(positional)));
+				}
+			}
+		}
+
+		script
+	}
+
+	fn powershell_completions(&self, program: &str) -> String {
+		let quote = |text: &str| format!("'{}'", text.replace('\'', "''"));
+
+		let mut commands = String::new();
+		let mut options = String::new();
+		let mut values = String::new();
+
+		// Tables are keyed by the names of the subcommands leading to the schema, separated by spaces
+		for (path, schema) in self.command_schemas() {
+			let key = path.iter().map(|command| command.name.as_str()).collect::<Vec<&str>>().join(" ");
+
+			commands.push_str(&format!("\t\t{} = @(\n", quote(&key)));
+			for command in &schema.commands {
+				let next = match key.is_empty() {
+					true => command.name.clone(),
+					false => format!("{} {}", key, command.name),
+				};
+				let help = command.schema.about.as_deref().unwrap_or_default();
+				for name in std::iter::once(&command.name).chain(&command.aliases) {
+					commands.push_str(&format!("\t\t\t@{{ Name = {}; Path = {}; Help = {} }}\n", quote(name), quote(&next), quote(help)));
+				}
+			}
+			commands.push_str("\t\t)\n");
+
+			options.push_str(&format!("\t\t{} = @(\n", quote(&key)));
+			values.push_str(&format!("\t\t{} = @{{\n", quote(&key)));
+			for option in schema.completion_options() {
+				let spec = option.spec.filter(|spec| spec.kind == ArgKind::Pair);
+				let help = option.description();
+
+				let long = match spec {
+					Some(_) => format!("{}=", option.long),
+					None => option.long.clone(),
+				};
+				for name in std::iter::once(&long).chain(&option.short) {
+					options.push_str(&format!("\t\t\t@{{ Name = {}; Help = {} }}\n", quote(name), quote(&help)));
+				}
+
+				// Files are what PowerShell offers when nothing is returned
+				let words = match spec.map(hint) {
+					Some(Hint::Words(words)) => words.iter().map(|word| quote(word)).collect::<Vec<String>>().join(", "),
+					_ => continue,
+				};
+				for name in std::iter::once(&long).chain(&option.short) {
+					values.push_str(&format!("\t\t\t{} = @({})\n", quote(name), words));
+				}
+			}
+			options.push_str("\t\t)\n");
+			values.push_str("\t\t}\n");
+		}
+
+		format!(
+"Register-ArgumentCompleter -Native -CommandName {program} -ScriptBlock {{
+	param($wordToComplete, $commandAst, $cursorPosition)
+
+	$commands = @{{
+{commands}	}}
+	$options = @{{
+{options}	}}
+	$values = @{{
+{values}	}}
+
+	$result = {{
+		param($text, $shown, $type, $help)
+		# Tooltips cannot be empty
+		if (-not $help) {{
+			$help = ' '
+		}}
+		[System.Management.Automation.CompletionResult]::new($text, $shown, $type, $help)
+	}}
+
+	# The subcommands given so far select the schema
+	$before = @($commandAst.CommandElements | Where-Object {{ $_.Extent.EndOffset -lt $cursorPosition }})
+	$path = ''
+	foreach ($element in $before | Select-Object -Skip 1) {{
+		$command = $commands[$path] | Where-Object {{ $_.Name -eq $element.ToString() }}
+		if ($command) {{
+			$path = $command.Path
+		}}
+	}}
+	$options = $options[$path]
+	$values = $values[$path]
+
+	# Values attached with an equal sign
+	if ($wordToComplete -match '^(-[^=]+=)(.*)$') {{
+		$key, $value = $Matches[1], $Matches[2]
+		if ($values.ContainsKey($key)) {{
+			$values[$key] | Where-Object {{ $_ -like \"$value*\" }} | ForEach-Object {{ & $result \"$key$_\" $_ 'ParameterValue' $_ }}
+		}}
+		return
+	}}
+
+	# Values after the single-dash name of a pair
+	if ($before.Count -gt 1 -and $values.ContainsKey($before[-1].ToString())) {{
+		$values[$before[-1].ToString()] | Where-Object {{ $_ -like \"$wordToComplete*\" }} | ForEach-Object {{ & $result $_ $_ 'ParameterValue' $_ }}
+		return
+	}}
+
+	if ($wordToComplete -like '-*') {{
+		$options | Where-Object {{ $_.Name -like \"$wordToComplete*\" }} | ForEach-Object {{ & $result $_.Name $_.Name 'ParameterName' $_.Help }}
+		return
+	}}
+
+	$commands[$path] | Where-Object {{ $_.Name -like \"$wordToComplete*\" }} | ForEach-Object {{ & $result $_.Name $_.Name 'ParameterValue' $_.Help }}
+}}
+",
+			program = quote(program),
+			commands = commands,
+			options = options,
+			values = values,
+		)
+	}
+
+	fn nushell_completions(&self, program: &str) -> String {
+		// Nushell completes the subcommands of an external from the definitions named after them
+		self.command_schemas().iter()
+			.map(|(path, schema)| {
+				let name = std::iter::once(program).chain(path.iter().map(|command| command.name.as_str())).collect::<Vec<&str>>().join(" ");
+				schema.nushell_extern(&name)
+			})
+			.collect::<Vec<String>>()
+			.join("\n")
+	}
+
+	/// Nushell `extern` definition of the program or subcommand `name`, after the helper completing its arguments.
+	fn nushell_extern(&self, name: &str) -> String {
+		let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+		let candidate = |value: &str, help: &str| match help.is_empty() {
+			true => format!("\t\t\t{{ value: {} }}\n", quote(value)),
+			false => format!("\t\t\t{{ value: {}, description: {} }}\n", quote(value), quote(&help.replace('\n', " "))),
+		};
+
+		// Nushell would send declared options in its own syntax, so every argument goes through the rest parameter
+		// and the helper offers names and values the way the parser reads them
+		let mut options = String::new();
+		for option in self.completion_options() {
+			// Nushell handles `--help` itself
+			if option.spec.is_none() && option.long == "--help" {
+				continue;
+			}
+			match option.spec.filter(|spec| spec.kind == ArgKind::Pair).map(hint) {
+				Some(Hint::Words(words)) => {
+					for word in words {
+						options.push_str(&candidate(&format!("{}={}", option.long, word), &option.help));
+					}
+				}
+				Some(_) => options.push_str(&candidate(&format!("{}=", option.long), &option.help)),
+				None => options.push_str(&candidate(&option.long, &option.help)),
+			}
+			if let Some(short) = &option.short {
+				options.push_str(&candidate(short, &option.help));
+			}
+		}
+
+		let mut words = String::new();
+		for command in &self.commands {
+			let about = command.schema.about.clone().unwrap_or_default();
+			for alias in std::iter::once(&command.name).chain(&command.aliases) {
+				words.push_str(&candidate(alias, &about));
+			}
+		}
+		let positional = self.positionals().find(|spec| !spec.hidden).map_or(Hint::Files, hint);
+		if let Hint::Words(values) = &positional {
+			for value in values {
+				words.push_str(&candidate(value, ""));
+			}
+		}
+		// Nushell falls back to files when the helper returns nothing
+		let words = match (self.commands.is_empty(), positional) {
+			(true, Hint::Files | Hint::Dirs) => "null".to_string(),
+			_ => format!("[\n{}\t\t]", words),
+		};
+
+		let helper = format!("nu-complete {}", name);
+		format!(
+"def {helper} [context: string] {{
+	let word = ($context | split row ' ' | last)
+	if ($word | str starts-with '-') {{
+		[
+{options}		]
+	}} else {{
+		{words}
+	}}
+}}
+
+export extern {name} [
+	...args: string@{helper}
+]
+",
+			helper = quote(&helper),
+			options = options,
+			words = words,
+			name = quote(name),
+		)
+	}
+
+	fn elvish_completions(&self, program: &str) -> String {
+		let quote = |text: &str| format!("'{}'", text.replace('\'', "''"));
+
+		let mut commands = String::new();
+		let mut options = String::new();
+		let mut values = String::new();
+
+		// Maps are keyed by the names of the subcommands leading to the schema, separated by spaces
+		for (path, schema) in self.command_schemas() {
+			let key = path.iter().map(|command| command.name.as_str()).collect::<Vec<&str>>().join(" ");
+
+			let mut names: Vec<String> = Vec::new();
+			for command in &schema.commands {
+				let next = match key.is_empty() {
+					true => command.name.clone(),
+					false => format!("{} {}", key, command.name),
+				};
+				for name in std::iter::once(&command.name).chain(&command.aliases) {
+					names.push(format!("&{}={}", quote(name), quote(&next)));
+				}
+			}
+			commands.push_str(&format!("\t\t&{}=[{}]\n", quote(&key), if names.is_empty() { "&".to_string() } else { names.join(" ") }));
+
+			let mut listed = String::new();
+			let mut completed = String::new();
+			for option in schema.completion_options() {
+				let spec = option.spec.filter(|spec| spec.kind == ArgKind::Pair);
+				let long = match spec {
+					Some(_) => format!("{}=", option.long),
+					None => option.long.clone(),
+				};
+				for name in std::iter::once(&long).chain(&option.short) {
+					listed.push_str(&format!("\t\t\t[&name={} &help={}]\n", quote(name), quote(&option.description())));
+				}
+
+				let words = match spec.map(hint) {
+					Some(Hint::Words(words)) => words.iter().map(|word| quote(word)).collect::<Vec<String>>().join(" "),
+					_ => continue,
+				};
+				for name in std::iter::once(&long).chain(&option.short) {
+					completed.push_str(&format!("\t\t\t&{}=[{}]\n", quote(name), words));
+				}
+			}
+			options.push_str(&format!("\t\t&{}=[\n{}\t\t]\n", quote(&key), listed));
+			values.push_str(&format!("\t\t&{}={}\n", quote(&key), match completed.is_empty() {
+				true => "[&]".to_string(),
+				false => format!("[\n{}\t\t]", completed),
+			}));
+		}
+
+		format!(
+"use str
+
+set edit:completion:arg-completer[{program}] = {{|@words|
+	var current = $words[-1]
+	var previous = ''
+	if (> (count $words) 2) {{
+		set previous = $words[-2]
+	}}
+
+	var commands = [
+{commands}	]
+	var options = [
+{options}	]
+	var values = [
+{values}	]
+
+	# The subcommands given so far select the schema
+	var path = ''
+	for word $words[1..-1] {{
+		if (has-key $commands[$path] $word) {{
+			set path = $commands[$path][$word]
+		}}
+	}}
+	set options = $options[$path]
+	set values = $values[$path]
+
+	# Values attached with an equal sign
+	if (and (str:has-prefix $current '-') (str:contains $current '=')) {{
+		var key = (str:split &max=2 '=' $current | take 1)'='
+		if (has-key $values $key) {{
+			for value $values[$key] {{
+				edit:complex-candidate $key$value &display=$value
+			}}
+		}}
+		return
+	}}
+
+	# Values after the single-dash name of a pair
+	if (has-key $values $previous) {{
+		for value $values[$previous] {{
+			put $value
+		}}
+		return
+	}}
+
+	if (str:has-prefix $current '-') {{
+		for option $options {{
+			edit:complex-candidate $option[name] &display=(str:trim-space $option[name]' '$option[help])
+		}}
+		return
+	}}
+
+	if (> (count $commands[$path]) 0) {{
+		keys $commands[$path]
+		return
+	}}
+
+	edit:complete-filename $current
+}}
+",
+			program = quote(program),
+			commands = commands,
+			options = options,
+			values = values,
+		)
+	}
+
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::{CLIParser, Schema, Shell, ValueType};
+
+	/// Values the Nushell helper of the program offers.
+	fn nushell_candidates(script: &str) -> Vec<String> {
+		let helper = &script[..script.find("export extern").unwrap()];
+		helper.split("{ value: \"").skip(1)
+			.map(|rest| rest[..rest.find('"').unwrap()].to_string())
+			.collect()
+	}
+
+	#[test]
+	fn nushell_candidates_parse_back() {
+		let schema = Schema::new()
+			.program("mytool")
+			.flag("verbose").short("v")
+			.pair("format").one_of(["json", "yaml"])
+			.pair("output").value_type(ValueType::Path);
+
+		let candidates = nushell_candidates(&schema.completions(Shell::Nushell));
+		assert!(candidates.contains(&"-verbose".to_string()));
+		assert!(candidates.contains(&"--format=json".to_string()));
+
+		// A completed command line, filling in the values left open
+		let line: Vec<String> = candidates.iter()
+			.filter(|candidate| !candidate.starts_with("--format=yaml") && !candidate.starts_with("--version"))
+			.map(|candidate| match candidate.ends_with('=') {
+				true => format!("{}out.txt", candidate),
+				false => candidate.clone(),
+			})
+			.chain(["input.txt".to_string()])
+			.collect();
+
+		let parser = CLIParser::new().schema(schema).parse_str(&line.join(" ")).unwrap();
+		assert!(parser.flag("verbose"));
+		assert_eq!(parser.count("verbose"), 2);
+		assert_eq!(parser.pair("format"), Some("json"));
+		assert_eq!(parser.pair("output"), Some("out.txt"));
+		assert_eq!(parser.posits, vec!["input.txt"]);
+	}
+
+}
@@ -0,0 +1,456 @@
+use crate::WarningHook;
+
+
+/// **Parsing behavior switches**.
+/// 
+/// Everything is off by default, so that a bare [`CLIParser::new()`](crate::CLIParser::new) follows the plain
+/// 0 / 1 / 2 dashes syntax. Apply it with [`CLIParser::config`](crate::CLIParser::config).
+/// 
+/// ```
+/// use cliparser::{CLIParser, ParserConfig};
+/// 
+/// let parser = CLIParser::new().config(ParserConfig {
+///     response_files: true,
+///     ..ParserConfig::default()
+/// });
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParserConfig {
+
+	/// **Response files**.
+	/// 
+	/// Arguments of the form `@path` are replaced by the arguments listed in that file, one per line.
+	/// Surrounding whitespace is trimmed, while blank lines and lines starting with `#` are skipped.
+	/// Response files may reference other response files, up to a depth of 16.
+	/// 
+	/// Example:
+	/// ```bash
+	/// ./my_program @path/to/args.txt
+	/// ```
+	pub response_files: bool,
+
+	/// **Environment variables prefix**.
+	/// 
+	/// When set, environment variables starting with this prefix fill in any arguments missing from the command line.
+	/// The rest of the variable name, lowercased, becomes the key, with underscores matching the dashes of declared names.
+	/// Values `1` and `true` turn it into a flag, `0`, `false` and empty values are ignored, and anything else turns it into a key - value pair.
+	/// Declared flags accept any [boolean](crate::ValueType::Bool) instead, and declared pairs any value.
+	/// 
+	/// Example, with prefix `MYAPP_`:
+	/// ```bash
+	/// MYAPP_DEBUG_LEVEL=2 MYAPP_VERBOSE=1 ./my_program
+	/// ```
+	pub env_prefix: Option<String>,
+
+	/// **Standard input marker**.
+	/// 
+	/// When set, the first argument equal to this marker (typically `-`) is replaced by the whitespace
+	/// separated arguments read from the standard input. Any further markers are dropped.
+	/// 
+	/// Example, with marker `-`:
+	/// ```bash
+	/// generate_args | ./my_program -
+	/// ```
+	pub stdin_marker: Option<String>,
+
+	/// **Space separated pair values**.
+	/// 
+	/// Key - value pairs without an equal sign take their value from the next argument, unless it starts with a dash.
+	/// 
+	/// Example:
+	/// ```bash
+	/// ./my_program --output result.txt
+	/// ```
+	pub space_separated_values: bool,
+
+	/// **Clustered flags**.
+	/// 
+	/// Single-dash arguments of several characters are split into one flag per character, POSIX style.
+	/// A character declared in the [`Schema`](crate::Schema) as the single-dash name of a pair takes
+	/// the rest of the argument, or the next argument, as its value.
+	/// 
+	/// Example, equivalent to `-x -v -f archive.tar` when `f` is declared as a pair:
+	/// ```bash
+	/// ./my_program -xvf archive.tar
+	/// ```
+	pub cluster_short_flags: bool,
+
+	/// **Negative numbers**.
+	/// 
+	/// Single-dash arguments that are numeric literals are positional arguments instead of flags.
+	/// They may also serve as [space separated values](ParserConfig::space_separated_values).
+	/// 
+	/// Example, with `-5` and `-2.5e3` positional:
+	/// ```bash
+	/// ./calc -5 3 -2.5e3
+	/// ```
+	pub negative_numbers: bool,
+
+	/// **POSIX ordering**.
+	/// 
+	/// The first positional argument ends option parsing, as if a `--` separator preceded it.
+	/// Everything after it is positional, which lets wrapper programs forward the command line of another program.
+	/// Response files and the standard input marker are expanded before parsing, so they are still replaced there.
+	/// 
+	/// Example, with `-q` a flag and `grep`, `-r` and `TODO` positional:
+	/// ```bash
+	/// ./my_wrapper -q grep -r TODO
+	/// ```
+	pub posix_ordering: bool,
+
+	/// **Passthrough arguments**.
+	/// 
+	/// Arguments after a bare `--` separator are stored verbatim in [`CLIParser::passthrough`](crate::CLIParser::passthrough)
+	/// instead of [`CLIParser::posits`](crate::CLIParser::posits), ready to be forwarded to another program.
+	/// 
+	/// Example, with `input.txt` positional and `-v` and `out.txt` passed through:
+	/// ```bash
+	/// ./my_program input.txt -- -v out.txt
+	/// ```
+	pub passthrough: bool,
+
+	/// **Syntax style**.
+	/// 
+	/// [`SyntaxStyle::Windows`] additionally accepts slash-prefixed flags and pairs, separated by a colon or an equal sign.
+	/// 
+	/// Example:
+	/// ```bash
+	/// my_program.exe /verbose /out:file.txt
+	/// ```
+	pub syntax: SyntaxStyle,
+
+	/// **Flag prefixes**.
+	/// 
+	/// Prefixes marking an argument as a flag. Defaults to a single dash line.
+	/// When several prefixes match an argument, the longest one wins.
+	/// 
+	/// Example, with flag prefixes `-` and `+`:
+	/// ```bash
+	/// ./my_program -verbose +feature
+	/// ```
+	pub flag_prefixes: Vec<String>,
+
+	/// **Pair prefixes**.
+	/// 
+	/// Prefixes marking an argument as a key - value pair. Defaults to a double dash line.
+	/// 
+	/// Example, with pair prefix `:`:
+	/// ```bash
+	/// ./my_program :debug_level=2
+	/// ```
+	pub pair_prefixes: Vec<String>,
+
+	/// **Negation prefixes**.
+	/// 
+	/// Prefixes turning a flag off, for `+feature` / `-feature` style toggles. None by default.
+	/// A flag turned off stays off, even if the environment or a configuration file sets it.
+	/// 
+	/// Example, with flag prefix `+` and negation prefix `-`:
+	/// ```bash
+	/// ./my_program +feature -feature
+	/// ```
+	pub negation_prefixes: Vec<String>,
+
+	/// **Long option abbreviations**.
+	/// 
+	/// Keys of key - value pairs, and declared flags toggled with `--flag` or `--no-flag`, may be abbreviated
+	/// to any prefix that only one argument declared in the [`Schema`](crate::Schema) starts with, GNU style.
+	/// 
+	/// Example, equivalent to `--verbose --output=file.txt`:
+	/// ```bash
+	/// ./my_program --verb --out=file.txt
+	/// ```
+	pub long_abbreviations: bool,
+
+	/// **Subcommand abbreviations**.
+	/// 
+	/// [Subcommands](crate::Schema::command) may be abbreviated to any prefix that only one of them starts with.
+	/// A prefix that several of them start with is rejected with [`CLIError::AmbiguousCommand`](crate::CLIError::AmbiguousCommand).
+	/// 
+	/// Example, equivalent to `git checkout main`:
+	/// ```bash
+	/// ./my_program che main
+	/// ```
+	pub command_abbreviations: bool,
+
+	/// **Case insensitive keys**.
+	/// 
+	/// Flag names and pair keys are lowercased when stored, so that any capitalization resolves to the same entry.
+	/// Arguments declared in the [`Schema`](crate::Schema) should then use lowercase names.
+	/// 
+	/// Example, all storing the `debug_level` pair:
+	/// ```bash
+	/// ./my_program --Debug_Level=2 --DEBUG_LEVEL=2 --debug_level=2
+	/// ```
+	pub case_insensitive: bool,
+
+	/// **Duplicates policy**.
+	/// 
+	/// What to do when a flag or a key - value pair is given more than once. Defaults to keeping the last value.
+	/// Repeated flags are always [counted](crate::CLIParser::count), unless the policy rejects them.
+	/// Declared arguments can override it with [`Schema::duplicates`](crate::Schema::duplicates).
+	/// 
+	/// Example, with a duplicate `include` key and a duplicate `v` flag:
+	/// ```bash
+	/// ./my_program --include=a --include=b -v -v
+	/// ```
+	pub duplicates: DuplicatePolicy,
+
+	/// **Property defines**.
+	/// 
+	/// Flag arguments starting with the [define prefix](ParserConfig::define_prefix) and containing an equal sign
+	/// are key - value pairs instead, JVM style.
+	/// 
+	/// Example, storing the `log.level` pair:
+	/// ```bash
+	/// ./my_program -Dlog.level=debug
+	/// ```
+	pub defines: bool,
+
+	/// **Define prefix**.
+	/// 
+	/// What marks a flag argument as a [property define](ParserConfig::defines). Defaults to `D`.
+	pub define_prefix: String,
+
+	/// **Unicode dashes normalization**.
+	/// 
+	/// Unicode dashes at the start of arguments, as pasted from documents and chats, are replaced with ASCII dash lines.
+	/// Em dashes count as two dash lines, every other kind of dash as one.
+	/// Each replacement is reported as a [`Warning::DashesNormalized`](crate::Warning::DashesNormalized).
+	/// 
+	/// Example, equivalent to `-flag --key=value`:
+	/// ```bash
+	/// ./my_program –flag —key=value
+	/// ```
+	pub normalize_dashes: bool,
+
+	/// **Empty values**.
+	/// 
+	/// Key - value pairs may have an empty value, explicitly setting the key to an empty string.
+	/// This is how a value coming from the environment or a configuration file gets cleared.
+	/// 
+	/// Example:
+	/// ```bash
+	/// ./my_program --tag=
+	/// ```
+	pub empty_values: bool,
+
+	/// **Truthy pairs**.
+	/// 
+	/// A key - value pair without an equal sign is stored with the value `true`, instead of throwing an error,
+	/// so that boolean options can all be read from [`CLIParser::pairs`](crate::CLIParser::pairs).
+	/// [Space separated values](ParserConfig::space_separated_values) take precedence, when there is one.
+	/// 
+	/// Example, storing `verbose` as `true`:
+	/// ```bash
+	/// ./my_program --verbose
+	/// ```
+	pub truthy_pairs: bool,
+
+	/// **Strict mode**.
+	/// 
+	/// Flags and pairs missing from the [schema](crate::Schema) throw a [`CLIError::UnknownArgument`](crate::CLIError::UnknownArgument)
+	/// error as soon as they are parsed, instead of being stored, suggesting the declared names they may be misspellings of.
+	/// Has no effect without a schema.
+	/// 
+	/// Example, failing on the misspelled `verbsoe`:
+	/// ```bash
+	/// ./my_program -verbsoe
+	/// ```
+	pub strict: bool,
+
+	/// **Program path in positional arguments**.
+	/// 
+	/// Keeps the program path in [`CLIParser::posits`](crate::CLIParser::posits) when parsing with
+	/// [`CLIParser::init`](crate::CLIParser::init), as version 0.1.0 did, on top of [`CLIParser::program`](crate::CLIParser::program).
+	/// Indexed [positional accessors](crate::CLIParser::posit) skip it either way.
+	/// 
+	/// Example, storing `./my_program` and `file.txt` as positional arguments:
+	/// ```bash
+	/// ./my_program file.txt
+	/// ```
+	pub program_in_posits: bool,
+
+	/// **Help style**.
+	/// 
+	/// Whether the help text shown for `--help` is colored, see [`HelpStyle`]. Either way, it is wrapped to the `COLUMNS` variable,
+	/// or to 80 characters without it, unless [detecting the width](ParserConfig::detect_width) of the terminal.
+	/// 
+	/// Example, printing monochrome help in CI logs with [`HelpStyle::Plain`]:
+	/// ```bash
+	/// ./my_program --help
+	/// ```
+	pub help_style: HelpStyle,
+
+	/// **Terminal width detection**.
+	/// 
+	/// Asks the terminal the standard output goes to for its width, to wrap the help text to, when the `COLUMNS` variable is not set.
+	/// Off by default, since it runs `stty size` on Unix every time help is shown.
+	/// 
+	/// Example, wrapping help to the full width of a wide terminal window, which shells do not export `COLUMNS` for:
+	/// ```bash
+	/// ./my_program --help
+	/// ```
+	pub detect_width: bool,
+
+	/// **Tilde expansion**.
+	/// 
+	/// Values of pairs declared as [paths](crate::ValueType::Path) starting with `~` or `~user` get it replaced by
+	/// the home directory of the current or the given user, as shells do for standalone arguments but not after an equal sign.
+	/// The home directory is `$HOME` on Unix and `%USERPROFILE%` on Windows. Values are left as is when it cannot be found.
+	/// 
+	/// Example, storing `/home/john/build` when run by `john`:
+	/// ```bash
+	/// ./my_program --out=~/build
+	/// ```
+	pub expand_tilde: bool,
+
+	/// **Environment variable interpolation**.
+	/// 
+	/// References to environment variables in the values of pairs, as `${NAME}` or `$NAME`, are replaced by their contents.
+	/// A double `$$` stands for a literal dollar sign. Off by default, see [`Interpolation`] for the handling of unknown variables.
+	/// 
+	/// Example, storing `/home/john/build` and `hi john` when run by `john`:
+	/// ```bash
+	/// ./my_program '--out=${HOME}/build' '--msg=hi $USER'
+	/// ```
+	pub interpolation: Interpolation,
+
+	/// **Warning hook**.
+	/// 
+	/// Called with every [`Warning`](crate::Warning) as soon as it occurs. Warnings are collected into
+	/// [`CLIParser::warnings`](crate::CLIParser::warnings) either way.
+	pub on_warning: Option<WarningHook>,
+}
+
+
+/// Handling of arguments given more than once, see [`ParserConfig::duplicates`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DuplicatePolicy {
+	/// Throw a [`CLIError::DuplicateArgument`](crate::CLIError::DuplicateArgument) error.
+	Error,
+	/// Keep the first value, ignoring the later ones with a [`Warning::DuplicateDiscarded`](crate::Warning::DuplicateDiscarded).
+	KeepFirst,
+	/// Keep the last value, overwriting the earlier ones with a [`Warning::DuplicateDiscarded`](crate::Warning::DuplicateDiscarded).
+	#[default]
+	KeepLast,
+	/// Keep every value in [`CLIParser::pairs_multi`](crate::CLIParser::pairs_multi), and the last one in `pairs`.
+	Collect,
+}
+
+
+/// Handling of environment variable references in values, see [`ParserConfig::interpolation`].
+/// 
+/// ```
+/// use cliparser::{CLIError, CLIParser, Interpolation, ParserConfig};
+/// 
+/// std::env::set_var("MYAPP_ROOT", "/srv/app");
+/// std::env::remove_var("MYAPP_MISSING");
+/// 
+/// let config = ParserConfig { interpolation: Interpolation::Strict, ..ParserConfig::default() };
+/// let parser = CLIParser::new()
+///     .config(config.clone())
+///     .parse_str("'--out=${MYAPP_ROOT}/build' '--price=$$5'")
+///     .unwrap();
+/// 
+/// assert_eq!(parser.pairs["out"], "/srv/app/build");
+/// assert_eq!(parser.pairs["price"], "$5");
+/// 
+/// let error = CLIParser::new().config(config).parse_str("--out=$MYAPP_MISSING").unwrap_err();
+/// assert!(matches!(error, CLIError::UnknownVariable(..)));
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Interpolation {
+	/// Values are stored as given.
+	#[default]
+	Off,
+	/// Unknown variables expand to nothing, like in shells.
+	Lenient,
+	/// Unknown variables throw a [`CLIError::UnknownVariable`](crate::CLIError::UnknownVariable) error.
+	Strict,
+}
+
+
+impl Default for ParserConfig {
+	fn default() -> Self {
+		Self {
+			response_files: false,
+			env_prefix: None,
+			stdin_marker: None,
+			space_separated_values: false,
+			cluster_short_flags: false,
+			negative_numbers: false,
+			posix_ordering: false,
+			passthrough: false,
+			syntax: SyntaxStyle::Unix,
+			flag_prefixes: vec!["-".to_string()],
+			pair_prefixes: vec!["--".to_string()],
+			negation_prefixes: Vec::new(),
+			long_abbreviations: false,
+			command_abbreviations: false,
+			case_insensitive: false,
+			duplicates: DuplicatePolicy::KeepLast,
+			defines: false,
+			define_prefix: "D".to_string(),
+			normalize_dashes: false,
+			empty_values: false,
+			truthy_pairs: false,
+			strict: false,
+			program_in_posits: false,
+			help_style: HelpStyle::Auto,
+			detect_width: false,
+			expand_tilde: false,
+			interpolation: Interpolation::Off,
+			on_warning: None,
+		}
+	}
+}
+
+
+/// What a prefix of [`ParserConfig`] marks an argument as.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Sigil {
+	Flag,
+	Pair,
+	Negation,
+}
+
+
+impl ParserConfig {
+
+	/// Finds the longest prefix the `argument` starts with, if any.
+	pub(crate) fn match_prefix(&self, argument: &str) -> Option<(Sigil, &str)> {
+		let flags = self.flag_prefixes.iter().map(|prefix| (Sigil::Flag, prefix));
+		let pairs = self.pair_prefixes.iter().map(|prefix| (Sigil::Pair, prefix));
+		let negations = self.negation_prefixes.iter().map(|prefix| (Sigil::Negation, prefix));
+
+		flags.chain(pairs).chain(negations)
+			.filter(|(_, prefix)| !prefix.is_empty() && argument.starts_with(prefix.as_str()))
+			.max_by_key(|(_, prefix)| prefix.len())
+			.map(|(sigil, prefix)| (sigil, prefix.as_str()))
+	}
+}
+
+
+/// Coloring of the help text, see [`ParserConfig::help_style`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum HelpStyle {
+	/// Colored when the standard output is a terminal, unless `NO_COLOR` is set, or whenever `CLICOLOR_FORCE` is set.
+	#[default]
+	Auto,
+	/// Always colored, with bold names and headings.
+	Colored,
+	/// Never colored.
+	Plain,
+}
+
+
+/// Families of command line syntaxes, see [`ParserConfig::syntax`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SyntaxStyle {
+	/// Dashes only: `-flag`, `--key=value`.
+	#[default]
+	Unix,
+	/// Dashes, plus `/flag`, `/key:value` and `/key=value`.
+	Windows,
+}
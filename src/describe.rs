@@ -0,0 +1,154 @@
+//! JSON description of the [`Schema`], for wrappers and completion engines that introspect the program.
+
+use crate::{ArgKind, ArgSpec, Schema, ValueType};
+
+
+/// Quotes `text` as a JSON string.
+fn string(text: &str) -> String {
+	let mut quoted = String::from("\"");
+	for c in text.chars() {
+		match c {
+			'"' => quoted.push_str("\\\""),
+			'\\' => quoted.push_str("\\\\"),
+			'\n' => quoted.push_str("\\n"),
+			'\r' => quoted.push_str("\\r"),
+			'\t' => quoted.push_str("\\t"),
+			c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+			c => quoted.push(c),
+		}
+	}
+	quoted.push('"');
+	quoted
+}
+
+
+/// A JSON string, or `null`.
+fn optional(text: Option<&str>) -> String {
+	text.map(string).unwrap_or_else(|| "null".to_string())
+}
+
+
+/// A JSON array of strings.
+fn list(texts: &[String]) -> String {
+	format!("[{}]", texts.iter().map(|text| string(text)).collect::<Vec<String>>().join(","))
+}
+
+
+fn kind_name(kind: ArgKind) -> &'static str {
+	match kind {
+		ArgKind::Flag => "flag",
+		ArgKind::Pair => "pair",
+		ArgKind::Positional => "positional",
+	}
+}
+
+
+fn type_name(value_type: ValueType) -> &'static str {
+	match value_type {
+		ValueType::String => "string",
+		ValueType::Int => "int",
+		ValueType::Float => "float",
+		ValueType::Bool => "bool",
+		ValueType::Duration => "duration",
+		ValueType::Size => "size",
+		ValueType::Path => "path",
+	}
+}
+
+
+/// JSON object of a single argument.
+fn argument(spec: &ArgSpec) -> String {
+	let long = match spec.kind {
+		ArgKind::Flag => Some(format!("-{}", spec.name)),
+		ArgKind::Pair => Some(format!("--{}", spec.name)),
+		ArgKind::Positional => None,
+	};
+	let delimiter = spec.delimiter.map(String::from);
+
+	let fields = [
+		("name", string(&spec.name)),
+		("kind", string(kind_name(spec.kind))),
+		("long", optional(long.as_deref())),
+		("short", optional(spec.short.as_ref().map(|short| format!("-{}", short)).as_deref())),
+		("aliases", list(&spec.aliases)),
+		("type", match spec.kind {
+			ArgKind::Flag => "null".to_string(),
+			_ => string(type_name(spec.value_type)),
+		}),
+		("value_parser", optional(spec.value_parser.as_deref())),
+		("choices", list(&spec.choices)),
+		("delimiter", optional(delimiter.as_deref())),
+		("required", spec.required.to_string()),
+		("variadic", spec.variadic.to_string()),
+		("default", optional(spec.default.as_deref())),
+		("env", optional(spec.env.as_deref())),
+		("help", optional(spec.help.as_deref())),
+		("heading", optional(spec.heading.as_deref())),
+		("requires", list(&spec.requires)),
+		("conflicts", list(&spec.conflicts)),
+		("deprecated", (spec.deprecated || spec.replacement.is_some()).to_string()),
+		("replacement", optional(spec.replacement.as_deref())),
+		("hidden", spec.hidden.to_string()),
+	];
+
+	format!("{{{}}}", fields.iter().map(|(key, value)| format!("{}:{}", string(key), value)).collect::<Vec<String>>().join(","))
+}
+
+
+impl Schema {
+
+	/// Every declared argument with its metadata, as a single line of JSON, for GUI wrappers and external completion engines.
+	/// 
+	/// The parser also prints it for `--help=json` and `-help=json`, returning it in a [`CLIError::HelpRequested`](crate::CLIError::HelpRequested) error.
+	/// The object holds the `name`, `version`, `about` and `usage` of the program, and its `arguments` in declaration order,
+	/// including [hidden](Schema::hidden) ones, marked as such. Missing values are `null`.
+	/// [Subcommands](Schema::command) are described the same way in `commands`.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema, ValueType};
+	/// 
+	/// let schema = Schema::new()
+	///     .program("mytool")
+	///     .pair("threads").short("j").value_type(ValueType::Int).default_value("4").help("Worker count");
+	/// 
+	/// let json = schema.describe_json();
+	/// 
+	/// assert!(json.starts_with(r#"{"name":"mytool","aliases":[],"version":null,"about":null,"usage":"mytool [OPTIONS]","arguments":[{"name":"threads","kind":"pair","long":"--threads","short":"-j","#));
+	/// assert!(json.contains(r#""type":"int","#));
+	/// assert!(json.contains(r#""default":"4","env":null,"help":"Worker count","#));
+	/// 
+	/// let error = CLIParser::new().schema(schema).parse_str("--help=json").unwrap_err();
+	/// assert_eq!(error, CLIError::HelpRequested(format!("{}\n", json)));
+	/// ```
+	pub fn describe_json(&self) -> String {
+		let program = self.display_name();
+		self.describe(&program, &[], &program)
+	}
+
+	/// JSON object of the schema of the program or subcommand `name`, invoked as `program`.
+	fn describe(&self, name: &str, aliases: &[String], program: &str) -> String {
+		let arguments: Vec<String> = self.args().iter().map(argument).collect();
+		let commands: Vec<String> = self.commands.iter()
+			.map(|command| command.schema.describe(&command.name, &command.aliases, &format!("{} {}", program, command.name)))
+			.collect();
+
+		let fields = [
+			("name", string(name)),
+			("aliases", list(aliases)),
+			("version", optional(self.version.as_deref())),
+			("about", optional(self.about.as_deref())),
+			("usage", string(&self.synopsis(program))),
+			("arguments", format!("[{}]", arguments.join(","))),
+			("commands", format!("[{}]", commands.join(","))),
+			("default_command", optional(self.default_command.as_deref())),
+		];
+
+		format!("{{{}}}", fields.iter().map(|(key, value)| format!("{}:{}", string(key), value)).collect::<Vec<String>>().join(","))
+	}
+
+	/// Whether `argument` asks for the [JSON description](Schema::describe_json) of the schema.
+	pub(crate) fn is_json_help_request(&self, argument: &str) -> bool {
+		matches!(argument, "--help=json" | "-help=json") && self.find("help").is_none()
+	}
+
+}
@@ -0,0 +1,153 @@
+//! Environment variables as a fallback source of arguments.
+
+use crate::{ArgKind, CLIParser, CLIError, ValueType};
+use crate::sources::{Source, SourceValue};
+use crate::values::parse_bool;
+
+
+/// Reads the value of a declared flag, which is off when empty.
+fn flag_value(name: &str, value: String) -> Result<SourceValue, CLIError> {
+	if value.is_empty() {
+		return Ok(SourceValue::Flag(false));
+	}
+	match parse_bool(&value) {
+		Some(on) => Ok(SourceValue::Flag(on)),
+		None => Err(CLIError::InvalidValue { key: name.to_string(), value, expected: ValueType::Bool }),
+	}
+}
+
+
+impl CLIParser {
+
+	/// Fills in flags and pairs from the environment variables starting with `prefix`.
+	/// 
+	/// Arguments already given on the command line are left untouched. Declared arguments keep their kind,
+	/// with flags read like [booleans](crate::ValueType::Bool). Otherwise `1` and `true` turn flags on,
+	/// empty values, `0` and `false` turn them off, and anything else is a pair.
+	/// Will throw error if a value does not fit its declared [type](crate::ValueType).
+	pub(crate) fn merge_env(&mut self, prefix: &str) -> Result<(), CLIError> {
+		let vars = std::env::vars_os()
+			.filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)));
+
+		for (key, value) in vars {
+			let name = match key.strip_prefix(prefix) {
+				Some(name) if !name.is_empty() => self.env_name(name),
+				_ => continue,
+			};
+
+			// Declared arguments keep their kind, whatever their value looks like
+			let kind = self.schema.as_ref().and_then(|schema| schema.find(&name)).map(|spec| spec.kind);
+			let value = match (kind, value.to_lowercase().as_str()) {
+				(Some(ArgKind::Flag), _) => flag_value(&name, value)?,
+				(Some(_), _) => SourceValue::Pair(value),
+				(None, "1" | "true") => SourceValue::Flag(true),
+				(None, "" | "0" | "false") => SourceValue::Flag(false),
+				(None, _) => SourceValue::Pair(value),
+			};
+
+			self.fill_missing(name, value, Source::Environment)?;
+		}
+
+		Ok(())
+	}
+
+	/// Key of the variable `name`, once the prefix is stripped. Underscores stand for the dashes of declared names.
+	fn env_name(&self, name: &str) -> String {
+		let name = name.to_lowercase();
+		let dashed = name.replace('_', "-");
+		match self.schema.as_ref() {
+			Some(schema) if schema.find(&name).is_none() && schema.find(&dashed).is_some() => dashed,
+			_ => name,
+		}
+	}
+
+	/// Fills in flags and pairs from the environment variables [declared](crate::Schema::env) for them.
+	/// 
+	/// Arguments already given on the command line are left untouched, and flags are read like [booleans](crate::ValueType::Bool).
+	/// Will throw error if a value does not fit its declared [type](crate::ValueType).
+	pub(crate) fn merge_declared_env(&mut self) -> Result<(), CLIError> {
+		let vars: Vec<(String, SourceValue)> = match &self.schema {
+			Some(schema) => schema.args().iter()
+				.filter(|spec| spec.kind != ArgKind::Positional)
+				.filter_map(|spec| {
+					let value = std::env::var(spec.env.as_ref()?).ok()?;
+					let value = match spec.kind {
+						ArgKind::Flag => flag_value(&spec.name, value),
+						_ => Ok(SourceValue::Pair(value)),
+					};
+					Some(value.map(|value| (spec.name.clone(), value)))
+				})
+				.collect::<Result<_, CLIError>>()?,
+			None => return Ok(()),
+		};
+
+		for (name, value) in vars {
+			self.fill_missing(name, value, Source::Environment)?;
+		}
+
+		Ok(())
+	}
+
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::{CLIError, CLIParser, Schema, ValueType};
+
+	#[test]
+	fn declared_pairs_stay_pairs() {
+		std::env::set_var("CLIPARSER_ENV_TEST_RETRIES", "1");
+		std::env::set_var("CLIPARSER_ENV_TEST_NAME", "true");
+		std::env::set_var("CLIPARSER_ENV_TEST_QUIET", "1");
+
+		let schema = Schema::new()
+			.pair("retries").value_type(ValueType::Int)
+			.pair("name");
+		let parser = CLIParser::new().schema(schema).env_prefix("CLIPARSER_ENV_TEST_").parse_str("").unwrap();
+
+		assert_eq!(parser.pair("retries"), Some("1"));
+		assert_eq!(parser.pair("name"), Some("true"));
+		assert!(!parser.flag("retries"));
+		assert!(parser.flag("quiet"));
+	}
+
+	#[test]
+	fn declared_flags_stay_flags() {
+		std::env::set_var("CLIPARSER_ENV_FLAG_TEST_COLOR", "yes");
+		std::env::set_var("CLIPARSER_ENV_FLAG_TEST_QUIET", "off");
+
+		let schema = Schema::new().flag("color").flag("quiet");
+		let parser = CLIParser::new().schema(schema).env_prefix("CLIPARSER_ENV_FLAG_TEST_").parse_str("").unwrap();
+
+		assert!(parser.flag("color"));
+		assert!(!parser.has_pair("color"));
+		assert!(!parser.flag("quiet"));
+	}
+
+	#[test]
+	fn declared_flags_reject_other_values() {
+		std::env::set_var("CLIPARSER_ENV_BAD_FLAG_TEST_COLOR", "maybe");
+
+		let schema = Schema::new().flag("color");
+		let result = CLIParser::new().schema(schema).env_prefix("CLIPARSER_ENV_BAD_FLAG_TEST_").parse_str("");
+
+		assert!(matches!(result, Err(CLIError::InvalidValue { ref key, expected: ValueType::Bool, .. }) if key == "color"));
+	}
+
+	#[test]
+	fn underscores_match_dashes() {
+		std::env::set_var("CLIPARSER_ENV_DASH_TEST_DRY_RUN", "1");
+		std::env::set_var("CLIPARSER_ENV_DASH_TEST_LOG_FILE", "out.log");
+		std::env::set_var("CLIPARSER_ENV_DASH_TEST_MAX_DEPTH", "3");
+
+		let schema = Schema::new().flag("dry-run").pair("log-file").pair("max_depth");
+		let parser = CLIParser::new().schema(schema).env_prefix("CLIPARSER_ENV_DASH_TEST_").parse_str("").unwrap();
+
+		assert!(parser.flag("dry-run"));
+		assert_eq!(parser.pair("log-file"), Some("out.log"));
+		assert_eq!(parser.pair("max_depth"), Some("3"));
+	}
+
+}
@@ -0,0 +1,104 @@
+//! Subcommands provided by other executables, git style, such as `mytool-foo` on the `PATH` for `mytool foo`.
+
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+use crate::{CLIParser, Schema};
+
+
+/// **External subcommand**, an executable found on the `PATH` for a subcommand that is not declared.
+/// 
+/// See [`Schema::external_commands`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExternalCommand {
+	/// The subcommand as given, `foo` for `mytool foo`.
+	pub name: String,
+	/// Where the executable was found, `mytool-foo` in a directory of the `PATH`.
+	pub path: PathBuf,
+	/// Every argument after the subcommand, verbatim.
+	pub args: Vec<String>,
+}
+
+
+impl ExternalCommand {
+
+	/// Runs the executable with the arguments, waiting for it to finish.
+	pub fn spawn(&self) -> std::io::Result<ExitStatus> {
+		std::process::Command::new(&self.path).args(&self.args).status()
+	}
+
+}
+
+
+/// Executable `name` in one of the directories of the `PATH`, if there is one.
+fn find_on_path(name: &str) -> Option<PathBuf> {
+	let name = format!("{}{}", name, std::env::consts::EXE_SUFFIX);
+	std::env::split_paths(&std::env::var_os("PATH")?)
+		.map(|dir| dir.join(&name))
+		.find(|path| path.is_file())
+}
+
+
+impl Schema {
+
+	/// Looks up undeclared subcommands on the `PATH`, as `<program>-<subcommand>` executables, to support plugins.
+	/// 
+	/// A first positional argument that names no declared subcommand is then looked up, before falling back to the
+	/// [default subcommand](Schema::default_command). Once found, every argument after it is kept verbatim
+	/// for the [external subcommand](CLIParser::external_command), which the program can [spawn](ExternalCommand::spawn).
+	/// Nested subcommands look up `<program>-<subcommand>-<plugin>` executables.
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	/// 
+	/// let dir = std::env::temp_dir().join("cliparser-external-doctest");
+	/// std::fs::create_dir_all(&dir).unwrap();
+	/// std::fs::write(dir.join(format!("mytool-lint{}", std::env::consts::EXE_SUFFIX)), "").unwrap();
+	/// std::env::set_var("PATH", &dir);
+	/// 
+	/// let schema = Schema::new()
+	///     .program("mytool")
+	///     .flag("verbose")
+	///     .command("build", Schema::new())
+	///     .external_commands();
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("-verbose lint --fix src").unwrap();
+	/// let external = parser.external_command().unwrap();
+	/// 
+	/// assert_eq!(external.name, "lint");
+	/// assert_eq!(external.args, ["--fix", "src"]);
+	/// assert!(external.path.starts_with(&dir));
+	/// assert_eq!(parser.validate(), Ok(()));
+	/// ```
+	pub fn external_commands(mut self) -> Self {
+		self.external_commands = true;
+		self
+	}
+
+}
+
+
+impl CLIParser {
+
+	/// The external subcommand given on the command line, if any. See [`Schema::external_commands`].
+	pub fn external_command(&self) -> Option<&ExternalCommand> {
+		self.external.as_ref()
+	}
+
+	/// Starts collecting the arguments of the external subcommand named by the positional `argument`, if it is on the `PATH`.
+	pub(crate) fn enter_external_command(&mut self, argument: &str) -> bool {
+		if !self.schema.as_ref().is_some_and(|schema| schema.external_commands) || argument.is_empty() || argument.contains(std::path::is_separator) {
+			return false;
+		}
+
+		let executable = format!("{}-{}", self.program_name().replace(' ', "-"), argument);
+		match find_on_path(&executable) {
+			Some(path) => {
+				self.external = Some(ExternalCommand { name: argument.to_string(), path, args: Vec::new() });
+				true
+			}
+			None => false,
+		}
+	}
+
+}
@@ -0,0 +1,452 @@
+//! Help and version texts generated from the [`Schema`], shown for `--help`, `-help` and `-h`, and for `--version`, `-version` and `-V`.
+
+use crate::{terminal, ArgKind, ArgSpec, CLIParser, HelpStyle, Schema, ValueType};
+use crate::commands::Command;
+
+
+/// Placeholder of the value of a pair, after its type or choices.
+pub(crate) fn placeholder(spec: &ArgSpec) -> String {
+	if !spec.choices.is_empty() {
+		return format!("<{}>", spec.choices.join("|"));
+	}
+	if let Some(name) = &spec.value_parser {
+		return format!("<{}>", name);
+	}
+
+	let name = match spec.value_type {
+		ValueType::String => "value",
+		ValueType::Int => "int",
+		ValueType::Float => "number",
+		ValueType::Bool => "bool",
+		ValueType::Duration => "duration",
+		ValueType::Size => "size",
+		ValueType::Path => "path",
+	};
+	format!("<{}>", name)
+}
+
+
+/// Left column of the help text, the names of an argument.
+fn names(spec: &ArgSpec) -> String {
+	let long = match spec.kind {
+		ArgKind::Flag => format!("-{}", spec.name),
+		ArgKind::Pair => format!("--{}={}", spec.name, placeholder(spec)),
+		ArgKind::Positional => return positional_name(spec),
+	};
+
+	match &spec.short {
+		Some(short) => format!("-{}, {}", short, long),
+		None => long,
+	}
+}
+
+
+/// Name of a positional argument in angle brackets, or square ones when optional, followed by dots when variadic.
+pub(crate) fn positional_name(spec: &ArgSpec) -> String {
+	let dots = if spec.variadic { "..." } else { "" };
+	match spec.required {
+		true => format!("<{}>{}", spec.name, dots),
+		false => format!("[{}]{}", spec.name, dots),
+	}
+}
+
+
+/// Right column of the help text, the help of an argument followed by its settings.
+pub(crate) fn description(spec: &ArgSpec) -> String {
+	let mut notes: Vec<String> = Vec::new();
+
+	if let Some(default) = &spec.default {
+		notes.push(format!("[default: {}]", default));
+	}
+	if let Some(env) = &spec.env {
+		notes.push(format!("[env: {}]", env));
+	}
+	let aliases: Vec<&str> = spec.aliases.iter()
+		.filter(|alias| !spec.deprecated_aliases.contains(alias))
+		.map(String::as_str)
+		.collect();
+	if !aliases.is_empty() {
+		notes.push(format!("[aliases: {}]", aliases.join(", ")));
+	}
+	match (&spec.replacement, spec.deprecated) {
+		(Some(replacement), _) => notes.push(format!("[deprecated, use {}]", replacement)),
+		(None, true) => notes.push("[deprecated]".to_string()),
+		(None, false) => (),
+	}
+
+	spec.help.iter().cloned().chain(notes).collect::<Vec<String>>().join(" ")
+}
+
+
+/// Name of the running executable, without its directory and extension.
+fn executable_stem() -> Option<String> {
+	let path = std::env::args_os().next()?;
+	Some(std::path::Path::new(&path).file_stem()?.to_string_lossy().into_owned())
+}
+
+
+/// Narrowest column of descriptions worth wrapping to, below which they run on.
+const MIN_WRAP: usize = 20;
+
+
+/// Splits `text` into lines of at most `width` characters, at spaces. Longer words get lines of their own.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+	let mut lines: Vec<String> = Vec::new();
+	let mut line = String::new();
+
+	for word in text.split_whitespace() {
+		if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+			lines.push(std::mem::take(&mut line));
+		}
+		if !line.is_empty() {
+			line.push(' ');
+		}
+		line.push_str(word);
+	}
+	lines.push(line);
+
+	lines
+}
+
+
+/// How the help text is laid out.
+struct Layout {
+	colored: bool,
+	/// Width to wrap descriptions to, if any.
+	width: Option<usize>,
+}
+
+
+impl Layout {
+
+	fn bold(&self, text: &str) -> String {
+		match self.colored {
+			true => format!("\x1b[1m{}\x1b[0m", text),
+			false => text.to_string(),
+		}
+	}
+
+	fn heading(&self, title: &str) -> String {
+		match self.colored {
+			true => format!("\x1b[1;4m{}:\x1b[0m", title),
+			false => format!("{}:", title),
+		}
+	}
+
+	/// Lines of a section of the help text, with the descriptions aligned after the widest names.
+	fn section(&self, title: &str, rows: &[(String, String)], names_width: usize) -> String {
+		let indent = 2 + names_width + 2;
+		let wrap_width = self.width.map(|width| width.saturating_sub(indent)).filter(|&width| width >= MIN_WRAP);
+
+		let mut text = format!("{}\n", self.heading(title));
+		for (names, description) in rows {
+			let lines = match wrap_width {
+				Some(width) => wrap(description, width),
+				None => vec![description.clone()],
+			};
+
+			let padding = " ".repeat(names_width - names.chars().count());
+			let line = format!("  {}{}  {}", self.bold(names), padding, lines[0]);
+			text.push_str(line.trim_end());
+			text.push('\n');
+
+			for line in &lines[1..] {
+				text.push_str(&format!("{}{}\n", " ".repeat(indent), line));
+			}
+		}
+		text
+	}
+
+}
+
+
+impl Schema {
+
+	/// One-line synopsis of the program, listing the required flags and pairs and the positional arguments.
+	/// 
+	/// Optional flags and pairs are summed up as `[OPTIONS]`, and [hidden](Schema::hidden) arguments are left out.
+	/// The program name is the [declared](Schema::program) one, or the name of the running executable.
+	/// 
+	/// ```
+	/// use cliparser::{Schema, ValueType};
+	/// 
+	/// let schema = Schema::new()
+	///     .program("myprog")
+	///     .flag("verbose")
+	///     .pair("output").value_type(ValueType::Path).required()
+	///     .positional("input").required().variadic()
+	///     .positional("extra");
+	/// 
+	/// assert_eq!(schema.usage(), "myprog [OPTIONS] --output=<path> <input>... [extra]");
+	/// ```
+	pub fn usage(&self) -> String {
+		self.synopsis(&self.display_name())
+	}
+
+	/// The [declared](Schema::program) program name, or the name of the running executable.
+	pub(crate) fn display_name(&self) -> String {
+		self.program.clone().or_else(executable_stem).unwrap_or_else(|| "program".to_string())
+	}
+
+	/// Synopsis of [`Schema::usage`], for the given program name.
+	pub(crate) fn synopsis(&self, program: &str) -> String {
+		let mut usage = program.to_string();
+
+		let visible = |spec: &&ArgSpec| !spec.hidden;
+		if self.options().filter(visible).any(|spec| !spec.required) {
+			usage.push_str(" [OPTIONS]");
+		}
+		for spec in self.options().filter(visible).filter(|spec| spec.required) {
+			usage.push(' ');
+			usage.push_str(names(spec).rsplit(", ").next().unwrap_or_default());
+		}
+		for spec in self.positionals().filter(visible) {
+			usage.push(' ');
+			usage.push_str(&positional_name(spec));
+		}
+		if !self.commands.is_empty() {
+			usage.push_str(" [COMMAND]");
+		}
+
+		usage
+	}
+
+	/// Help text of the declared arguments, as shown by the parser for `--help`, `-help` and `-h`.
+	/// 
+	/// Lists the [description](Schema::about) and usage, then the [subcommands](Schema::command), the positional arguments and the flags and pairs with their [help](Schema::help),
+	/// default values, environment variables, aliases and deprecation, grouped by [heading](Schema::heading).
+	/// Then come the [examples](Schema::example) and the [closing text](Schema::after_help). [Hidden](Schema::hidden) arguments are left out,
+	/// and a [template](Schema::help_template) can change the layout.
+	/// 
+	/// ```
+	/// use cliparser::{Schema, ValueType};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("verbose").short("v").help("Print more details")
+	///     .pair("output").short("o").value_type(ValueType::Path).default_value("out.bin").help("Where to write")
+	///     .pair("format").one_of(["json", "yaml"])
+	///     .pair("token").hidden()
+	///     .positional("input").required().help("File to read");
+	/// 
+	/// assert_eq!(schema.render_help("mytool"), "\
+	/// Usage: mytool [OPTIONS] <input>
+	///
+	/// Arguments:
+	///   <input>               File to read
+	///
+	/// Options:
+	///   -v, -verbose          Print more details
+	///   -o, --output=<path>   Where to write [default: out.bin]
+	///   --format=<json|yaml>
+	///   -h, --help            Print help
+	/// ");
+	/// ```
+	pub fn render_help(&self, program: &str) -> String {
+		self.render_help_with(program, HelpStyle::Plain, None)
+	}
+
+	/// Help text like [`Schema::render_help`], colored in the given `style` and with descriptions wrapped to `width` characters.
+	/// 
+	/// Colors make names bold and headings bold and underlined, with ANSI escapes.
+	/// Without a width, or if it is too narrow, descriptions are not wrapped.
+	/// 
+	/// ```
+	/// use cliparser::{HelpStyle, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("verbose").help("Print the name of every file as it is processed, and a summary at the end");
+	/// 
+	/// assert_eq!(schema.render_help_with("mytool", HelpStyle::Plain, Some(51)), "\
+	/// Usage: mytool [OPTIONS]
+	///
+	/// Options:
+	///   -verbose    Print the name of every file as it is
+	///               processed, and a summary at the end
+	///   -h, --help  Print help
+	/// ");
+	/// 
+	/// let colored = schema.render_help_with("mytool", HelpStyle::Colored, None);
+	/// assert!(colored.contains("\x1b[1;4mOptions:\x1b[0m\n  \x1b[1m-verbose\x1b[0m    Print"));
+	/// ```
+	pub fn render_help_with(&self, program: &str, style: HelpStyle, width: Option<usize>) -> String {
+		let layout = Layout { colored: terminal::colored(style), width };
+
+		let commands: Vec<(String, String)> = self.commands.iter()
+			.map(|command| (command.name.clone(), self.command_description(command)))
+			.collect();
+
+		let arguments: Vec<(String, String)> = self.positionals()
+			.filter(|spec| !spec.hidden)
+			.map(|spec| (names(spec), description(spec)))
+			.collect();
+
+		// Flags and pairs grouped by heading, the default one first and the others in order of first use
+		let mut groups: Vec<(&str, Vec<(String, String)>)> = vec![("Options", Vec::new())];
+		for spec in self.options().filter(|spec| !spec.hidden) {
+			let heading = spec.heading.as_deref().unwrap_or("Options");
+			let row = (names(spec), description(spec));
+			match groups.iter_mut().find(|(title, _)| *title == heading) {
+				Some((_, rows)) => rows.push(row),
+				None => groups.push((heading, vec![row])),
+			}
+		}
+		if self.find("help").is_none() {
+			let short = if self.find_short("h").is_none() { "-h, " } else { "" };
+			groups[0].1.push((format!("{}--help", short), "Print help".to_string()));
+		}
+		if self.version.is_some() && self.find("version").is_none() {
+			let short = if self.find_short("V").is_none() { "-V, " } else { "" };
+			groups[0].1.push((format!("{}--version", short), "Print version".to_string()));
+		}
+
+		let names_width = commands.iter()
+			.chain(&arguments)
+			.chain(groups.iter().flat_map(|(_, rows)| rows))
+			.map(|(names, _)| names.chars().count())
+			.max()
+			.unwrap_or(0);
+
+		let about = self.about.as_ref().map(|about| format!("{}\n", about)).unwrap_or_default();
+		let usage = format!("{} {}\n", layout.heading("Usage"), self.synopsis(program));
+		let commands = match commands.is_empty() {
+			true => String::new(),
+			false => layout.section("Commands", &commands, names_width),
+		};
+		let positionals = match arguments.is_empty() {
+			true => String::new(),
+			false => layout.section("Arguments", &arguments, names_width),
+		};
+		let options = groups.iter()
+			.filter(|(_, rows)| !rows.is_empty())
+			.map(|(title, rows)| layout.section(title, rows, names_width))
+			.collect::<Vec<_>>()
+			.join("\n");
+		let examples = match self.examples.is_empty() {
+			true => String::new(),
+			false => {
+				let mut text = format!("{}\n", layout.heading("Examples"));
+				for example in &self.examples {
+					text.push_str(&format!("  {}\n", example));
+				}
+				text
+			}
+		};
+		let after_help = self.after_help.as_ref().map(|text| format!("{}\n", text)).unwrap_or_default();
+
+		let template = match &self.help_template {
+			Some(template) => template,
+			None => {
+				let parts = [about, usage, commands, positionals, options, examples, after_help];
+				return parts.into_iter().filter(|part| !part.is_empty()).collect::<Vec<_>>().join("\n");
+			}
+		};
+
+		let placeholders = [
+			("{name}", program.to_string()),
+			("{version}", self.version.clone().unwrap_or_default()),
+			("{about}", about),
+			("{usage}", usage),
+			("{commands}", commands),
+			("{positionals}", positionals),
+			("{options}", options),
+			("{examples}", examples),
+			("{after-help}", after_help),
+		];
+		let mut help = template.clone();
+		for (placeholder, part) in placeholders {
+			help = help.replace(placeholder, part.trim_end_matches('\n'));
+		}
+		if !help.ends_with('\n') {
+			help.push('\n');
+		}
+		help
+	}
+
+	/// Version text, the program name and [version](Schema::version), then the commit hash if there is one.
+	/// 
+	/// `None` without a version.
+	pub fn render_version(&self, program: &str) -> Option<String> {
+		let mut text = format!("{} {}\n", program, self.version.as_ref()?);
+		if let Some(commit) = &self.commit {
+			text.push_str(&format!("commit {}\n", commit));
+		}
+		Some(text)
+	}
+
+	/// Description of the subcommand, followed by its aliases and whether it is the default one.
+	pub(crate) fn command_description(&self, command: &Command) -> String {
+		let mut notes = Vec::new();
+		if !command.aliases.is_empty() {
+			notes.push(format!("[aliases: {}]", command.aliases.join(", ")));
+		}
+		if self.default_command.as_ref() == Some(&command.name) {
+			notes.push("[default]".to_string());
+		}
+		let about = command.schema.about.iter().cloned();
+		about.chain(notes).collect::<Vec<String>>().join(" ")
+	}
+
+	/// Whether `argument` asks for the version, rather than being a declared argument of the same name.
+	pub(crate) fn is_version_request(&self, argument: &str) -> bool {
+		self.version.is_some() && match argument {
+			"--version" | "-version" => self.find("version").is_none(),
+			"-V" => self.find_short("V").is_none() && self.find("V").is_none(),
+			_ => false,
+		}
+	}
+
+	/// Whether `argument` asks for help, rather than being a declared argument of the same name.
+	pub(crate) fn is_help_request(&self, argument: &str) -> bool {
+		match argument {
+			"--help" | "-help" => self.find("help").is_none(),
+			"-h" => self.find_short("h").is_none() && self.find("h").is_none(),
+			_ => false,
+		}
+	}
+
+}
+
+
+impl CLIParser {
+
+	/// Help text of the [schema](CLIParser::schema), if there is one, in the [configured style](crate::ParserConfig::help_style).
+	/// See [`Schema::render_help_with`].
+	/// 
+	/// Parsing `--help`, `-help` or `-h` with a schema stops with a [`CLIError::HelpRequested`](crate::CLIError::HelpRequested) error
+	/// carrying this text, unless the schema declares arguments of these names. The program name is the [declared](Schema::program) one,
+	/// or else the [stem](CLIParser::program_stem) of the program path, or of the running executable when there is none.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new().flag("verbose").help("Print more details");
+	/// 
+	/// match CLIParser::new().schema(schema).parse_str("-verbose --help") {
+	///     Err(CLIError::HelpRequested(help)) => assert!(help.starts_with("Usage: ")),
+	///     _ => panic!("expected the help text"),
+	/// }
+	/// ```
+	pub fn help(&self) -> Option<String> {
+		let schema = self.schema.as_ref()?;
+		Some(schema.render_help_with(&self.program_name(), self.config.help_style, Some(terminal::width(self.config.detect_width))))
+	}
+
+	/// Version text of the [schema](CLIParser::schema), if it has a version. See [`Schema::render_version`].
+	pub fn version(&self) -> Option<String> {
+		self.schema.as_ref()?.render_version(&self.program_name())
+	}
+
+	/// Synopsis of the [schema](CLIParser::schema), if there is one. See [`Schema::usage`].
+	pub fn usage(&self) -> Option<String> {
+		Some(self.schema.as_ref()?.synopsis(&self.program_name()))
+	}
+
+	/// Name of the program in help texts, the declared one or else the [stem](CLIParser::program_stem) of the program path.
+	pub(crate) fn program_name(&self) -> String {
+		self.schema.as_ref().and_then(|schema| schema.program.clone())
+			.or_else(|| self.program_stem().map(String::from))
+			.or_else(executable_stem)
+			.unwrap_or_else(|| "program".to_string())
+	}
+
+}
@@ -0,0 +1,43 @@
+//! Home directory expansion for path values, see [`ParserConfig::expand_tilde`](crate::ParserConfig::expand_tilde).
+
+use std::path::PathBuf;
+
+
+/// Home directory of the current user.
+fn current_home() -> Option<PathBuf> {
+	let var = if cfg!(windows) { "USERPROFILE" } else { "HOME" };
+	std::env::var_os(var).filter(|home| !home.is_empty()).map(PathBuf::from)
+}
+
+
+/// Home directory of the given user, as listed in the password database.
+#[cfg(unix)]
+fn user_home(user: &str) -> Option<PathBuf> {
+	let passwd = std::fs::read_to_string("/etc/passwd").ok()?;
+
+	// name:password:uid:gid:gecos:home:shell
+	passwd.lines()
+		.map(|line| line.split(':').collect::<Vec<&str>>())
+		.find(|fields| fields.len() >= 7 && fields[0] == user)
+		.map(|fields| PathBuf::from(fields[5]))
+}
+
+
+/// Home directory of the given user, assumed to sit next to the one of the current user.
+#[cfg(not(unix))]
+fn user_home(user: &str) -> Option<PathBuf> {
+	Some(current_home()?.parent()?.join(user))
+}
+
+
+/// Replaces a leading `~` or `~user` in the `value` with the matching home directory.
+/// 
+/// Returns `None` when there is nothing to expand, or the home directory cannot be found.
+pub(crate) fn expand_tilde(value: &str) -> Option<String> {
+	let rest = value.strip_prefix('~')?;
+	let end = rest.find(|c: char| c == '/' || (cfg!(windows) && c == '\\')).unwrap_or(rest.len());
+	let (user, path) = rest.split_at(end);
+
+	let home = if user.is_empty() { current_home()? } else { user_home(user)? };
+	Some(format!("{}{}", home.to_str()?, path))
+}
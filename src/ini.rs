@@ -0,0 +1,106 @@
+//! Minimal INI reader, for [`CLIParser::merge_ini`].
+
+use std::path::Path;
+
+use crate::{CLIParser, CLIError};
+use crate::sources::ConfigValue;
+
+
+/// Parses an INI document into its entries, with each section as a table.
+/// 
+/// Errors carry the line number they occurred at.
+pub(crate) fn parse(text: &str) -> Result<Vec<(String, ConfigValue)>, (usize, String)> {
+	let mut entries: Vec<(String, ConfigValue)> = Vec::new();
+	let mut section: Option<(String, Vec<(String, ConfigValue)>)> = None;
+
+	for (index, line) in text.lines().enumerate() {
+		let line = line.trim();
+
+		if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+			continue;
+		}
+
+		if let Some(header) = line.strip_prefix('[') {
+			let name = match header.strip_suffix(']').map(str::trim) {
+				Some(name) if !name.is_empty() => name.to_string(),
+				_ => return Err((index + 1, format!("malformed section header `{}`", line))),
+			};
+
+			if let Some((previous, table)) = section.replace((name, Vec::new())) {
+				entries.push((previous, ConfigValue::Table(table)));
+			}
+			continue;
+		}
+
+		// Bare keys, as in `my.cnf`, are switches
+		let (key, value) = match line.split_once(['=', ':']) {
+			Some((key, value)) => (key.trim(), ConfigValue::Text(unquote(value.trim()).to_string())),
+			None => (line, ConfigValue::Bool(true)),
+		};
+
+		if key.is_empty() {
+			return Err((index + 1, format!("missing key in `{}`", line)));
+		}
+
+		match &mut section {
+			Some((_, table)) => table.push((key.to_string(), value)),
+			None => entries.push((key.to_string(), value)),
+		}
+	}
+
+	if let Some((name, table)) = section {
+		entries.push((name, ConfigValue::Table(table)));
+	}
+
+	Ok(entries)
+}
+
+
+/// Strips one pair of matching surrounding quotes, if any.
+fn unquote(value: &str) -> &str {
+	for quote in ['"', '\''] {
+		if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+			return &value[1..value.len() - 1];
+		}
+	}
+	value
+}
+
+
+impl CLIParser {
+
+	/// Fills in any flags and pairs missing from the command line, with the contents of an INI file.
+	/// 
+	/// `key = value` (or `key: value`) lines become pairs, bare `key` lines become flags,
+	/// and entries under a `[section]` header get the dotted key `section.key`.
+	/// Lines starting with `;` or `#` are comments.
+	/// Will throw error if the file cannot be read or is malformed.
+	/// 
+	/// ```
+	/// let path = std::env::temp_dir().join("cliparser_merge_ini.conf");
+	/// std::fs::write(&path, "verbose\ndebug_level = 2\n\n[db]\nhost = localhost\n").unwrap();
+	/// 
+	/// let parser = cliparser::CLIParser::new()
+	///     .parse_from(vec!["--debug_level=3".to_string()])
+	///     .unwrap()
+	///     .merge_ini(&path)
+	///     .unwrap();
+	/// 
+	/// assert!(parser.flags.contains("verbose"));
+	/// assert_eq!(parser.pairs["debug_level"], "3");
+	/// assert_eq!(parser.pairs["db.host"], "localhost");
+	/// ```
+	pub fn merge_ini<P: AsRef<Path>>(mut self, path: P) -> Result<Self, CLIError> {
+		let path = path.as_ref();
+
+		let text = std::fs::read_to_string(path)
+			.map_err(|err| CLIError::ConfigFile(path.display().to_string(), err.to_string()))?;
+
+		let entries = parse(&text)
+			.map_err(|(line, reason)| CLIError::ConfigMalformed(format!("{}:{}", path.display(), line), reason))?;
+
+		self.merge_config(entries)?;
+		Ok(self)
+	}
+
+}
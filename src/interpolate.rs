@@ -0,0 +1,46 @@
+//! Environment variable references inside values, see [`ParserConfig::interpolation`](crate::ParserConfig::interpolation).
+
+
+/// Replaces `${NAME}` and `$NAME` in the `value` with the environment variable `NAME`, and `$$` with a single `$`.
+/// 
+/// Unknown variables expand to nothing, or fail with their name when `strict`.
+/// A `$` that starts no reference is kept as is.
+pub(crate) fn interpolate(value: &str, strict: bool) -> Result<String, String> {
+	let mut expanded = String::new();
+	let mut rest = value;
+
+	while let Some(pos) = rest.find('$') {
+		expanded.push_str(&rest[..pos]);
+		let after = &rest[pos + 1..];
+
+		let (name, consumed) = if let Some(braced) = after.strip_prefix('{') {
+			match braced.find('}') {
+				Some(end) if end > 0 => (&braced[..end], end + 2),
+				_ => ("", 0),
+			}
+		} else if let Some(escaped) = after.strip_prefix('$') {
+			expanded.push('$');
+			rest = escaped;
+			continue;
+		} else {
+			let end = after.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(after.len());
+			if after.starts_with(|c: char| c.is_ascii_digit()) { ("", 0) } else { (&after[..end], end) }
+		};
+
+		if name.is_empty() {
+			expanded.push('$');
+			rest = after;
+			continue;
+		}
+
+		match std::env::var(name) {
+			Ok(value) => expanded.push_str(&value),
+			Err(_) if strict => return Err(name.to_string()),
+			Err(_) => {},
+		}
+		rest = &after[consumed..];
+	}
+
+	expanded.push_str(rest);
+	Ok(expanded)
+}
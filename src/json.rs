@@ -0,0 +1,320 @@
+//! Minimal JSON reader, for [`CLIParser::merge_json`].
+
+use std::io::Read;
+
+use crate::{CLIParser, CLIError};
+use crate::sources::ConfigValue;
+
+
+/// How deep objects and arrays may nest, so that hostile input cannot exhaust the stack.
+const MAX_DEPTH: usize = 64;
+
+
+/// Character cursor over the JSON text, tracking the line number for error messages.
+struct Cursor {
+	chars: Vec<char>,
+	pos: usize,
+	line: usize,
+	/// Objects and arrays the cursor is in.
+	depth: usize,
+}
+
+
+type Malformed = (usize, String);
+
+
+impl Cursor {
+
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.pos).copied()
+	}
+
+	fn bump(&mut self) -> Option<char> {
+		let c = self.peek()?;
+		self.pos += 1;
+		if c == '\n' {
+			self.line += 1;
+		}
+		Some(c)
+	}
+
+	fn error<T>(&self, reason: &str) -> Result<T, Malformed> {
+		Err((self.line, reason.to_string()))
+	}
+
+	fn expect(&mut self, expected: char) -> Result<(), Malformed> {
+		self.skip_whitespace();
+		match self.bump() {
+			Some(c) if c == expected => Ok(()),
+			_ => self.error(&format!("expected `{}`", expected)),
+		}
+	}
+
+	fn skip_whitespace(&mut self) {
+		while matches!(self.peek(), Some(' ' | '\t' | '\r' | '\n')) {
+			self.bump();
+		}
+	}
+
+	/// Parses a value, with `null` mapped to `None`.
+	fn value(&mut self) -> Result<Option<ConfigValue>, Malformed> {
+		self.skip_whitespace();
+
+		match self.peek() {
+			Some(c @ ('{' | '[')) => {
+				if self.depth >= MAX_DEPTH {
+					return self.error(&format!("nested more than {} levels deep", MAX_DEPTH));
+				}
+				self.depth += 1;
+				let value = if c == '{' { self.object() } else { self.array() };
+				self.depth -= 1;
+				value.map(Some)
+			}
+			Some('"') => self.string().map(|s| Some(ConfigValue::Text(s))),
+			Some('t' | 'f' | 'n') => {
+				let mut word = String::new();
+				while let Some(c @ 'a'..='z') = self.peek() {
+					word.push(c);
+					self.bump();
+				}
+				match word.as_str() {
+					"true" => Ok(Some(ConfigValue::Bool(true))),
+					"false" => Ok(Some(ConfigValue::Bool(false))),
+					"null" => Ok(None),
+					_ => self.error(&format!("invalid value `{}`", word)),
+				}
+			}
+			Some('-' | '0'..='9') => self.number().map(|n| Some(ConfigValue::Text(n))),
+			_ => self.error("expected a value"),
+		}
+	}
+
+	fn object(&mut self) -> Result<ConfigValue, Malformed> {
+		self.expect('{')?;
+		let mut entries: Vec<(String, ConfigValue)> = Vec::new();
+
+		self.skip_whitespace();
+		if self.peek() == Some('}') {
+			self.bump();
+			return Ok(ConfigValue::Table(entries));
+		}
+
+		loop {
+			self.skip_whitespace();
+			let key = self.string()?;
+			self.expect(':')?;
+			if let Some(value) = self.value()? {
+				entries.push((key, value));
+			}
+
+			self.skip_whitespace();
+			match self.bump() {
+				Some(',') => {},
+				Some('}') => return Ok(ConfigValue::Table(entries)),
+				_ => return self.error("expected `,` or `}` in object"),
+			}
+		}
+	}
+
+	fn array(&mut self) -> Result<ConfigValue, Malformed> {
+		self.expect('[')?;
+		let mut items: Vec<ConfigValue> = Vec::new();
+
+		self.skip_whitespace();
+		if self.peek() == Some(']') {
+			self.bump();
+			return Ok(ConfigValue::Array(items));
+		}
+
+		loop {
+			if let Some(item) = self.value()? {
+				items.push(item);
+			}
+
+			self.skip_whitespace();
+			match self.bump() {
+				Some(',') => {},
+				Some(']') => return Ok(ConfigValue::Array(items)),
+				_ => return self.error("expected `,` or `]` in array"),
+			}
+		}
+	}
+
+	fn hex4(&mut self) -> Result<u32, Malformed> {
+		let mut code = String::new();
+		for _ in 0..4 {
+			match self.bump() {
+				Some(c) => code.push(c),
+				None => return self.error("unterminated unicode escape"),
+			}
+		}
+		match u32::from_str_radix(&code, 16) {
+			Ok(code) => Ok(code),
+			Err(_) => self.error("invalid unicode escape"),
+		}
+	}
+
+	fn string(&mut self) -> Result<String, Malformed> {
+		if self.bump() != Some('"') {
+			return self.error("expected a string");
+		}
+		let mut string = String::new();
+
+		loop {
+			match self.bump() {
+				Some('"') => return Ok(string),
+				Some('\\') => {
+					let escaped = match self.bump() {
+						Some('"') => '"',
+						Some('\\') => '\\',
+						Some('/') => '/',
+						Some('b') => '\u{8}',
+						Some('f') => '\u{c}',
+						Some('n') => '\n',
+						Some('r') => '\r',
+						Some('t') => '\t',
+						Some('u') => {
+							let mut code = self.hex4()?;
+
+							// Characters outside the basic plane come as a surrogate pair
+							if (0xD800..0xDC00).contains(&code) {
+								if self.bump() != Some('\\') || self.bump() != Some('u') {
+									return self.error("unpaired surrogate in unicode escape");
+								}
+								let low = self.hex4()?;
+								if !(0xDC00..0xE000).contains(&low) {
+									return self.error("unpaired surrogate in unicode escape");
+								}
+								code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+							}
+
+							match char::from_u32(code) {
+								Some(c) => c,
+								None => return self.error("invalid unicode escape"),
+							}
+						}
+						_ => return self.error("invalid escape sequence"),
+					};
+					string.push(escaped);
+				}
+				Some(c) if (c as u32) < 0x20 => return self.error("control characters must be escaped in strings"),
+				Some(c) => string.push(c),
+				None => return self.error("unterminated string"),
+			}
+		}
+	}
+
+	/// Numbers are kept as written, after checking their syntax.
+	fn number(&mut self) -> Result<String, Malformed> {
+		let mut number = String::new();
+		while let Some(c @ ('0'..='9' | '-' | '+' | '.' | 'e' | 'E')) = self.peek() {
+			number.push(c);
+			self.bump();
+		}
+
+		let digits = number.strip_prefix('-').unwrap_or(&number);
+		let (mantissa, exponent) = match digits.find(['e', 'E']) {
+			Some(pos) => (&digits[..pos], Some(&digits[pos + 1..])),
+			None => (digits, None),
+		};
+		let (integer, fraction) = match mantissa.split_once('.') {
+			Some((integer, fraction)) => (integer, Some(fraction)),
+			None => (mantissa, None),
+		};
+
+		let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+		let valid = is_digits(integer)
+			&& (integer == "0" || !integer.starts_with('0'))
+			&& fraction.is_none_or(is_digits)
+			&& exponent.is_none_or(|e| is_digits(e.strip_prefix(['+', '-']).unwrap_or(e)));
+
+		if !valid {
+			return self.error(&format!("invalid number `{}`", number));
+		}
+
+		Ok(number)
+	}
+
+}
+
+
+/// Parses a JSON document, which must be an object, into its top level entries.
+pub(crate) fn parse(text: &str) -> Result<Vec<(String, ConfigValue)>, Malformed> {
+	let mut cursor = Cursor { chars: text.chars().collect(), pos: 0, line: 1, depth: 0 };
+
+	cursor.skip_whitespace();
+	if cursor.peek() != Some('{') {
+		return cursor.error("the document must be an object");
+	}
+
+	let entries = match cursor.object()? {
+		ConfigValue::Table(entries) => entries,
+		_ => unreachable!(),
+	};
+
+	cursor.skip_whitespace();
+	if cursor.peek().is_some() {
+		return cursor.error("unexpected content after the document");
+	}
+
+	Ok(entries)
+}
+
+
+impl CLIParser {
+
+	/// Fills in any flags and pairs missing from the command line, with the contents of a JSON object.
+	///
+	/// Booleans become flags (only when `true`), `null` is ignored, other values become pairs,
+	/// and nested objects flatten into dotted keys.
+	/// Will throw error if the input cannot be read, is not a valid JSON object, or contains arrays.
+	///
+	/// ```
+	/// let json = r#"{ "verbose": true, "debug_level": 2, "db": { "host": "localhost" } }"#;
+	///
+	/// let parser = cliparser::CLIParser::new()
+	///     .parse_from(vec!["--debug_level=3".to_string()])
+	///     .unwrap()
+	///     .merge_json(json.as_bytes())
+	///     .unwrap();
+	///
+	/// assert!(parser.flags.contains("verbose"));
+	/// assert_eq!(parser.pairs["debug_level"], "3");
+	/// assert_eq!(parser.pairs["db.host"], "localhost");
+	/// ```
+	pub fn merge_json(mut self, mut reader: impl Read) -> Result<Self, CLIError> {
+		let mut text = String::new();
+		reader.read_to_string(&mut text)
+			.map_err(|err| CLIError::ConfigFile("JSON input".to_string(), err.to_string()))?;
+
+		let entries = parse(&text)
+			.map_err(|(line, reason)| CLIError::ConfigMalformed(format!("JSON input line {}", line), reason))?;
+
+		self.merge_config(entries)?;
+		Ok(self)
+	}
+
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::{parse, MAX_DEPTH};
+
+	#[test]
+	fn deep_nesting_is_rejected() {
+		let text = format!("{}1{}", r#"{"a": "#.repeat(100_000), "}".repeat(100_000));
+		assert_eq!(parse(&text).unwrap_err(), (1, format!("nested more than {} levels deep", MAX_DEPTH)));
+
+		let text = format!("{{\"a\": {}{}}}", "[".repeat(100_000), "]".repeat(100_000));
+		assert!(parse(&text).is_err());
+	}
+
+	#[test]
+	fn nesting_within_the_limit_is_read() {
+		let text = format!("{}1{}", r#"{"a": "#.repeat(MAX_DEPTH), "}".repeat(MAX_DEPTH));
+		assert!(parse(&text).is_ok());
+	}
+
+}
@@ -0,0 +1,210 @@
+//! Merging several sources of arguments by priority, e.g. defaults < configuration file < environment < command line.
+
+use std::collections::HashMap;
+
+use crate::{CLIParser, CLIError, Schema};
+use crate::sources::{Source, SourceValue};
+
+
+/// **Layered configuration**.
+/// 
+/// Sources are registered from the lowest to the highest priority. Every flag and pair of the merged result
+/// comes from the highest priority layer that sets it, and [`Provenance`] tells which one that was.
+/// 
+/// ```
+/// use cliparser::{CLIParser, ConfigLayers};
+/// 
+/// let defaults = CLIParser::new().parse_str("--debug_level=1 --name=app").unwrap();
+/// let cli = CLIParser::new().parse_str("--debug_level=3 -verbose input.txt").unwrap();
+/// 
+/// let (parser, provenance) = ConfigLayers::new()
+///     .layer("defaults", defaults)
+///     .env("environment", "MYAPP_")
+///     .layer("command line", cli)
+///     .merge()
+///     .unwrap();
+/// 
+/// assert_eq!(parser.pairs["debug_level"], "3");
+/// assert_eq!(parser.pairs["name"], "app");
+/// assert_eq!(provenance.layer("debug_level"), Some("command line"));
+/// assert_eq!(provenance.layer("name"), Some("defaults"));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConfigLayers {
+	layers: Vec<(String, Layer)>,
+}
+
+
+/// Values of a single layer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Layer {
+	/// Already parsed.
+	Values(Box<CLIParser>),
+	/// Environment variables starting with the prefix, read when merging to follow the schema of the top layer.
+	Env(String),
+}
+
+
+/// Which layer each flag and pair of a merged [`ConfigLayers`] came from.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Provenance {
+	layers: HashMap<String, String>,
+}
+
+
+impl Provenance {
+
+	/// Name of the layer that set the flag or pair `key`, if any did.
+	pub fn layer(&self, key: &str) -> Option<&str> {
+		self.layers.get(key).map(String::as_str)
+	}
+
+	/// Iterates over all keys, along with the name of the layer that set them.
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+		self.layers.iter().map(|(key, layer)| (key.as_str(), layer.as_str()))
+	}
+
+}
+
+
+impl ConfigLayers {
+
+	/// Creates an empty stack of layers.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a layer, taking precedence over all layers added before it.
+	pub fn layer(mut self, name: &str, values: CLIParser) -> Self {
+		self.layers.push((name.to_string(), Layer::Values(Box::new(values))));
+		self
+	}
+
+	/// Adds a layer made of the environment variables starting with `prefix`.
+	/// 
+	/// See [`ParserConfig::env_prefix`](crate::ParserConfig::env_prefix) for how variables map to flags and pairs.
+	/// Variables are read by [`ConfigLayers::merge`], and follow the schema of the highest priority layer,
+	/// along with the variables [declared](crate::Schema::env) there.
+	pub fn env(mut self, name: &str, prefix: &str) -> Self {
+		self.layers.push((name.to_string(), Layer::Env(prefix.to_string())));
+		self
+	}
+
+	/// Merges all layers into one parser.
+	/// 
+	/// Positional arguments, parsing configuration and schema come from the highest priority layer alone.
+	/// Will throw error if a value of a lower layer does not fit the [type](crate::ValueType) declared by that schema.
+	pub fn merge(mut self) -> Result<(CLIParser, Provenance), CLIError> {
+		let mut provenance = Provenance::default();
+
+		let (top_name, mut merged) = match self.layers.pop() {
+			Some((name, Layer::Values(values))) => (name, *values),
+			Some((name, Layer::Env(prefix))) => (name, read_env(None, &prefix)?),
+			None => return Ok((CLIParser::new(), provenance)),
+		};
+
+		for key in merged.flags.iter().chain(merged.pairs.keys()).chain(merged.negated.iter()) {
+			provenance.layers.insert(key.clone(), top_name.clone());
+		}
+
+		for (name, layer) in self.layers.into_iter().rev() {
+			let layer = match layer {
+				Layer::Values(values) => *values,
+				Layer::Env(prefix) => read_env(merged.schema.clone(), &prefix)?,
+			};
+			let CLIParser { flags, pairs, counts, mut pairs_multi, negated, sources, .. } = layer;
+			let source = |key: &str| sources.get(key).copied().unwrap_or(Source::CommandLine);
+
+			for key in flags {
+				// Stored under the declared name, if the key is an alias
+				let stored = merged.canonical_key(key.clone());
+				if merged.fill_missing(stored.clone(), SourceValue::Flag(true), source(&key))? {
+					merged.counts.insert(stored.clone(), counts.get(&key).copied().unwrap_or(1));
+					provenance.layers.insert(stored, name.clone());
+				}
+			}
+
+			for (key, value) in pairs {
+				let stored = merged.canonical_key(key.clone());
+				if merged.fill_missing(stored.clone(), SourceValue::Pair(value), source(&key))? {
+					match pairs_multi.remove(&key) {
+						Some(values) => merged.pairs_multi.insert(stored.clone(), values),
+						None => merged.pairs_multi.remove(&stored),
+					};
+					provenance.layers.insert(stored, name.clone());
+				}
+			}
+
+			// Turned off explicitly, so that no lower layer turns them back on
+			for key in negated {
+				let stored = merged.canonical_key(key);
+				if !(merged.flags.contains(&stored) || merged.pairs.contains_key(&stored) || merged.negated.contains(&stored)) {
+					merged.negated.insert(stored.clone());
+					provenance.layers.insert(stored, name.clone());
+				}
+			}
+		}
+
+		Ok((merged, provenance))
+	}
+
+}
+
+
+/// Reads the environment variables starting with `prefix`, and the ones declared in `schema`, into a parser of their own.
+fn read_env(schema: Option<Schema>, prefix: &str) -> Result<CLIParser, CLIError> {
+	let mut values = CLIParser::new();
+	values.schema = schema;
+	values.merge_declared_env()?;
+	values.merge_env(prefix)?;
+	Ok(values)
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::{CLIParser, ConfigLayers, DuplicatePolicy, Schema, Source, ValueType};
+
+	#[test]
+	fn env_layer_follows_top_schema() {
+		std::env::set_var("CLIPARSER_LAYERS_TEST_RETRIES", "1");
+
+		let schema = Schema::new().pair("retries").value_type(ValueType::Int);
+		let cli = CLIParser::new().schema(schema).parse_str("").unwrap();
+
+		let (parser, provenance) = ConfigLayers::new()
+			.env("environment", "CLIPARSER_LAYERS_TEST_")
+			.layer("command line", cli)
+			.merge()
+			.unwrap();
+
+		assert_eq!(parser.pair("retries"), Some("1"));
+		assert!(!parser.flag("retries"));
+		assert_eq!(parser.source("retries"), Some(Source::Environment));
+		assert_eq!(provenance.layer("retries"), Some("environment"));
+	}
+
+	#[test]
+	fn lower_layers_keep_counts_collected_values_and_negations() {
+		let schema = Schema::new().flag("color").pair("include").duplicates(DuplicatePolicy::Collect);
+		let config = CLIParser::new().schema(schema.clone())
+			.parse_str("-v -v --include=a --include=b --no-color")
+			.unwrap();
+		let defaults = CLIParser::new().parse_str("-color").unwrap();
+		let cli = CLIParser::new().schema(schema).parse_str("").unwrap();
+
+		let (parser, provenance) = ConfigLayers::new()
+			.layer("defaults", defaults)
+			.layer("config", config)
+			.layer("command line", cli)
+			.merge()
+			.unwrap();
+
+		assert_eq!(parser.count("v"), 2);
+		assert_eq!(parser.pairs_multi["include"], vec!["a", "b"]);
+		assert!(!parser.flag("color"));
+		assert_eq!(provenance.layer("color"), Some("config"));
+	}
+
+}
@@ -1,18 +1,140 @@
 use std::collections::{HashSet, HashMap};
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+pub mod shlex;
+mod args;
+mod builder;
+mod commands;
+mod completions;
+mod config;
+mod describe;
+mod env;
+mod external;
+mod help;
+mod home;
+#[cfg(feature = "ini")]
+mod ini;
+mod interpolate;
+#[cfg(feature = "json")]
+mod json;
+mod layers;
+mod man;
+mod markdown;
+mod namespace;
+mod occurrence;
+mod os;
+mod positionals;
+#[cfg(feature = "regex")]
+mod regex;
+mod response;
+mod schema;
+mod sources;
+#[cfg(any(feature = "toml", feature = "json"))]
+mod spec;
+mod suggest;
+mod take;
+mod terminal;
+mod token;
+mod unused;
+mod validate;
+mod value_enum;
+mod values;
+mod warning;
+#[cfg(feature = "toml")]
+mod toml;
+
+pub use args::{CliArgs, FromCli};
+pub use builder::Arg;
+pub use commands::CommandHandler;
+#[doc(hidden)]
+pub use args::support as __derive;
+#[cfg(feature = "derive")]
+pub use cliparser_derive::{CliArgs, FromCliValue};
+pub use completions::Shell;
+pub use external::ExternalCommand;
+pub use config::{DuplicatePolicy, HelpStyle, Interpolation, ParserConfig, SyntaxStyle};
+use config::Sigil;
+pub use layers::{ConfigLayers, Provenance};
+pub use namespace::{Namespace, Tree};
+pub use occurrence::Occurrence;
+pub use os::OsCLIParser;
+pub use schema::{ArgKind, ArgSpec, Schema, ValueType};
+pub use sources::Source;
+pub use token::Token;
+pub use value_enum::FromCliValue;
+pub use values::Value;
+pub use warning::{Warning, WarningHook};
+
+
+/// Arguments not yet classified.
+type Rest = std::iter::Peekable<std::vec::IntoIter<String>>;
+
+
+/// Whether the `argument` is a negative numeric literal, such as `-5` or `-1.5e3`.
+fn is_negative_number(argument: &str) -> bool {
+	match argument.strip_prefix('-') {
+		Some(number) => number.starts_with(|c: char| c.is_ascii_digit() || c == '.') && number.parse::<f64>().is_ok(),
+		None => false,
+	}
+}
+
+
+/// Replaces the Unicode dashes at the start of the `argument` with ASCII ones, if there are any.
+/// 
+/// Em dashes usually come from a double dash line that got auto-corrected, so they stand for two of them.
+fn normalize_dashes(argument: &str) -> Option<String> {
+	let lead = argument.find(|c: char| !matches!(c, '-' | '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{fe58}' | '\u{fe63}' | '\u{ff0d}'))
+		.unwrap_or(argument.len());
+
+	if argument[..lead].chars().all(|c| c == '-') {
+		return None;
+	}
+
+	let dashes: String = argument[..lead].chars()
+		.map(|c| if c == '\u{2014}' { "--" } else { "-" })
+		.collect();
+
+	Some(format!("{}{}", dashes, &argument[lead..]))
+}
+
+
+/// Value of a declared single-dash pair, given whatever follows its name in the `argument`.
+/// 
+/// The value is either attached with an equal sign, attached directly, or the next argument.
+fn short_value(argument: &str, attached: &str, rest: &mut Rest, empty_values: bool) -> Result<String, CLIError> {
+	match attached.strip_prefix('=') {
+		Some("") if !empty_values => Err(CLIError::PairBadSign(argument.to_string())),
+		Some(value) => Ok(value.to_string()),
+		None if !attached.is_empty() => Ok(attached.to_string()),
+		None => rest.next().ok_or_else(|| CLIError::PairMissingSign(argument.to_string())),
+	}
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct CLIParser {
 
 	/// **Positional arguments**.
 	/// 
 	/// These are the standard arguments without any special syntax. 
+	/// Everything after a bare `--` separator is positional too, even if it starts with dashes.
 	/// 
 	/// Example:
 	/// ```bash
-	/// ./my_program posit_argument_1 posit_argument_2
+	/// ./my_program posit_argument_1 posit_argument_2 -- -posit_argument_3
 	/// ```
 	pub posits: Vec<String>,
 
+	/// **Program path**.
+	/// 
+	/// The first of the `std::env::args()`, when parsing with [`CLIParser::init`] and its variants.
+	/// It is not part of `posits`, unless [configured otherwise](ParserConfig::program_in_posits).
+	/// See [`CLIParser::program_stem`] for the bare program name.
+	/// 
+	/// Example, storing `./my_program`:
+	/// ```bash
+	/// ./my_program posit_argument_1
+	/// ```
+	pub program: Option<String>,
+
 	/// **Flags**.
 	/// 
 	/// These arguments are prefixed with a singular dash line. They are unique, unordered and don't take any values.
@@ -32,6 +154,89 @@ pub struct CLIParser {
 	/// ./my_program --debug_level=2 --id=5 --name="John Smith"
 	/// ```
 	pub pairs: HashMap<String, String>,
+
+	/// **Flag occurrences**.
+	/// 
+	/// How many times each flag was given. Repetitions collapse into a single entry of `flags`, but are counted here.
+	/// 
+	/// Example, counting `v` 3 times:
+	/// ```bash
+	/// ./my_program -v -v -v
+	/// ```
+	pub counts: HashMap<String, usize>,
+
+	/// **Repeated key - value pairs**.
+	/// 
+	/// Every value given to each key, in order, when [collecting duplicates](DuplicatePolicy::Collect).
+	/// The last value is still stored in `pairs` as well.
+	/// 
+	/// Example, collecting `a` and `b` for `include`:
+	/// ```bash
+	/// ./my_program --include=a --include=b
+	/// ```
+	pub pairs_multi: HashMap<String, Vec<String>>,
+
+	/// **Warnings**.
+	/// 
+	/// Notices about arguments that were accepted, but maybe not the way the user meant. See [`Warning`].
+	pub warnings: Vec<Warning>,
+
+	/// **Tokens**.
+	/// 
+	/// Every flag, pair and positional argument given on the command line, in order. See [`Token`].
+	/// Unlike the other data structures, this keeps the interleaving of the arguments,
+	/// but leaves out whatever the environment or configuration files filled in.
+	/// 
+	/// Example, reading `f`, `a.txt`, `f`, `b.txt`, `pos1`, `v` and `pos2` in this order:
+	/// ```bash
+	/// ./my_program -f a.txt -f b.txt pos1 -v pos2
+	/// ```
+	pub tokens: Vec<Token>,
+
+	/// **Passthrough arguments**.
+	/// 
+	/// Everything after a bare `--` separator, verbatim and in order, when [enabled](ParserConfig::passthrough).
+	/// These are kept apart from `posits` and `tokens`, typically to be handed over to [`std::process::Command::args`].
+	/// 
+	/// Example, passing through `-v` and `out.txt`:
+	/// ```bash
+	/// ./my_program input.txt -- -v out.txt
+	/// ```
+	pub passthrough: Vec<String>,
+
+	config: ParserConfig,
+
+	schema: Option<Schema>,
+
+	/// Whether option parsing has ended, after the `--` separator or in [POSIX ordering](ParserConfig::posix_ordering).
+	options_ended: bool,
+
+	/// Whether the `--` separator has been seen while [passing arguments through](ParserConfig::passthrough).
+	passing_through: bool,
+
+	/// Declared flags explicitly turned off with `--no-<flag>`, so that no other source turns them back on.
+	negated: HashSet<String>,
+
+	/// Where each flag and pair came from.
+	sources: HashMap<String, Source>,
+
+	/// Where each flag and pair appeared on the command line.
+	occurrences: HashMap<String, Vec<Occurrence>>,
+
+	/// Index of the argument being classified.
+	arg_index: usize,
+
+	/// Number of arguments classified so far, across calls to [`CLIParser::extend_args`].
+	arg_count: usize,
+
+	/// Which arguments were read, for [`CLIParser::unused`].
+	accessed: unused::AccessLog,
+
+	/// Name and parser of the subcommand given, see [`CLIParser::subcommand`].
+	command: Option<(String, Box<CLIParser>)>,
+
+	/// External subcommand given, see [`CLIParser::external_command`].
+	external: Option<ExternalCommand>,
 }
 
 
@@ -42,7 +247,37 @@ pub enum CLIError {
 	PairMissingSign(String),
 	PairBadSign(String),
 	PairMalformed(String),
-	DashesMalformed(String)
+	DashesMalformed(String),
+	QuoteUnterminated(String),
+	EscapeUnterminated(String),
+	InvalidUtf8(String),
+	ResponseFile(String, String),
+	ConfigFile(String, String),
+	ConfigMalformed(String, String),
+	ConfigMismatch(String, String),
+	Stdin(String),
+	AmbiguousAbbreviation(String, Vec<String>),
+	AmbiguousCommand(String, Vec<String>),
+	DuplicateArgument(String),
+	InvalidValue { key: String, value: String, expected: ValueType },
+	UnknownVariable(String, String),
+	ValueParse { key: String, value: String, type_name: String, reason: String },
+	UnregisteredType { key: String, type_name: String },
+	InvalidPattern { key: String, pattern: String, reason: String },
+	UnknownArgument { given: String, suggestions: Vec<String> },
+	UnknownCommand { given: String, suggestion: Option<String> },
+	MissingRequired(Vec<String>),
+	InvalidChoice { key: String, value: String, choices: Vec<String>, suggestion: Option<String> },
+	Conflict(String, String),
+	MissingDependency(String, String),
+	UnexpectedPositional(String),
+	ValidationFailed { key: String, message: String },
+	OutOfRange { key: String, value: String, range: String },
+	InvalidPath { key: String, path: String, reason: String },
+	MissingRequiredUnless(String, Vec<String>),
+	UnusedArguments(Vec<String>),
+	HelpRequested(String),
+	VersionRequested(String),
 }
 
 
@@ -56,6 +291,36 @@ impl std::error::Error for CLIError {
             CLIError::PairBadSign(_) => None,
             CLIError::PairMalformed(_) => None,
             CLIError::DashesMalformed(_) => None,
+            CLIError::QuoteUnterminated(_) => None,
+            CLIError::EscapeUnterminated(_) => None,
+            CLIError::InvalidUtf8(_) => None,
+            CLIError::ResponseFile(_, _) => None,
+            CLIError::ConfigFile(_, _) => None,
+            CLIError::ConfigMalformed(_, _) => None,
+            CLIError::ConfigMismatch(_, _) => None,
+            CLIError::Stdin(_) => None,
+            CLIError::AmbiguousAbbreviation(_, _) => None,
+            CLIError::AmbiguousCommand(_, _) => None,
+            CLIError::DuplicateArgument(_) => None,
+            CLIError::InvalidValue { .. } => None,
+            CLIError::UnknownVariable(_, _) => None,
+            CLIError::ValueParse { .. } => None,
+            CLIError::UnregisteredType { .. } => None,
+            CLIError::InvalidPattern { .. } => None,
+            CLIError::UnknownArgument { .. } => None,
+            CLIError::UnknownCommand { .. } => None,
+            CLIError::MissingRequired(_) => None,
+            CLIError::InvalidChoice { .. } => None,
+            CLIError::Conflict(_, _) => None,
+            CLIError::MissingDependency(_, _) => None,
+            CLIError::UnexpectedPositional(_) => None,
+            CLIError::ValidationFailed { .. } => None,
+            CLIError::OutOfRange { .. } => None,
+            CLIError::InvalidPath { .. } => None,
+            CLIError::MissingRequiredUnless(_, _) => None,
+            CLIError::UnusedArguments(_) => None,
+            CLIError::HelpRequested(_) => None,
+            CLIError::VersionRequested(_) => None,
         }
     }
 }
@@ -70,20 +335,95 @@ impl std::fmt::Display for CLIError {
             CLIError::PairBadSign(ref arg) => write!(f, "Improper use of equal sign in key-value pair: `{0}`\nProper syntax: `./my_program --key=value`", arg),
             CLIError::PairMalformed(ref arg) => write!(f, "Malformed key-value pair: `{0}`\nProper syntax: `./my_program --key=value`", arg),
             CLIError::DashesMalformed(ref arg) => write!(f, "Arguments cannot start with 3 or more dash lines: `{0}`", arg),
+            CLIError::QuoteUnterminated(ref arg) => write!(f, "Unterminated quote in arguments: `{0}`", arg),
+            CLIError::EscapeUnterminated(ref arg) => write!(f, "Arguments cannot end with an unquoted backslash: `{0}`", arg),
+            CLIError::InvalidUtf8(ref arg) => write!(f, "Argument is not valid UTF-8: `{0}`\nUse `OsCLIParser` to accept arbitrary bytes", arg),
+            CLIError::ResponseFile(ref path, ref reason) => write!(f, "Cannot read response file `{0}`: {1}", path, reason),
+            CLIError::ConfigFile(ref path, ref reason) => write!(f, "Cannot read configuration file `{0}`: {1}", path, reason),
+            CLIError::ConfigMalformed(ref location, ref reason) => write!(f, "Malformed configuration at `{0}`: {1}", location, reason),
+            CLIError::ConfigMismatch(ref key, ref reason) => write!(f, "Configuration value `{0}` does not fit a flag or a key-value pair: {1}", key, reason),
+            CLIError::Stdin(ref reason) => write!(f, "Cannot read arguments from standard input: {0}", reason),
+            CLIError::AmbiguousAbbreviation(ref arg, ref candidates) => write!(f, "Ambiguous abbreviation: `{0}`\nPossible completions: `{1}`", arg, candidates.join("`, `")),
+            CLIError::AmbiguousCommand(ref arg, ref candidates) => write!(f, "Ambiguous command: `{0}`\nPossible commands: `{1}`", arg, candidates.join("`, `")),
+            CLIError::DuplicateArgument(ref key) => write!(f, "Argument given more than once: `{0}`", key),
+            CLIError::InvalidValue { ref key, ref value, ref expected } => write!(f, "Invalid value for `{0}`: `{1}`\nExpected {2}", key, value, expected),
+            CLIError::UnknownVariable(ref name, ref value) => write!(f, "Unknown environment variable `{0}` in value: `{1}`\nUse `$$` for a literal dollar sign", name, value),
+            CLIError::ValueParse { ref key, ref value, ref type_name, ref reason } => {
+                write!(f, "Cannot read the value of `{0}` as `{2}`: `{1}`", key, value, type_name)?;
+                match reason.is_empty() {
+                    false => write!(f, "\n{0}", reason),
+                    true => Ok(()),
+                }
+            }
+            CLIError::UnregisteredType { ref key, ref type_name } => write!(f, "No type of values registered as `{1}`, for `{0}`", key, type_name),
+            CLIError::InvalidPattern { ref key, ref pattern, ref reason } => write!(f, "Invalid pattern `{1}` for `{0}`: {2}", key, pattern, reason),
+            CLIError::UnknownArgument { ref given, ref suggestions } => {
+                write!(f, "Unknown argument: `{0}`", given)?;
+                match suggestions.is_empty() {
+                    false => write!(f, "\nDid you mean `{0}`?", suggestions.join("` or `")),
+                    true => Ok(()),
+                }
+            }
+            CLIError::UnknownCommand { ref given, ref suggestion } => {
+                write!(f, "Unknown command: `{0}`", given)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, "\nDid you mean `{0}`?", suggestion),
+                    None => Ok(()),
+                }
+            }
+            CLIError::MissingRequired(ref names) => write!(f, "Missing required arguments: `{0}`", names.join("`, `")),
+            CLIError::InvalidChoice { ref key, ref value, ref choices, ref suggestion } => {
+                write!(f, "Invalid value for `{0}`: `{1}`\nPossible values: `{2}`", key, value, choices.join("`, `"))?;
+                match suggestion {
+                    Some(suggestion) => write!(f, "\nDid you mean `{0}`?", suggestion),
+                    None => Ok(()),
+                }
+            }
+            CLIError::Conflict(ref first, ref second) => write!(f, "Arguments cannot be used together: `{0}` and `{1}`", first, second),
+            CLIError::MissingDependency(ref arg, ref required) => write!(f, "Argument `{0}` requires `{1}` to be given as well", arg, required),
+            CLIError::UnexpectedPositional(ref arg) => write!(f, "Unexpected positional argument: `{0}`", arg),
+            CLIError::ValidationFailed { ref key, ref message } => write!(f, "Invalid value for `{0}`: {1}", key, message),
+            CLIError::OutOfRange { ref key, ref value, ref range } => write!(f, "Value out of range for `{0}`: `{1}`\nExpected a number in `{2}`", key, value, range),
+            CLIError::InvalidPath { ref key, ref path, ref reason } => write!(f, "Invalid path for `{0}`: `{1}` {2}", key, path, reason),
+            CLIError::MissingRequiredUnless(ref name, ref alternatives) => write!(f, "Missing required argument `{0}`, unless `{1}` is given", name, alternatives.join("` or `")),
+            CLIError::UnusedArguments(ref names) => write!(f, "Unrecognized arguments: `{0}`", names.join("`, `")),
+            CLIError::HelpRequested(ref help) => write!(f, "{0}", help),
+            CLIError::VersionRequested(ref version) => write!(f, "{0}", version),
         }
     }
 }
 
-impl Default for CLIParser {
-	fn default() -> Self {
-		Self {
-			posits: Vec::new(),
-			flags: HashSet::new(),
-			pairs: HashMap::new(),
-		}
+
+impl CLIError {
+
+	/// Whether parsing stopped to show the [help](CLIParser::help) or [version](Schema::version) text, rather than on bad arguments.
+	/// 
+	/// The program is then expected to print the error and exit successfully.
+	/// 
+	/// ```no_run
+	/// use cliparser::{CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new().version(env!("CARGO_PKG_VERSION")).flag("verbose");
+	/// 
+	/// let parser = match CLIParser::new().init_with(schema) {
+	///     Ok(parser) => parser,
+	///     Err(error) if error.is_early_exit() => {
+	///         print!("{}", error);
+	///         std::process::exit(0);
+	///     }
+	///     Err(error) => {
+	///         eprintln!("{}", error);
+	///         std::process::exit(2);
+	///     }
+	/// };
+	/// ```
+	pub fn is_early_exit(&self) -> bool {
+		matches!(self, CLIError::HelpRequested(_) | CLIError::VersionRequested(_))
 	}
+
 }
 
+
 impl CLIParser {
 	
 	/// Creates a new cli-parser object, with empty data structures. 
@@ -91,9 +431,51 @@ impl CLIParser {
 		Self::default()
 	}
 
+	/// Sets the parsing behavior switches. See [`ParserConfig`].
+	pub fn config(mut self, config: ParserConfig) -> Self {
+		self.config = config;
+		self
+	}
+
+	/// Sets the declarations of the expected arguments. See [`Schema`].
+	/// 
+	/// Declared [default values](Schema::default_value) are stored right away.
+	pub fn schema(mut self, schema: Schema) -> Self {
+		for spec in schema.args().iter().filter(|spec| spec.kind == ArgKind::Pair) {
+			if let Some(value) = &spec.default {
+				self.set_default(spec.name.clone(), value.clone());
+			}
+		}
+		self.schema = Some(schema);
+		self
+	}
+
+	/// Fills in missing flags and pairs from environment variables starting with `prefix`.
+	/// 
+	/// Shorthand for setting [`ParserConfig::env_prefix`]. Command line arguments always take precedence.
+	/// 
+	/// ```
+	/// std::env::set_var("MYAPP_DEBUG_LEVEL", "2");
+	/// std::env::set_var("MYAPP_VERBOSE", "1");
+	/// 
+	/// let parser = cliparser::CLIParser::new()
+	///     .env_prefix("MYAPP_")
+	///     .parse_from(vec!["--debug_level=3".to_string()])
+	///     .unwrap();
+	/// 
+	/// assert_eq!(parser.pairs["debug_level"], "3");
+	/// assert!(parser.flags.contains("verbose"));
+	/// ```
+	pub fn env_prefix(mut self, prefix: &str) -> Self {
+		self.config.env_prefix = Some(prefix.to_string());
+		self
+	}
+
 	/// Parses the `std::env::args()` and collects them into data structures.
 	/// 
 	/// Will throw error if CLI arguments are considered malformed by this crate.
+	/// The program path goes to [`CLIParser::program`]. Unlike in 0.1.0, it is not the first of the [`CLIParser::posits`],
+	/// unless [configured otherwise](ParserConfig::program_in_posits).
 	/// 
 	/// ```
 	/// // Initialize parser
@@ -105,54 +487,804 @@ impl CLIParser {
 	/// let pairs = parser.pairs.clone(); // HashMap
 	/// ```
 	pub fn init(mut self) -> Result<Self, CLIError> {
-		
-		for argument in std::env::args() {
+		let args = self.take_program(std::env::args(), self.config.program_in_posits);
+		self.parse_from(args)
+	}
 
-			// Positional
-			if !argument.starts_with("-") {
-				self.posits.push(argument);
-				continue;
+	/// Parses the `std::env::args()` like [`CLIParser::init`], but reports every malformed argument instead of the first one.
+	/// 
+	/// See [`CLIParser::parse_collect`].
+	/// 
+	/// ```no_run
+	/// let parser = match cliparser::CLIParser::new().init_collect() {
+	///     Ok(parser) => parser,
+	///     Err(errors) => {
+	///         errors.iter().for_each(|error| eprintln!("{}", error));
+	///         std::process::exit(2);
+	///     }
+	/// };
+	/// ```
+	pub fn init_collect(mut self) -> Result<Self, Vec<CLIError>> {
+		let args = self.take_program(std::env::args(), self.config.program_in_posits);
+		self.parse_collect(args)
+	}
+
+	/// Parses the `std::env::args()` against the declared arguments, and [validates](CLIParser::validate) them.
+	/// 
+	/// The program path is always left out of [`CLIParser::posits`], so that they only hold what the user typed
+	/// and line up with the [declared positional arguments](Schema::positional).
+	/// Will throw error if CLI arguments are malformed, undeclared, missing or of the wrong type.
+	/// 
+	/// ```no_run
+	/// use cliparser::{CLIParser, Schema, ValueType};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("verbose").short("v")
+	///     .pair("output").short("o").required()
+	///     .pair("timeout").value_type(ValueType::Duration);
+	/// 
+	/// let parser = CLIParser::new().init_with(schema).unwrap();
+	/// ```
+	pub fn init_with(mut self, schema: Schema) -> Result<Self, CLIError> {
+		let args = self.take_program(std::env::args(), false);
+		let parser = self.schema(schema).parse_from(args)?;
+		parser.validate()?;
+		Ok(parser)
+	}
+
+	/// Parses the `std::env::args_os()` and collects them into data structures.
+	/// 
+	/// Unlike [`CLIParser::init`], which panics on non-UTF-8 arguments, this will throw a [`CLIError::InvalidUtf8`] error.
+	/// See [`OsCLIParser`] to keep such arguments instead.
+	pub fn init_os(mut self) -> Result<Self, CLIError> {
+		let args = std::env::args_os()
+			.map(|arg| arg.into_string().map_err(|arg| CLIError::InvalidUtf8(arg.to_string_lossy().into_owned())))
+			.collect::<Result<Vec<String>, CLIError>>()?;
+
+		let args = self.take_program(args, self.config.program_in_posits);
+		self.parse_from(args)
+	}
+
+	/// Moves the program path at the front of `args` to [`CLIParser::program`], and to the positional arguments if `keep` is set.
+	pub(crate) fn take_program<I: IntoIterator<Item = String>>(&mut self, args: I, keep: bool) -> I::IntoIter {
+		let mut args = args.into_iter();
+		self.program = args.next();
+		self.arg_count = 1;
+
+		if let Some(program) = self.program.clone().filter(|_| keep) {
+			self.tokens.push(Token::Positional(program.clone()));
+			self.posits.push(program);
+		}
+
+		args
+	}
+
+	/// Name of the program, the [program path](CLIParser::program) without its directory and extension.
+	/// 
+	/// ```no_run
+	/// // Run as `/usr/local/bin/mytool.exe --verbose`
+	/// let parser = cliparser::CLIParser::new().init().unwrap();
+	/// 
+	/// assert_eq!(parser.program.as_deref(), Some("/usr/local/bin/mytool.exe"));
+	/// assert_eq!(parser.program_stem(), Some("mytool"));
+	/// assert!(parser.posits.is_empty());
+	/// ```
+	pub fn program_stem(&self) -> Option<&str> {
+		std::path::Path::new(self.program.as_ref()?).file_stem()?.to_str()
+	}
+
+	/// Parses the given arguments and collects them into data structures.
+	/// 
+	/// Behaves like [`CLIParser::init`], but takes the arguments from any iterator instead of `std::env::args()`.
+	/// There is no program path among them, so [`CLIParser::program`] stays empty.
+	/// Useful for testing, or for arguments that don't come from the command line.
+	/// 
+	/// ```
+	/// let args = vec!["--debug_level=2", "-verb", "path/to/file"];
+	/// let parser = cliparser::CLIParser::new()
+	///     .parse_from(args.into_iter().map(String::from))
+	///     .unwrap();
+	/// 
+	/// assert_eq!(parser.program, None);
+	/// assert_eq!(parser.posits, vec!["path/to/file"]);
+	/// assert!(parser.flags.contains("verb"));
+	/// assert_eq!(parser.pairs["debug_level"], "2");
+	/// ```
+	pub fn parse_from<I: IntoIterator<Item = String>>(mut self, args: I) -> Result<Self, CLIError> {
+
+		self.check_types()?;
+		self.enter_multicall();
+		self.extend_args(args)?;
+		self.merge_declared_env()?;
+
+		if let Some(prefix) = self.config.env_prefix.clone() {
+			self.merge_env(&prefix)?;
+		}
+		self.finish_command()?;
+
+		Ok(self)
+	}
+
+	/// Checks that the declared arguments only use [registered](Schema::register_type) types of values, and valid [patterns](Schema::matches).
+	fn check_types(&self) -> Result<(), CLIError> {
+		match &self.schema {
+			Some(schema) => schema.check_types(),
+			None => Ok(()),
+		}
+	}
+
+	/// Classifies one more argument into the existing data structures.
+	/// 
+	/// Meant for long-running programs that keep receiving arguments after the initial parsing.
+	/// 
+	/// ```
+	/// let mut parser = cliparser::CLIParser::new().parse_str("-verbose").unwrap();
+	/// 
+	/// parser.push_arg("--debug_level=2").unwrap();
+	/// parser.push_arg("path/to/file").unwrap();
+	/// 
+	/// assert!(parser.flags.contains("verbose"));
+	/// assert_eq!(parser.pairs["debug_level"], "2");
+	/// assert_eq!(parser.posits, vec!["path/to/file"]);
+	/// ```
+	pub fn push_arg(&mut self, argument: &str) -> Result<(), CLIError> {
+		self.extend_args(std::iter::once(argument.to_string()))
+	}
+
+	/// Classifies more arguments into the existing data structures.
+	/// 
+	/// Arguments go through the same expansions as in [`CLIParser::parse_from`], but environment variables are not consulted again.
+	/// On error, the arguments before the malformed one have already been stored.
+	pub fn extend_args<I: IntoIterator<Item = String>>(&mut self, args: I) -> Result<(), CLIError> {
+
+		let args = self.expand_args(args)?;
+		self.classify_all(args, Err)
+	}
+
+	/// Parses the given arguments like [`CLIParser::parse_from`], but carries on past malformed ones to report every one of them.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser};
+	/// 
+	/// let args = vec!["-verbose=1", "path/to/file", "---x"];
+	/// let errors = CLIParser::new().parse_collect(args.into_iter().map(String::from)).unwrap_err();
+	/// 
+	/// assert_eq!(errors, vec![
+	///     CLIError::FlagWithSign("-verbose=1".to_string()),
+	///     CLIError::DashesMalformed("---x".to_string()),
+	/// ]);
+	/// ```
+	pub fn parse_collect<I: IntoIterator<Item = String>>(mut self, args: I) -> Result<Self, Vec<CLIError>> {
+		let mut errors: Vec<CLIError> = Vec::new();
+
+		if let Err(error) = self.check_types() {
+			return Err(vec![error]);
+		}
+		self.enter_multicall();
+		match self.expand_args(args) {
+			Ok(args) => {
+				let collect = |error| {
+					errors.push(error);
+					Ok(())
+				};
+				self.classify_all(args, collect).expect("collected errors are never returned");
 			}
+			Err(error) => errors.push(error),
+		}
 
-			else if !argument.starts_with("--") {
-				
-				if argument.contains("=") {
-					return Err(CLIError::FlagWithSign(argument));
+		if let Err(error) = self.merge_declared_env() {
+			errors.push(error);
+		}
+		if let Some(prefix) = self.config.env_prefix.clone() {
+			if let Err(error) = self.merge_env(&prefix) {
+				errors.push(error);
+			}
+		}
+		if let Err(error) = self.finish_command() {
+			errors.push(error);
+		}
+
+		match errors.is_empty() {
+			true => Ok(self),
+			false => Err(errors),
+		}
+	}
+
+	/// Expands response files and the standard input marker, leaving what follows the `--` separator as is.
+	fn expand_args<I: IntoIterator<Item = String>>(&self, args: I) -> Result<Vec<String>, CLIError> {
+
+		let mut args: Vec<String> = args.into_iter().collect();
+
+		// Arguments after the `--` separator are not expanded either
+		let verbatim = if self.options_ended {
+			std::mem::take(&mut args)
+		} else {
+			match args.iter().position(|arg| arg == "--") {
+				Some(pos) => args.split_off(pos),
+				None => Vec::new(),
+			}
+		};
+
+		if let Some(marker) = &self.config.stdin_marker {
+			args = response::read_input(args, marker, std::io::stdin().lock())?;
+		}
+
+		if self.config.response_files {
+			args = response::expand(args)?;
+		}
+
+		args.extend(verbatim);
+		Ok(args)
+	}
+
+	/// Classifies the expanded arguments in order, handing errors over to `on_error`, which decides whether to carry on.
+	fn classify_all<F: FnMut(CLIError) -> Result<(), CLIError>>(&mut self, args: Vec<String>, mut on_error: F) -> Result<(), CLIError> {
+		let start = self.arg_count;
+		let total = args.len();
+
+		let mut args = args.into_iter().peekable();
+		while let Some(argument) = args.next() {
+			self.arg_index = start + total - args.len() - 1;
+			let result = self.classify(argument, &mut args);
+			self.arg_count = start + total - args.len();
+			if let Err(error) = result {
+				on_error(error)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Stores a single argument into the data structure matching its syntax.
+	/// 
+	/// Some syntaxes take their value from the next argument, hence the access to the `rest` of them.
+	fn classify(&mut self, argument: String, rest: &mut Rest) -> Result<(), CLIError> {
+
+		// Everything after a subcommand
+		let argument = match self.forward_to_command(argument, rest)? {
+			Some(argument) => argument,
+			None => return Ok(()),
+		};
+
+		// Everything after the `--` separator
+		if self.passing_through {
+			self.passthrough.push(argument);
+			return Ok(());
+		}
+		if self.options_ended {
+			self.insert_posit(argument);
+			return Ok(());
+		}
+
+		let argument = match normalize_dashes(&argument).filter(|_| self.config.normalize_dashes) {
+			Some(normalized) => {
+				self.warn(Warning::DashesNormalized { original: argument, normalized: normalized.clone() });
+				normalized
+			}
+			None => argument,
+		};
+
+		if argument == "--" {
+			self.options_ended = true;
+			self.passing_through = self.config.passthrough;
+			return Ok(());
+		}
+
+		if let Some(schema) = self.schema.as_ref().filter(|schema| schema.is_json_help_request(&argument)) {
+			return Err(CLIError::HelpRequested(format!("{}\n", schema.describe_json())));
+		}
+		if self.schema.as_ref().is_some_and(|schema| schema.is_help_request(&argument)) {
+			return Err(CLIError::HelpRequested(self.help().unwrap_or_default()));
+		}
+		if self.schema.as_ref().is_some_and(|schema| schema.is_version_request(&argument)) {
+			return Err(CLIError::VersionRequested(self.version().unwrap_or_default()));
+		}
+
+		if self.config.syntax == SyntaxStyle::Windows && argument.starts_with('/') {
+			return self.classify_windows(argument);
+		}
+
+		if self.config.negative_numbers && is_negative_number(&argument) {
+			self.insert_posit(argument);
+			return Ok(());
+		}
+
+		match self.config.match_prefix(&argument) {
+			// Subcommand or positional
+			None => {
+				if !self.enter_command(&argument, rest)? {
+					self.insert_posit(argument);
 				}
+				Ok(())
+			}
+
+			Some((_, prefix)) if prefix.ends_with('-') && argument[prefix.len()..].starts_with('-') => {
+				Err(CLIError::DashesMalformed(argument))
+			}
 
-				if argument.len() < 2 {
+			Some((Sigil::Flag, prefix)) => {
+				let start = prefix.len();
+				self.classify_flag(argument, start, rest)
+			}
+
+			Some((Sigil::Pair, prefix)) => {
+				let start = prefix.len();
+				self.classify_pair(argument, start, rest)
+			}
+
+			Some((Sigil::Negation, prefix)) => {
+				let start = prefix.len();
+				self.classify_negation(argument, start)
+			}
+		}
+	}
+
+	/// Stores a flag, whose name starts at `start` in the `argument`.
+	fn classify_flag(&mut self, argument: String, start: usize, rest: &mut Rest) -> Result<(), CLIError> {
+		let body = &argument[start..];
+
+		// Declared single-dash names
+		let name = body.split('=').next().unwrap();
+		if let Some(spec) = self.schema.as_ref().and_then(|schema| schema.find_short(name)) {
+			if spec.kind == ArgKind::Flag {
+				if body.contains('=') {
 					return Err(CLIError::FlagWithSign(argument));
 				}
+				let name = spec.name.clone();
+				self.insert_flag(name)?;
+			} else {
+				let value = short_value(&argument, &body[name.len()..], rest, self.config.empty_values)?;
+				let name = spec.name.clone();
+				self.insert_pair(name, value)?;
+			}
+			return Ok(());
+		}
 
-				self.flags.insert(argument[1..].to_string());
-				continue;
+		// Digits attached to declared single-dash pairs, make style
+		if let Some((spec, digits)) = self.schema.as_ref().and_then(|schema| schema.find_numeric_short(body)) {
+			let (name, value) = (spec.name.clone(), digits.to_string());
+			return self.insert_pair(name, value);
+		}
+
+		// Java style property defines, other flags may start with the prefix too
+		let define = body.strip_prefix(self.config.define_prefix.as_str()).and_then(|define| define.split_once('='));
+		if let Some((key, value)) = define.filter(|_| self.config.defines) {
+			if key.is_empty() || (value.is_empty() && !self.config.empty_values) {
+				return Err(CLIError::PairBadSign(argument));
 			}
-			
-			else if !argument.starts_with("---") {
-				if !argument.contains("=") {
-					return Err(CLIError::PairMissingSign(argument));
-				}
+			return self.insert_pair(key.to_string(), value.to_string());
+		}
 
-				if argument.len() < 5 {
-					return Err(CLIError::PairMalformed(argument));
-				}
+		if self.config.cluster_short_flags && body.chars().count() > 1 {
+			return self.classify_cluster(argument, start, rest);
+		}
+
+		if body.contains("=") {
+			return Err(CLIError::FlagWithSign(argument));
+		}
+
+		if body.is_empty() {
+			return Err(CLIError::FlagMalformed(argument));
+		}
 
-				let equal_sign_pos: usize = argument.find('=').unwrap();
-				if equal_sign_pos == 2 || equal_sign_pos == argument.len() - 1 {
-					return Err(CLIError::PairBadSign(argument));
+		self.insert_flag(body.to_string())
+	}
+
+	/// Stores a key - value pair, whose key starts at `start` in the `argument`.
+	fn classify_pair(&mut self, argument: String, start: usize, rest: &mut Rest) -> Result<(), CLIError> {
+		// Keys are compared to the declared ones in their normalized form
+		let argument = if self.config.case_insensitive {
+			let key_end = argument.find('=').unwrap_or(argument.len());
+			format!("{}{}", argument[..key_end].to_lowercase(), &argument[key_end..])
+		} else {
+			argument
+		};
+
+		let argument = match self.expand_abbreviation(&argument, start)? {
+			Some(expanded) => expanded,
+			None => argument,
+		};
+		let body = &argument[start..];
+
+		if !body.contains("=") {
+
+			// Declared flags, toggled GNU style
+			let schema = self.schema.as_ref();
+			if schema.and_then(|schema| schema.find(body)).is_some_and(|spec| spec.kind == ArgKind::Flag) {
+				return self.insert_flag(body.to_string());
+			}
+			if let Some(name) = body.strip_prefix("no-") {
+				if schema.and_then(|schema| schema.find(name)).is_some_and(|spec| spec.kind == ArgKind::Flag) {
+					return self.negate_flag(name);
 				}
+			}
 
-				let kwarg: (&str, &str) = argument.split_once("=").unwrap();
-				self.pairs.insert(kwarg.0[2..].to_string(), kwarg.1.to_string());
+			if !body.is_empty() {
+				let key = self.normalize_key(body.to_string());
+				self.check_declared(&key)?;
 			}
 
-			else {
-				return Err(CLIError::DashesMalformed(argument));
+			let missing_value = schema.and_then(|schema| schema.find(body)).and_then(|spec| spec.default_missing.clone());
+			if let Some(value) = missing_value {
+				return self.insert_pair(body.to_string(), value);
 			}
+
+			let value = match rest.peek() {
+				Some(next) if self.config.space_separated_values && !body.is_empty()
+					&& (self.config.match_prefix(next).is_none() || (self.config.negative_numbers && is_negative_number(next))) => rest.next().unwrap(),
+				_ if self.config.truthy_pairs && !body.is_empty() => "true".to_string(),
+				_ => return Err(CLIError::PairMissingSign(argument)),
+			};
+
+			self.insert_pair(body.to_string(), value)?;
+			return Ok(());
 		}
 
-		Ok(self)
+		let equal_sign_pos: usize = body.find('=').unwrap();
+		let empty_value = equal_sign_pos == body.len() - 1;
+
+		if body.len() < 3 && !(self.config.empty_values && empty_value && equal_sign_pos > 0) {
+			return Err(CLIError::PairMalformed(argument));
+		}
+
+		if equal_sign_pos == 0 || (empty_value && !self.config.empty_values) {
+			return Err(CLIError::PairBadSign(argument));
+		}
+
+		let kwarg: (&str, &str) = body.split_once("=").unwrap();
+		self.insert_pair(kwarg.0.to_string(), kwarg.1.to_string())?;
+		Ok(())
+	}
+
+	/// Completes an abbreviated key, starting at `start` in the `argument`, to the declared key it uniquely starts.
+	/// 
+	/// Returns the argument rewritten with the full key, or `None` when there is nothing to complete.
+	fn expand_abbreviation(&self, argument: &str, start: usize) -> Result<Option<String>, CLIError> {
+		let schema = match &self.schema {
+			Some(schema) if self.config.long_abbreviations => schema,
+			_ => return Ok(None),
+		};
+
+		let body = &argument[start..];
+		let key = body.split('=').next().unwrap();
+		if key.is_empty() || schema.find(key).is_some() {
+			return Ok(None);
+		}
+
+		// `--no-<flag>` toggles complete the flag name
+		let (negation, key) = match key.strip_prefix("no-") {
+			Some(flag) if schema.complete(key, None).is_empty() => ("no-", flag),
+			_ => ("", key),
+		};
+		let kind = if negation.is_empty() { None } else { Some(ArgKind::Flag) };
+
+		let candidates = schema.complete(key, kind);
+		match candidates.len() {
+			0 => Ok(None),
+			1 => Ok(Some(format!("{}{}{}{}", &argument[..start], negation, candidates[0], &body[negation.len() + key.len()..]))),
+			_ => Err(CLIError::AmbiguousAbbreviation(argument.to_string(), candidates.into_iter().map(String::from).collect())),
+		}
+	}
+
+	/// Turns off a flag, whose name starts at `start` in the `argument`.
+	fn classify_negation(&mut self, argument: String, start: usize) -> Result<(), CLIError> {
+		let body = &argument[start..];
+
+		if body.contains("=") {
+			return Err(CLIError::FlagWithSign(argument));
+		}
+
+		if body.is_empty() {
+			return Err(CLIError::FlagMalformed(argument));
+		}
+
+		self.negate_flag(body)
+	}
+
+	/// Stores a `/flag`, `/key:value` or `/key=value` argument.
+	fn classify_windows(&mut self, argument: String) -> Result<(), CLIError> {
+		let body = &argument[1..];
+
+		match body.find([':', '=']) {
+			None if body.is_empty() => return Err(CLIError::FlagMalformed(argument)),
+			None => self.insert_flag(body.to_string())?,
+			Some(pos) if pos == 0 || (pos == body.len() - 1 && !self.config.empty_values) => return Err(CLIError::PairBadSign(argument)),
+			Some(pos) => self.insert_pair(body[..pos].to_string(), body[pos + 1..].to_string())?,
+		}
+
+		Ok(())
+	}
+
+	/// Stores every character of a `-abc` argument as its own flag, starting at `start`.
+	/// 
+	/// A character declared as the single-dash name of a pair takes the rest of the argument,
+	/// or the next argument if nothing is left, as its value.
+	fn classify_cluster(&mut self, argument: String, start: usize, rest: &mut Rest) -> Result<(), CLIError> {
+
+		for (pos, c) in argument[start..].char_indices() {
+			let short = c.to_string();
+			let spec = self.schema.as_ref().and_then(|schema| schema.find_short(&short));
+
+			match spec {
+				Some(spec) if spec.kind == ArgKind::Pair => {
+					let value = short_value(&argument, &argument[start + pos + c.len_utf8()..], rest, self.config.empty_values)?;
+					let name = spec.name.clone();
+					self.insert_pair(name, value)?;
+					return Ok(());
+				}
+				_ if c == '=' => return Err(CLIError::FlagWithSign(argument)),
+				Some(spec) => {
+					let name = spec.name.clone();
+					self.insert_flag(name)?;
+				}
+				None => self.insert_flag(short)?,
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Turns off a flag, and keeps any other source from turning it back on.
+	fn negate_flag(&mut self, name: &str) -> Result<(), CLIError> {
+		let name = self.canonical_key(name.to_string());
+		self.check_known(&name)?;
+		self.flags.remove(&name);
+		self.counts.remove(&name);
+		self.sources.remove(&name);
+		self.negated.insert(name.clone());
+		self.tokens.push(Token::Negated(name));
+		Ok(())
+	}
+
+	/// Rejects undeclared flags and pairs in [strict](ParserConfig::strict) mode, and warns about deprecated ones.
+	fn check_known(&mut self, name: &str) -> Result<(), CLIError> {
+		self.check_declared(name)?;
+
+		let deprecated = self.schema.as_ref()
+			.and_then(|schema| schema.find(name))
+			.filter(|spec| spec.deprecated)
+			.map(|spec| Warning::Deprecated { name: spec.name.clone(), replacement: spec.replacement.clone() });
+
+		if let Some(warning) = deprecated {
+			self.warn(warning);
+		}
+		Ok(())
+	}
+
+	/// Rejects undeclared flags and pairs in [strict](ParserConfig::strict) mode.
+	fn check_declared(&self, name: &str) -> Result<(), CLIError> {
+		match &self.schema {
+			Some(schema) if self.config.strict && schema.find(name).is_none() => Err(CLIError::UnknownArgument {
+				given: name.to_string(),
+				suggestions: schema.similar_names(name),
+			}),
+			_ => Ok(()),
+		}
+	}
+
+	/// Stores a positional argument given on the command line.
+	/// 
+	/// In [POSIX ordering](ParserConfig::posix_ordering) mode, this ends option parsing.
+	fn insert_posit(&mut self, argument: String) {
+		if self.config.posix_ordering {
+			self.options_ended = true;
+		}
+		self.tokens.push(Token::Positional(argument.clone()));
+		self.posits.push(argument);
+	}
+
+	/// Stores a key - value pair given on the command line, see [`CLIParser::store_pair`].
+	fn insert_pair(&mut self, key: String, value: String) -> Result<(), CLIError> {
+		let key = self.canonical_key(key);
+		self.check_known(&key)?;
+		let value = self.store_pair(key.clone(), value)?;
+		self.sources.insert(key.clone(), Source::CommandLine);
+		self.record_occurrence(&key, Some(value.clone()));
+		self.tokens.push(Token::Pair(key, value));
+		Ok(())
+	}
+
+	/// Stores a flag given on the command line, see [`CLIParser::store_flag`].
+	fn insert_flag(&mut self, name: String) -> Result<(), CLIError> {
+		let name = self.canonical_key(name);
+		self.check_known(&name)?;
+		self.store_flag(name.clone())?;
+		self.sources.insert(name.clone(), Source::CommandLine);
+		self.record_occurrence(&name, None);
+		self.tokens.push(Token::Flag(name));
+		Ok(())
+	}
+
+	/// Lowercases keys in [case insensitive](ParserConfig::case_insensitive) mode.
+	pub(crate) fn normalize_key(&self, key: String) -> String {
+		if self.config.case_insensitive {
+			key.to_lowercase()
+		} else {
+			key
+		}
+	}
+
+	/// Normalizes the `key`, and resolves it to the declared name if it is an alias.
+	/// 
+	/// Deprecated aliases emit a warning.
+	pub(crate) fn canonical_key(&mut self, key: String) -> String {
+		let key = self.normalize_key(key);
+		let (name, deprecated) = match self.schema.as_ref().and_then(|schema| schema.find_alias(&key)) {
+			Some((spec, deprecated)) => (spec.name.clone(), deprecated),
+			None => return key,
+		};
+
+		if deprecated {
+			self.warn(Warning::DeprecatedAlias { alias: key, name: name.clone() });
+		}
+		name
+	}
+
+	/// Handling of the argument `key` given more than once, as declared in the schema or configured globally.
+	fn duplicate_policy(&self, key: &str) -> DuplicatePolicy {
+		self.schema.as_ref()
+			.and_then(|schema| schema.find(key))
+			.and_then(|spec| spec.duplicates)
+			.unwrap_or(self.config.duplicates)
+	}
+
+	/// Stores a key - value pair, following the [duplicates policy](ParserConfig::duplicates).
+	/// Default values do not count as duplicates.
+	/// 
+	/// Returns the value as stored, after any expansions.
+	pub(crate) fn store_pair(&mut self, key: String, value: String) -> Result<String, CLIError> {
+		let key = self.normalize_key(key);
+		let value = self.prepare_value(&key, value)?;
+		let exists = self.pairs.contains_key(&key) && !self.is_default(&key);
+
+		match self.duplicate_policy(&key) {
+			DuplicatePolicy::Error if exists => return Err(CLIError::DuplicateArgument(key)),
+			DuplicatePolicy::KeepFirst if exists => {
+				if self.pairs[&key] != value {
+					let kept = self.pairs[&key].clone();
+					self.warn(Warning::DuplicateDiscarded { key, kept, discarded: value.clone() });
+				}
+				return Ok(value);
+			}
+			DuplicatePolicy::KeepLast if exists && self.pairs[&key] != value => {
+				let discarded = self.pairs[&key].clone();
+				self.warn(Warning::DuplicateDiscarded { key: key.clone(), kept: value.clone(), discarded });
+			}
+			DuplicatePolicy::Collect => self.pairs_multi.entry(key.clone()).or_default().push(value.clone()),
+			_ => {},
+		}
+
+		self.pairs.insert(key, value.clone());
+		Ok(value)
+	}
+
+	/// Stores a flag, counting its occurrences unless the [duplicates policy](ParserConfig::duplicates) rejects them.
+	/// 
+	/// This also undoes any earlier `--no-<flag>`.
+	pub(crate) fn store_flag(&mut self, name: String) -> Result<(), CLIError> {
+		let name = self.normalize_key(name);
+		if self.duplicate_policy(&name) == DuplicatePolicy::Error && self.flags.contains(&name) {
+			return Err(CLIError::DuplicateArgument(name));
+		}
+
+		self.negated.remove(&name);
+		*self.counts.entry(name.clone()).or_insert(0) += 1;
+		self.flags.insert(name);
+		Ok(())
+	}
+
+	/// How many times the flag `name` was given, 0 if it was not.
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new().parse_str("-v -v -v -quiet").unwrap();
+	/// 
+	/// assert_eq!(parser.count("v"), 3);
+	/// assert_eq!(parser.count("quiet"), 1);
+	/// assert_eq!(parser.count("debug"), 0);
+	/// ```
+	pub fn count(&self, name: &str) -> usize {
+		self.mark_used(name);
+		self.counts.get(name).copied().unwrap_or(0)
+	}
+
+	/// Whether the flag `name` was given.
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new().parse_str("-verbose --output=out.txt").unwrap();
+	/// 
+	/// assert!(parser.flag("verbose"));
+	/// assert!(!parser.flag("quiet"));
+	/// assert!(parser.has_pair("output"));
+	/// assert_eq!(parser.pair("output"), Some("out.txt"));
+	/// assert_eq!(parser.pair("input"), None);
+	/// ```
+	pub fn flag(&self, name: &str) -> bool {
+		self.mark_used(name);
+		self.flags.contains(name)
+	}
+
+	/// Whether the pair `key` has a value. See [`CLIParser::flag`].
+	pub fn has_pair(&self, key: &str) -> bool {
+		self.mark_used(key);
+		self.pairs.contains_key(key)
+	}
+
+	/// Value of the pair `key`, if it has one. See [`CLIParser::flag`].
+	pub fn pair(&self, key: &str) -> Option<&str> {
+		self.mark_used(key);
+		self.pairs.get(key).map(String::as_str)
+	}
+
+	/// Splits a single string into arguments with shell-style quoting, then parses them like [`CLIParser::parse_from`].
+	/// 
+	/// See [`shlex::split`] for the quoting rules.
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new()
+	///     .parse_str(r#"--name="John Smith" -v input.txt"#)
+	///     .unwrap();
+	/// 
+	/// assert_eq!(parser.posits, vec!["input.txt"]);
+	/// assert!(parser.flags.contains("v"));
+	/// assert_eq!(parser.pairs["name"], "John Smith");
+	/// ```
+	pub fn parse_str(self, input: &str) -> Result<Self, CLIError> {
+		let args = shlex::split(input)?;
+		self.parse_from(args)
 	}
 
 }
 
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::{CLIError, CLIParser, ParserConfig};
+
+	#[test]
+	fn push_arg_rejects_malformed_and_keeps_state() {
+		let mut parser = CLIParser::new().parse_str("-verbose").unwrap();
+
+		assert_eq!(parser.push_arg("---x"), Err(CLIError::DashesMalformed("---x".to_string())));
+		assert_eq!(parser.push_arg("-a=1"), Err(CLIError::FlagWithSign("-a=1".to_string())));
+		assert_eq!(parser.push_arg("--key"), Err(CLIError::PairMissingSign("--key".to_string())));
+
+		parser.push_arg("--key=value").unwrap();
+		assert!(parser.flags.contains("verbose"));
+		assert_eq!(parser.pairs["key"], "value");
+		assert!(parser.posits.is_empty());
+	}
+
+	#[test]
+	fn extend_args_stops_at_first_malformed() {
+		let mut parser = CLIParser::new();
+
+		let error = parser.extend_args(["a", "-b", "--key=", "d"].map(String::from)).unwrap_err();
+		assert_eq!(error, CLIError::PairBadSign("--key=".to_string()));
+		assert_eq!(parser.posits, vec!["a"]);
+		assert!(parser.flags.contains("b"));
+	}
+
+	#[test]
+	fn extend_args_keeps_separator_across_calls() {
+		let mut parser = CLIParser::new();
+
+		parser.extend_args(["--", "-a"].map(String::from)).unwrap();
+		parser.extend_args(["-b"].map(String::from)).unwrap();
+		assert_eq!(parser.posits, vec!["-a", "-b"]);
+		assert!(parser.flags.is_empty());
+	}
+
+	#[test]
+	fn extend_args_reports_response_file_errors() {
+		let path = std::env::temp_dir().join("cliparser-extend-missing-test.txt");
+		let _ = std::fs::remove_file(&path);
+
+		let mut parser = CLIParser::new().config(ParserConfig { response_files: true, ..ParserConfig::default() });
+		let error = parser.extend_args([format!("@{}", path.display())]).unwrap_err();
+		assert!(matches!(error, CLIError::ResponseFile(..)));
+		assert!(parser.posits.is_empty());
+	}
+
+}
@@ -0,0 +1,138 @@
+//! Man pages generated from the [`Schema`], in the `man(7)` macros of troff.
+
+use crate::{ArgKind, ArgSpec, Schema};
+use crate::help::{description, placeholder, positional_name};
+
+
+/// Escapes text for troff, so that backslashes and dashes print as typed and no line reads as a request.
+fn escape(text: &str) -> String {
+	let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+	escaped.split('\n')
+		.map(|line| match line.starts_with(['.', '\'']) {
+			true => format!("\\&{}", line),
+			false => line.to_string(),
+		})
+		.collect::<Vec<String>>()
+		.join("\n")
+}
+
+
+/// Bold names and italic placeholder of an argument.
+fn names(spec: &ArgSpec) -> String {
+	let long = match spec.kind {
+		ArgKind::Flag => format!("\\fB\\-{}\\fR", escape(&spec.name)),
+		ArgKind::Pair => {
+			let value = placeholder(spec);
+			format!("\\fB\\-\\-{}\\fR=\\fI{}\\fR", escape(&spec.name), escape(&value[1..value.len() - 1]))
+		}
+		ArgKind::Positional => return format!("\\fI{}\\fR", escape(&positional_name(spec))),
+	};
+
+	match &spec.short {
+		Some(short) => format!("\\fB\\-{}\\fR, {}", escape(short), long),
+		None => long,
+	}
+}
+
+
+/// Tagged paragraphs of a section, one per argument.
+fn section(title: &str, specs: &[&ArgSpec]) -> String {
+	let mut text = format!(".SH {}\n", title);
+	for spec in specs {
+		text.push_str(&format!(".TP\n{}\n", names(spec)));
+		let description = description(spec);
+		if !description.is_empty() {
+			text.push_str(&format!("{}\n", escape(&description)));
+		}
+	}
+	text
+}
+
+
+impl Schema {
+
+	/// Man page of the program, for section 1 of the manual.
+	/// 
+	/// Made of the NAME, SYNOPSIS, COMMANDS, ARGUMENTS, OPTIONS and VERSION sections, from the [program name](Schema::program),
+	/// [description](Schema::about), [version](Schema::version) and the help of every argument, leaving out [hidden](Schema::hidden) ones.
+	/// COMMANDS lists the usage and description of each [subcommand](Schema::command).
+	/// Meant to be written to a file at build time, e.g. by a small generator binary.
+	/// 
+	/// ```
+	/// use cliparser::{Schema, ValueType};
+	/// 
+	/// let schema = Schema::new()
+	///     .program("mytool")
+	///     .about("Convert data files")
+	///     .version("1.4.0")
+	///     .flag("verbose").short("v").help("Print more details")
+	///     .pair("output").value_type(ValueType::Path).help("Where to write")
+	///     .pair("config").help("Settings file, e.g.\n.mytoolrc")
+	///     .positional("input").required();
+	/// 
+	/// let page = schema.render_man();
+	/// 
+	/// assert!(page.starts_with(".TH MYTOOL 1 \"\" \"mytool 1.4.0\"\n.SH NAME\nmytool \\- Convert data files\n"));
+	/// assert!(page.contains(".TP\n\\fB\\-v\\fR, \\fB\\-verbose\\fR\nPrint more details\n"));
+	/// assert!(page.contains(".TP\n\\fB\\-\\-output\\fR=\\fIpath\\fR\nWhere to write\n"));
+	/// assert!(page.contains("Settings file, e.g.\n\\&.mytoolrc\n"));
+	/// 
+	/// let schema = Schema::new()
+	///     .program("mytool")
+	///     .command("build", Schema::new().about("Compile the project").flag("release")).command_alias("b");
+	/// 
+	/// let page = schema.render_man();
+	/// 
+	/// assert!(page.contains(".SH COMMANDS\n.TP\n\\fBbuild\\fR [OPTIONS]\nCompile the project [aliases: b]\n"));
+	/// ```
+	pub fn render_man(&self) -> String {
+		let program = self.display_name();
+		let version = match &self.version {
+			Some(version) => format!("{} {}", program, version),
+			None => program.clone(),
+		};
+
+		let mut page = format!(".TH {} 1 \"\" \"{}\"\n", escape(&program.to_uppercase()), escape(&version));
+
+		page.push_str(".SH NAME\n");
+		match &self.about {
+			Some(about) => page.push_str(&format!("{} \\- {}\n", escape(&program), escape(about))),
+			None => page.push_str(&format!("{}\n", escape(&program))),
+		}
+
+		let synopsis = self.synopsis(&program);
+		let arguments = synopsis.strip_prefix(program.as_str()).unwrap_or_default();
+		page.push_str(&format!(".SH SYNOPSIS\n\\fB{}\\fR{}\n", escape(&program), escape(arguments)));
+
+		if !self.commands.is_empty() {
+			page.push_str(".SH COMMANDS\n");
+			for command in &self.commands {
+				let synopsis = command.schema.synopsis(&command.name);
+				let arguments = synopsis.strip_prefix(command.name.as_str()).unwrap_or_default();
+				page.push_str(&format!(".TP\n\\fB{}\\fR{}\n", escape(&command.name), escape(arguments)));
+
+				let description = self.command_description(command);
+				if !description.is_empty() {
+					page.push_str(&format!("{}\n", escape(&description)));
+				}
+			}
+		}
+
+		let positionals: Vec<&ArgSpec> = self.positionals().filter(|spec| !spec.hidden).collect();
+		if !positionals.is_empty() {
+			page.push_str(&section("ARGUMENTS", &positionals));
+		}
+
+		let options: Vec<&ArgSpec> = self.options().filter(|spec| !spec.hidden).collect();
+		if !options.is_empty() {
+			page.push_str(&section("OPTIONS", &options));
+		}
+
+		if let Some(version) = &self.version {
+			page.push_str(&format!(".SH VERSION\nv{}\n", escape(version)));
+		}
+
+		page
+	}
+
+}
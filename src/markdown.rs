@@ -0,0 +1,134 @@
+//! Markdown reference of the command line, generated from the [`Schema`] for documentation sites.
+
+use crate::{ArgKind, ArgSpec, Schema};
+use crate::help::{placeholder, positional_name};
+
+
+/// Keeps pipes and line breaks from ending a table cell.
+fn cell(text: &str) -> String {
+	text.replace('|', "\\|").replace('\n', " ")
+}
+
+
+/// Row of the table of arguments: names, type, default value, environment variable and help.
+fn row(spec: &ArgSpec) -> String {
+	let names = match spec.kind {
+		ArgKind::Flag => format!("`-{}`", spec.name),
+		ArgKind::Pair => format!("`--{}`", spec.name),
+		ArgKind::Positional => format!("`{}`", positional_name(spec)),
+	};
+	let names = match &spec.short {
+		Some(short) => format!("`-{}`, {}", short, names),
+		None => names,
+	};
+
+	let value_type = match spec.kind {
+		ArgKind::Flag => "flag".to_string(),
+		_ => {
+			let value = placeholder(spec);
+			format!("`{}`", &value[1..value.len() - 1])
+		}
+	};
+	let default = spec.default.as_ref().map(|default| format!("`{}`", default)).unwrap_or_default();
+	let env = spec.env.as_ref().map(|env| format!("`{}`", env)).unwrap_or_default();
+
+	let mut description = spec.help.clone().unwrap_or_default();
+	if spec.required {
+		description = format!("**Required.** {}", description);
+	}
+	if spec.deprecated || spec.replacement.is_some() {
+		let replacement = spec.replacement.as_ref().map(|replacement| format!(", use `{}`", replacement)).unwrap_or_default();
+		description = format!("{} *Deprecated{}.*", description, replacement);
+	}
+
+	let cells = [names, value_type, default, env, description.trim().to_string()];
+	format!("| {} |\n", cells.iter().map(|text| cell(text)).collect::<Vec<String>>().join(" | "))
+}
+
+
+/// Table of arguments under a heading.
+fn table(heading: &str, first_column: &str, specs: &[&ArgSpec]) -> String {
+	let mut text = format!("{}\n\n| {} | Type | Default | Environment | Description |\n|---|---|---|---|---|\n", heading, first_column);
+	for spec in specs {
+		text.push_str(&row(spec));
+	}
+	text
+}
+
+
+impl Schema {
+
+	/// Markdown reference of the program, with its [description](Schema::about), usage, and tables of arguments.
+	/// 
+	/// The tables list the names, type, default value, environment variable and help of every argument,
+	/// leaving out [hidden](Schema::hidden) ones. Meant to be generated along with the documentation, so that it never drifts.
+	/// [Subcommands](Schema::command) are listed in a table of commands, and each gets a section of its own one level down.
+	/// 
+	/// ```
+	/// use cliparser::{Schema, ValueType};
+	/// 
+	/// let schema = Schema::new()
+	///     .program("mytool")
+	///     .pair("threads").short("j").value_type(ValueType::Int).default_value("4").env("MYTOOL_THREADS").help("Worker count")
+	///     .pair("format").one_of(["json", "yaml"]);
+	/// 
+	/// let reference = schema.render_markdown();
+	/// 
+	/// assert!(reference.starts_with("# mytool\n"));
+	/// assert!(reference.contains("| `-j`, `--threads` | `int` | `4` | `MYTOOL_THREADS` | Worker count |\n"));
+	/// assert!(reference.contains("| `--format` | `json\\|yaml` |  |  |  |\n"));
+	/// 
+	/// let schema = Schema::new()
+	///     .program("mytool")
+	///     .command("build", Schema::new().about("Compile the project").flag("release"));
+	/// 
+	/// let reference = schema.render_markdown();
+	/// 
+	/// assert!(reference.contains("## Commands\n\n| Command | Description |\n|---|---|\n| `build` | Compile the project |\n"));
+	/// assert!(reference.contains("## mytool build\n\nCompile the project\n\n### Usage\n\n```text\nmytool build [OPTIONS]\n```\n"));
+	/// assert!(reference.contains("### Options\n"));
+	/// ```
+	pub fn render_markdown(&self) -> String {
+		let program = self.display_name();
+		self.markdown(&program, 1)
+	}
+
+	/// Reference of the program or subcommand invoked as `program`, under a heading of `level`.
+	fn markdown(&self, program: &str, level: usize) -> String {
+		// Markdown has six levels of headings
+		let heading = |title: &str, level: usize| format!("{} {}", "#".repeat(level.min(6)), title);
+
+		let mut text = format!("{}\n\n", heading(program, level));
+		if let Some(about) = &self.about {
+			text.push_str(&format!("{}\n\n", about));
+		}
+		text.push_str(&format!("{}\n\n```text\n{}\n```\n", heading("Usage", level + 1), self.synopsis(program)));
+
+		if !self.commands.is_empty() {
+			text.push_str(&format!("\n{}\n\n| Command | Description |\n|---|---|\n", heading("Commands", level + 1)));
+			for command in &self.commands {
+				text.push_str(&format!("| `{}` | {} |\n", cell(&command.name), cell(&self.command_description(command))));
+			}
+		}
+
+		let positionals: Vec<&ArgSpec> = self.positionals().filter(|spec| !spec.hidden).collect();
+		if !positionals.is_empty() {
+			text.push('\n');
+			text.push_str(&table(&heading("Arguments", level + 1), "Argument", &positionals));
+		}
+
+		let options: Vec<&ArgSpec> = self.options().filter(|spec| !spec.hidden).collect();
+		if !options.is_empty() {
+			text.push('\n');
+			text.push_str(&table(&heading("Options", level + 1), "Option", &options));
+		}
+
+		for command in &self.commands {
+			text.push('\n');
+			text.push_str(&command.schema.markdown(&format!("{} {}", program, command.name), level + 1));
+		}
+
+		text
+	}
+
+}
@@ -0,0 +1,173 @@
+//! Views over dotted keys, e.g. `db.host` and `db.port` under the `db` namespace.
+
+use std::collections::BTreeMap;
+use std::ops::Index;
+
+use crate::CLIParser;
+
+
+/// **Namespaced view** of the flags and pairs whose keys start with `<name>.`.
+/// 
+/// Keys are seen relative to the namespace, and namespaces nest. See [`Tree`] for the keys resolved into nested tables.
+/// 
+/// ```
+/// let parser = cliparser::CLIParser::new()
+///     .parse_str("--db.host=localhost --db.port=5432 --db.replica.host=backup -db.tls --name=app")
+///     .unwrap();
+/// 
+/// let db = parser.namespace("db");
+/// assert_eq!(db.get("host"), Some("localhost"));
+/// assert_eq!(db.get("port"), Some("5432"));
+/// assert!(db.has_flag("tls"));
+/// assert_eq!(db.namespace("replica").get("host"), Some("backup"));
+/// assert_eq!(db.children(), vec!["replica"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Namespace<'a> {
+	parser: &'a CLIParser,
+	prefix: String,
+}
+
+
+impl<'a> Namespace<'a> {
+
+	/// Full dotted name of the namespace.
+	pub fn name(&self) -> &str {
+		&self.prefix[..self.prefix.len() - 1]
+	}
+
+	/// Value of the pair `key`, relative to the namespace.
+	pub fn get(&self, key: &str) -> Option<&'a str> {
+		self.parser.pair(&format!("{}{}", self.prefix, key))
+	}
+
+	/// Whether the flag `name`, relative to the namespace, was given.
+	pub fn has_flag(&self, name: &str) -> bool {
+		self.parser.flag(&format!("{}{}", self.prefix, name))
+	}
+
+	/// All pairs under the namespace, with their keys relative to it.
+	pub fn pairs(&self) -> impl Iterator<Item = (&'a str, &'a str)> + '_ {
+		self.parser.pairs.iter()
+			.filter_map(|(key, value)| Some((key.strip_prefix(self.prefix.as_str())?, value.as_str())))
+	}
+
+	/// All flags under the namespace, with their names relative to it.
+	pub fn flags(&self) -> impl Iterator<Item = &'a str> + '_ {
+		self.parser.flags.iter()
+			.filter_map(|name| name.strip_prefix(self.prefix.as_str()))
+	}
+
+	/// Nested namespace, e.g. `db.replica` for `replica` within `db`.
+	pub fn namespace(&self, name: &str) -> Namespace<'a> {
+		Namespace {
+			parser: self.parser,
+			prefix: format!("{}{}.", self.prefix, name),
+		}
+	}
+
+	/// Names of the namespaces directly nested in this one, sorted.
+	pub fn children(&self) -> Vec<&'a str> {
+		let keys = self.pairs().map(|(key, _)| key).chain(self.flags());
+
+		let mut children: Vec<&str> = keys.filter_map(|key| Some(key.split_once('.')?.0)).collect();
+		children.sort_unstable();
+		children.dedup();
+		children
+	}
+
+	/// The flags and pairs under the namespace, as a [`Tree`] of their relative keys.
+	pub fn tree(&self) -> Tree {
+		Tree::build(self.pairs(), self.flags())
+	}
+
+}
+
+
+/// **Nested values**, the dotted keys of flags and pairs resolved into tables of tables.
+/// 
+/// Each node stands for a key, with the value of the pair and the flag of that name, if given,
+/// and the nodes of the keys nested under it. A key can be both, e.g. `--db=main --db.host=localhost`.
+/// 
+/// ```
+/// let parser = cliparser::CLIParser::new()
+///     .parse_str("--db.host=localhost --db.port=5432 --db.replica.host=backup -db.tls --name=app")
+///     .unwrap();
+/// 
+/// let tree = parser.tree();
+/// assert_eq!(tree["db"]["host"].value.as_deref(), Some("localhost"));
+/// assert_eq!(tree["db"]["replica"]["host"].value.as_deref(), Some("backup"));
+/// assert!(tree["db"]["tls"].flag);
+/// assert_eq!(tree["name"].value.as_deref(), Some("app"));
+/// assert_eq!(tree["db"].children.keys().collect::<Vec<_>>(), ["host", "port", "replica", "tls"]);
+/// 
+/// assert_eq!(tree.get("db.replica.host"), Some(&tree["db"]["replica"]["host"]));
+/// assert_eq!(tree.get("db.user"), None);
+/// assert_eq!(parser.namespace("db").tree(), tree["db"]);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Tree {
+	/// Value of the pair of this key.
+	pub value: Option<String>,
+	/// Whether the flag of this key was given.
+	pub flag: bool,
+	/// Nodes of the keys directly nested under this one, by their relative names.
+	pub children: BTreeMap<String, Tree>,
+}
+
+
+impl Tree {
+
+	fn build<'a>(pairs: impl Iterator<Item = (&'a str, &'a str)>, flags: impl Iterator<Item = &'a str>) -> Self {
+		let mut tree = Self::default();
+		for (key, value) in pairs {
+			tree.node(key).value = Some(value.to_string());
+		}
+		for name in flags {
+			tree.node(name).flag = true;
+		}
+		tree
+	}
+
+	/// The node of the dotted `key`, created along with its parents if missing.
+	fn node(&mut self, key: &str) -> &mut Tree {
+		key.split('.').fold(self, |node, name| node.children.entry(name.to_string()).or_default())
+	}
+
+	/// Node of the dotted `key`, relative to this one, if anything was given under it.
+	pub fn get(&self, key: &str) -> Option<&Tree> {
+		key.split('.').try_fold(self, |node, name| node.children.get(name))
+	}
+
+}
+
+
+impl Index<&str> for Tree {
+	type Output = Tree;
+
+	/// Node of the key `name` directly nested under this one.
+	/// 
+	/// Panics if nothing was given under it, see [`Tree::get`].
+	fn index(&self, name: &str) -> &Tree {
+		self.children.get(name).unwrap_or_else(|| panic!("nothing given under `{}`", name))
+	}
+}
+
+
+impl CLIParser {
+
+	/// View of the flags and pairs under the dotted namespace `name`. See [`Namespace`].
+	pub fn namespace(&self, name: &str) -> Namespace<'_> {
+		Namespace {
+			parser: self,
+			prefix: format!("{}.", name),
+		}
+	}
+
+	/// Every flag and pair, with the dotted keys resolved into nested tables. See [`Tree`].
+	pub fn tree(&self) -> Tree {
+		let pairs = self.pairs.iter().map(|(key, value)| (key.as_str(), value.as_str()));
+		Tree::build(pairs, self.flags.iter().map(String::as_str))
+	}
+
+}
@@ -0,0 +1,45 @@
+//! Where each flag and pair appeared on the command line.
+
+use crate::CLIParser;
+
+
+/// A single appearance of a flag or pair on the command line, see [`CLIParser::occurrences`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Occurrence {
+	/// Position among the parsed arguments, after expanding response files.
+	/// With [`CLIParser::init`], the [program path](CLIParser::program) is at 0, as in `std::env::args()`.
+	pub index: usize,
+	/// Value given this time, for pairs.
+	pub value: Option<String>,
+}
+
+
+impl CLIParser {
+
+	/// Every appearance of the flag or pair `name` on the command line, in order.
+	/// 
+	/// Clustered flags share the index of their cluster, and pairs with a space-separated value have the index of their key.
+	/// Values set by other sources have no occurrences.
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Occurrence};
+	/// 
+	/// let parser = CLIParser::new().parse_str("--include=a src -v --include=b").unwrap();
+	/// 
+	/// assert_eq!(parser.occurrences("include"), [
+	///     Occurrence { index: 0, value: Some("a".to_string()) },
+	///     Occurrence { index: 3, value: Some("b".to_string()) },
+	/// ]);
+	/// assert_eq!(parser.occurrences("v"), [Occurrence { index: 2, value: None }]);
+	/// assert!(parser.occurrences("missing").is_empty());
+	/// ```
+	pub fn occurrences(&self, name: &str) -> &[Occurrence] {
+		self.occurrences.get(name).map(Vec::as_slice).unwrap_or_default()
+	}
+
+	pub(crate) fn record_occurrence(&mut self, name: &str, value: Option<String>) {
+		let occurrence = Occurrence { index: self.arg_index, value };
+		self.occurrences.entry(name.to_string()).or_default().push(occurrence);
+	}
+
+}
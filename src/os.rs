@@ -0,0 +1,292 @@
+//! Lossless parsing of arguments that are not guaranteed to be valid UTF-8.
+//!
+//! Arguments go through the very same classification as with [`CLIParser`]. The parts of them that are not valid UTF-8
+//! are set aside first, each one standing in the text as a single character of a private use plane, which none of the rules look at.
+//! They are put back in place in the parsed data structures.
+
+use std::collections::{HashSet, HashMap};
+use std::ffi::{OsStr, OsString};
+
+use crate::{CLIError, CLIParser, ParserConfig, Schema};
+
+
+/// First of the characters standing for the parts set aside, at the start of the supplementary private use area B.
+const MARKER_START: u32 = 0x10_0000;
+
+/// Last of them, the last private use character.
+const MARKER_END: u32 = 0x10_FFFD;
+
+
+/// [`CLIParser`] counterpart, backed by `OsString` instead of `String`.
+///
+/// Arguments are classified with the same rules and against the same [schema](OsCLIParser::schema) and [configuration](OsCLIParser::config),
+/// so any bytes the platform allows survive untouched.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OsCLIParser {
+
+	/// **Positional arguments**. See [`CLIParser::posits`].
+	pub posits: Vec<OsString>,
+
+	/// **Program path**. See [`CLIParser::program`].
+	pub program: Option<OsString>,
+
+	/// **Flags**. See [`CLIParser::flags`].
+	pub flags: HashSet<OsString>,
+
+	/// **Key - value pairs**. See [`CLIParser::pairs`].
+	pub pairs: HashMap<OsString, OsString>,
+
+	/// **Passthrough arguments**. See [`CLIParser::passthrough`].
+	pub passthrough: Vec<OsString>,
+
+	/// The parser classifying the arguments, as text.
+	parser: CLIParser,
+
+	/// The parts of the arguments set aside.
+	markers: Markers,
+}
+
+
+impl OsCLIParser {
+
+	/// Creates a new parser object, with empty data structures.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the parsing behavior switches. See [`CLIParser::config`].
+	pub fn config(mut self, config: ParserConfig) -> Self {
+		self.parser = std::mem::take(&mut self.parser).config(config);
+		self
+	}
+
+	/// Sets the declarations of the expected arguments. See [`CLIParser::schema`].
+	pub fn schema(mut self, schema: Schema) -> Self {
+		self.parser = std::mem::take(&mut self.parser).schema(schema);
+		self
+	}
+
+	/// Parses the `std::env::args_os()` and collects them into data structures.
+	///
+	/// Like [`CLIParser::init`], the program path goes to [`OsCLIParser::program`].
+	/// Will throw error if CLI arguments are considered malformed by this crate.
+	/// Offending arguments are reported lossily converted to UTF-8.
+	pub fn init(mut self) -> Result<Self, CLIError> {
+		let args = self.markers.encode_all(std::env::args_os())?;
+		let keep = self.parser.config.program_in_posits;
+		let args = self.parser.take_program(args, keep);
+		self.parse_encoded(args)
+	}
+
+	/// Parses the given arguments and collects them into data structures.
+	///
+	/// Behaves like [`CLIParser::parse_from`]: there is no program path among the arguments.
+	///
+	/// ```
+	/// use std::ffi::OsString;
+	/// use cliparser::{OsCLIParser, Schema};
+	///
+	/// let schema = Schema::new()
+	///     .flag("verbose").short("v")
+	///     .pair("output").short("o");
+	///
+	/// let args = vec!["-v", "-o", "out.txt", "input.txt", "--", "-literal"];
+	/// let parser = OsCLIParser::new()
+	///     .schema(schema)
+	///     .parse_from(args.into_iter().map(OsString::from))
+	///     .unwrap();
+	///
+	/// assert!(parser.flags.contains(&OsString::from("verbose")));
+	/// assert_eq!(parser.pairs[&OsString::from("output")], OsString::from("out.txt"));
+	/// assert_eq!(parser.posits, vec![OsString::from("input.txt"), OsString::from("-literal")]);
+	/// assert_eq!(parser.validate(), Ok(()));
+	/// ```
+	///
+	/// Arguments that are not valid UTF-8 are kept as they are:
+	///
+	/// ```
+	/// # #[cfg(unix)] {
+	/// use std::ffi::OsStr;
+	/// use std::os::unix::ffi::OsStrExt;
+	///
+	/// let args = vec![OsStr::from_bytes(b"--name=caf\xe9").to_os_string(), OsStr::from_bytes(b"\xff.txt").to_os_string()];
+	/// let parser = cliparser::OsCLIParser::new().parse_from(args).unwrap();
+	///
+	/// assert_eq!(parser.pairs[OsStr::new("name")], OsStr::from_bytes(b"caf\xe9"));
+	/// assert_eq!(parser.posits, vec![OsStr::from_bytes(b"\xff.txt")]);
+	/// # }
+	/// ```
+	pub fn parse_from<I: IntoIterator<Item = OsString>>(mut self, args: I) -> Result<Self, CLIError> {
+		let args = self.markers.encode_all(args)?;
+		self.parse_encoded(args)
+	}
+
+	/// Checks the parsed arguments against the declared ones. See [`CLIParser::validate`].
+	pub fn validate(&self) -> Result<(), CLIError> {
+		self.parser.validate().map_err(|error| self.markers.lossy_error(error))
+	}
+
+	/// Parses the arguments as text, then fills the data structures with the parts set aside put back.
+	fn parse_encoded<I: IntoIterator<Item = String>>(mut self, args: I) -> Result<Self, CLIError> {
+		let parser = std::mem::take(&mut self.parser);
+		self.parser = parser.parse_from(args).map_err(|error| self.markers.lossy_error(error))?;
+
+		let markers = &self.markers;
+		self.program = self.parser.program.as_deref().map(|program| markers.decode(program));
+		self.posits = self.parser.posits.iter().map(|posit| markers.decode(posit)).collect();
+		self.flags = self.parser.flags.iter().map(|flag| markers.decode(flag)).collect();
+		self.pairs = self.parser.pairs.iter().map(|(key, value)| (markers.decode(key), markers.decode(value))).collect();
+		self.passthrough = self.parser.passthrough.iter().map(|argument| markers.decode(argument)).collect();
+
+		Ok(self)
+	}
+
+}
+
+
+/// The parts of the arguments that cannot go through the parser as text, in the order they were set aside.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct Markers {
+	parts: Vec<OsString>,
+}
+
+
+impl Markers {
+
+	fn encode_all<I: IntoIterator<Item = OsString>>(&mut self, args: I) -> Result<Vec<String>, CLIError> {
+		args.into_iter().map(|argument| self.encode(&argument)).collect()
+	}
+
+	/// Text of `argument`, with each run of invalid UTF-8 replaced by a marker.
+	/// Characters that could be mistaken for markers are set aside as well.
+	fn encode(&mut self, argument: &OsStr) -> Result<String, CLIError> {
+		let mut text = String::new();
+		let mut rest = argument.as_encoded_bytes();
+
+		while !rest.is_empty() {
+			let (valid, invalid) = match std::str::from_utf8(rest) {
+				Ok(valid) => (valid, 0),
+				Err(error) => (std::str::from_utf8(&rest[..error.valid_up_to()]).unwrap_or_default(), invalid_run(&rest[error.valid_up_to()..])),
+			};
+
+			for c in valid.chars() {
+				match (MARKER_START..=MARKER_END).contains(&(c as u32)) {
+					true => text.push(self.set_aside(OsString::from(c.to_string()), argument)?),
+					false => text.push(c),
+				}
+			}
+
+			rest = &rest[valid.len()..];
+			if invalid > 0 {
+				// SAFETY: `rest` comes from `OsStr::as_encoded_bytes`, and the run is split off right next to valid UTF-8,
+				// or at the ends of the argument, as `OsStr::from_encoded_bytes_unchecked` allows.
+				let part = unsafe { OsStr::from_encoded_bytes_unchecked(&rest[..invalid]) };
+				text.push(self.set_aside(part.to_os_string(), argument)?);
+				rest = &rest[invalid..];
+			}
+		}
+
+		Ok(text)
+	}
+
+	/// Stores `part`, returning the marker standing for it.
+	fn set_aside(&mut self, part: OsString, argument: &OsStr) -> Result<char, CLIError> {
+		let marker = u32::try_from(self.parts.len()).ok()
+			.and_then(|index| MARKER_START.checked_add(index))
+			.filter(|marker| *marker <= MARKER_END)
+			.and_then(char::from_u32)
+			.ok_or_else(|| CLIError::InvalidUtf8(argument.to_string_lossy().into_owned()))?;
+		self.parts.push(part);
+		Ok(marker)
+	}
+
+	/// The part set aside for `marker`, if it is one.
+	fn part(&self, marker: char) -> Option<&OsStr> {
+		let index = (marker as u32).checked_sub(MARKER_START)?;
+		self.parts.get(index as usize).map(OsString::as_os_str)
+	}
+
+	/// `text` with the parts set aside put back in place of their markers.
+	fn decode(&self, text: &str) -> OsString {
+		let mut decoded = OsString::new();
+		let mut start = 0;
+
+		for (i, c) in text.char_indices() {
+			if let Some(part) = self.part(c) {
+				decoded.push(&text[start..i]);
+				decoded.push(part);
+				start = i + c.len_utf8();
+			}
+		}
+		decoded.push(&text[start..]);
+
+		decoded
+	}
+
+	/// `text` with the parts set aside put back, lossily converted to UTF-8.
+	fn lossy(&self, text: String) -> String {
+		match text.chars().any(|c| self.part(c).is_some()) {
+			true => self.decode(&text).to_string_lossy().into_owned(),
+			false => text,
+		}
+	}
+
+	/// `error` with the parts set aside put back in every argument it reports.
+	fn lossy_error(&self, error: CLIError) -> CLIError {
+		let lossy = |text: String| self.lossy(text);
+		let lossy_all = |texts: Vec<String>| texts.into_iter().map(lossy).collect();
+
+		match error {
+			CLIError::FlagWithSign(arg) => CLIError::FlagWithSign(lossy(arg)),
+			CLIError::FlagMalformed(arg) => CLIError::FlagMalformed(lossy(arg)),
+			CLIError::PairMissingSign(arg) => CLIError::PairMissingSign(lossy(arg)),
+			CLIError::PairBadSign(arg) => CLIError::PairBadSign(lossy(arg)),
+			CLIError::PairMalformed(arg) => CLIError::PairMalformed(lossy(arg)),
+			CLIError::DashesMalformed(arg) => CLIError::DashesMalformed(lossy(arg)),
+			CLIError::QuoteUnterminated(arg) => CLIError::QuoteUnterminated(lossy(arg)),
+			CLIError::EscapeUnterminated(arg) => CLIError::EscapeUnterminated(lossy(arg)),
+			CLIError::InvalidUtf8(arg) => CLIError::InvalidUtf8(lossy(arg)),
+			CLIError::ResponseFile(path, reason) => CLIError::ResponseFile(lossy(path), reason),
+			CLIError::ConfigFile(path, reason) => CLIError::ConfigFile(lossy(path), reason),
+			CLIError::ConfigMalformed(location, reason) => CLIError::ConfigMalformed(lossy(location), reason),
+			CLIError::ConfigMismatch(location, reason) => CLIError::ConfigMismatch(lossy(location), reason),
+			CLIError::Stdin(reason) => CLIError::Stdin(reason),
+			CLIError::AmbiguousAbbreviation(given, names) => CLIError::AmbiguousAbbreviation(lossy(given), names),
+			CLIError::AmbiguousCommand(given, names) => CLIError::AmbiguousCommand(lossy(given), names),
+			CLIError::DuplicateArgument(name) => CLIError::DuplicateArgument(lossy(name)),
+			CLIError::InvalidValue { key, value, expected } => CLIError::InvalidValue { key: lossy(key), value: lossy(value), expected },
+			CLIError::UnknownVariable(name, location) => CLIError::UnknownVariable(lossy(name), lossy(location)),
+			CLIError::ValueParse { key, value, type_name, reason } => CLIError::ValueParse { key: lossy(key), value: lossy(value), type_name, reason },
+			CLIError::UnregisteredType { key, type_name } => CLIError::UnregisteredType { key, type_name },
+			CLIError::InvalidPattern { key, pattern, reason } => CLIError::InvalidPattern { key, pattern, reason },
+			CLIError::UnknownArgument { given, suggestions } => CLIError::UnknownArgument { given: lossy(given), suggestions },
+			CLIError::UnknownCommand { given, suggestion } => CLIError::UnknownCommand { given: lossy(given), suggestion },
+			CLIError::MissingRequired(names) => CLIError::MissingRequired(names),
+			CLIError::InvalidChoice { key, value, choices, suggestion } => CLIError::InvalidChoice { key: lossy(key), value: lossy(value), choices, suggestion },
+			CLIError::Conflict(first, second) => CLIError::Conflict(lossy(first), lossy(second)),
+			CLIError::MissingDependency(name, dependency) => CLIError::MissingDependency(lossy(name), dependency),
+			CLIError::UnexpectedPositional(arg) => CLIError::UnexpectedPositional(lossy(arg)),
+			CLIError::ValidationFailed { key, message } => CLIError::ValidationFailed { key: lossy(key), message },
+			CLIError::OutOfRange { key, value, range } => CLIError::OutOfRange { key: lossy(key), value: lossy(value), range },
+			CLIError::InvalidPath { key, path, reason } => CLIError::InvalidPath { key: lossy(key), path: lossy(path), reason },
+			CLIError::MissingRequiredUnless(name, others) => CLIError::MissingRequiredUnless(name, others),
+			CLIError::UnusedArguments(names) => CLIError::UnusedArguments(lossy_all(names)),
+			CLIError::HelpRequested(text) => CLIError::HelpRequested(text),
+			CLIError::VersionRequested(text) => CLIError::VersionRequested(text),
+		}
+	}
+
+}
+
+
+/// Length of the run of invalid UTF-8 that `bytes` starts with, up to where valid UTF-8 starts again.
+fn invalid_run(bytes: &[u8]) -> usize {
+	let mut length = 0;
+	while length < bytes.len() {
+		match std::str::from_utf8(&bytes[length..]) {
+			Err(error) if error.valid_up_to() == 0 => length += error.error_len().unwrap_or(bytes.len() - length),
+			_ => break,
+		}
+	}
+	length
+}
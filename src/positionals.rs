@@ -0,0 +1,169 @@
+//! Indexed access to the positional arguments, and named access to those declared in the [`Schema`](crate::Schema).
+
+use std::ops::{Bound, Range, RangeBounds};
+
+use crate::{ArgSpec, CLIParser};
+
+
+impl CLIParser {
+
+	/// The positional arguments typed by the user, leaving out the program path kept with [`ParserConfig::program_in_posits`](crate::ParserConfig::program_in_posits).
+	pub(crate) fn operands(&self) -> &[String] {
+		let kept = self.config.program_in_posits && self.program.is_some() && self.posits.first() == self.program.as_ref();
+		match kept {
+			true => &self.posits[1..],
+			false => &self.posits,
+		}
+	}
+
+	/// The positional argument at `index`, counting from 0 and never counting the program path.
+	/// 
+	/// This lines up with what the user typed even when [`ParserConfig::program_in_posits`](crate::ParserConfig::program_in_posits)
+	/// keeps the program path in [`CLIParser::posits`].
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new().parse_str("build -release core cli").unwrap();
+	/// 
+	/// assert_eq!(parser.first_posit(), Some("build"));
+	/// assert_eq!(parser.posit(1), Some("core"));
+	/// assert_eq!(parser.posit(3), None);
+	/// assert_eq!(parser.posits_after(0), ["core", "cli"]);
+	/// assert!(parser.posits_after(5).is_empty());
+	/// assert_eq!(parser.posit(usize::MAX), None);
+	/// assert!(parser.posits_after(usize::MAX).is_empty());
+	/// ```
+	pub fn posit(&self, index: usize) -> Option<&str> {
+		self.mark_posits_used(index..index.saturating_add(1));
+		self.operands().get(index).map(String::as_str)
+	}
+
+	/// The first positional argument, typically a command word. See [`CLIParser::posit`].
+	pub fn first_posit(&self) -> Option<&str> {
+		self.posit(0)
+	}
+
+	/// The positional arguments after the one at `index`, empty if there are none. See [`CLIParser::posit`].
+	pub fn posits_after(&self, index: usize) -> &[String] {
+		let start = index.saturating_add(1).min(self.operands().len());
+		self.mark_posits_used(start..self.operands().len());
+		&self.operands()[start..]
+	}
+
+	/// The positional arguments within `range`, clamped to those there are. See [`CLIParser::posit`].
+	/// 
+	/// ```
+	/// use std::ops::Bound;
+	/// 
+	/// let parser = cliparser::CLIParser::new().parse_str("run a b c").unwrap();
+	/// 
+	/// assert_eq!(parser.posits_range(1..), ["a", "b", "c"]);
+	/// assert_eq!(parser.posits_range(..2), ["run", "a"]);
+	/// assert_eq!(parser.posits_range(2..10), ["b", "c"]);
+	/// assert!(parser.posits_range(7..).is_empty());
+	/// assert_eq!(parser.posits_range(2..=usize::MAX), ["b", "c"]);
+	/// assert!(parser.posits_range((Bound::Excluded(usize::MAX), Bound::Unbounded)).is_empty());
+	/// ```
+	pub fn posits_range<R: RangeBounds<usize>>(&self, range: R) -> &[String] {
+		let len = self.operands().len();
+		let start = match range.start_bound() {
+			Bound::Included(&start) => start,
+			Bound::Excluded(&start) => start.saturating_add(1),
+			Bound::Unbounded => 0,
+		};
+		let end = match range.end_bound() {
+			Bound::Included(&end) => end.saturating_add(1),
+			Bound::Excluded(&end) => end,
+			Bound::Unbounded => len,
+		};
+
+		let range = start.min(len)..end.clamp(start.min(len), len);
+		self.mark_posits_used(range.clone());
+		&self.operands()[range]
+	}
+
+	/// Splits the positional arguments around the first one matching `predicate`, typically a command word.
+	/// 
+	/// Returns those before it, the match itself and those after it, or `None` if none matches. See [`CLIParser::posit`].
+	/// 
+	/// ```
+	/// let commands = ["build", "test"];
+	/// let parser = cliparser::CLIParser::new().parse_str("ws/core extra test -- --nocapture unit").unwrap();
+	/// 
+	/// let (before, command, operands) = parser.split_at_first_posit_matching(|posit| commands.contains(&posit)).unwrap();
+	/// 
+	/// assert_eq!(before, ["ws/core", "extra"]);
+	/// assert_eq!(command, "test");
+	/// assert_eq!(operands, ["--nocapture", "unit"]);
+	/// assert!(parser.split_at_first_posit_matching(|posit| posit == "deploy").is_none());
+	/// ```
+	pub fn split_at_first_posit_matching<F: FnMut(&str) -> bool>(&self, mut predicate: F) -> Option<(&[String], &str, &[String])> {
+		let operands = self.operands();
+		let index = operands.iter().position(|posit| predicate(posit))?;
+
+		self.mark_posits_used(0..operands.len());
+		Some((&operands[..index], &operands[index], &operands[index + 1..]))
+	}
+
+	/// Matches the declared positional arguments to the indices of [`CLIParser::posits`] they cover, in declaration order.
+	/// 
+	/// Every positional argument takes a single value, except [variadic](crate::Schema::variadic) ones,
+	/// which take whatever the ones declared after them leave. Missing arguments get an empty range.
+	pub(crate) fn positional_ranges(&self) -> Vec<(&ArgSpec, Range<usize>)> {
+		let declared: Vec<&ArgSpec> = match &self.schema {
+			Some(schema) => schema.positionals().collect(),
+			None => return Vec::new(),
+		};
+
+		let mut ranges = Vec::new();
+		let mut start = 0;
+
+		for (i, spec) in declared.iter().enumerate() {
+			let end = if spec.variadic {
+				let after = declared[i + 1..].iter().filter(|spec| !spec.variadic).count();
+				self.operands().len().saturating_sub(after).max(start)
+			} else {
+				(start + 1).min(self.operands().len()).max(start)
+			};
+
+			ranges.push((*spec, start..end));
+			start = end;
+		}
+
+		ranges
+	}
+
+	/// Value of the declared positional argument `name`, or the first one if it is variadic.
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .positional("input").required()
+	///     .positional("extras").variadic()
+	///     .positional("output");
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("a.txt b.txt c.txt out.txt").unwrap();
+	/// 
+	/// assert_eq!(parser.positional("input"), Some("a.txt"));
+	/// assert_eq!(parser.positional("output"), Some("out.txt"));
+	/// assert_eq!(parser.positional_values("extras"), ["b.txt", "c.txt"]);
+	/// assert_eq!(parser.positional("missing"), None);
+	/// ```
+	pub fn positional(&self, name: &str) -> Option<&str> {
+		self.positional_values(name).first().map(String::as_str)
+	}
+
+	/// All values of the declared positional argument `name`, empty if it is missing.
+	/// 
+	/// See [`CLIParser::positional`].
+	pub fn positional_values(&self, name: &str) -> &[String] {
+		match self.positional_ranges().into_iter().find(|(spec, _)| spec.name == name) {
+			Some((_, range)) => {
+				self.mark_posits_used(range.clone());
+				&self.operands()[range]
+			}
+			None => &[],
+		}
+	}
+
+}
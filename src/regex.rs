@@ -0,0 +1,445 @@
+//! Minimal regular expressions, for [`Schema::matches`](crate::Schema::matches).
+//!
+//! Covers the everyday syntax of input validation: literals, `.`, character classes with ranges,
+//! `\d`, `\w`, `\s` and their negations, `^` and `$` anchors, groups with alternation,
+//! and the `*`, `+`, `?` and `{n,m}` quantifiers, greedy or lazy.
+//!
+//! Patterns compile to a Thompson NFA, which is simulated over every alternative at once, without backtracking
+//! and without recursion, so matching takes time linear in the length of the text whatever the pattern.
+
+
+/// A single element of a pattern.
+#[derive(Clone, Debug)]
+enum Node {
+	Char(char),
+	Any,
+	Class { ranges: Vec<(char, char)>, negated: bool },
+	Start,
+	End,
+	/// Alternatives, each a sequence of nodes.
+	Group(Vec<Vec<Node>>),
+	Repeat { node: Box<Node>, min: usize, max: Option<usize> },
+}
+
+
+/// A single instruction of a compiled pattern.
+#[derive(Clone, Debug)]
+enum Inst {
+	Char(char),
+	Any,
+	Class { ranges: Vec<(char, char)>, negated: bool },
+	Start,
+	End,
+	/// Carries on at both targets.
+	Split(usize, usize),
+	Jump(usize),
+	Match,
+}
+
+
+/// Most instructions a pattern compiles to, as counted repetitions multiply them.
+const MAX_PROGRAM: usize = 10_000;
+
+
+/// A compiled pattern.
+#[derive(Clone, Debug)]
+pub(crate) struct Regex {
+	program: Vec<Inst>,
+}
+
+
+/// Ranges of `\d`, `\w` and `\s`.
+fn shorthand_ranges(c: char) -> Option<Vec<(char, char)>> {
+	match c {
+		'd' => Some(vec![('0', '9')]),
+		'w' => Some(vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')]),
+		's' => Some(vec![(' ', ' '), ('\t', '\r')]),
+		_ => None,
+	}
+}
+
+
+/// Character escaped by a backslash, outside of the shorthand classes.
+fn escaped_char(c: char) -> char {
+	match c {
+		'n' => '\n',
+		'r' => '\r',
+		't' => '\t',
+		c => c,
+	}
+}
+
+
+/// Character cursor over the pattern.
+struct Parser {
+	chars: Vec<char>,
+	pos: usize,
+}
+
+
+impl Parser {
+
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.pos).copied()
+	}
+
+	fn bump(&mut self) -> Option<char> {
+		let c = self.peek()?;
+		self.pos += 1;
+		Some(c)
+	}
+
+	/// Alternatives separated by `|`, up to a closing parenthesis or the end.
+	fn alternation(&mut self) -> Result<Vec<Vec<Node>>, String> {
+		let mut alternatives = vec![self.sequence()?];
+		while self.peek() == Some('|') {
+			self.bump();
+			alternatives.push(self.sequence()?);
+		}
+		Ok(alternatives)
+	}
+
+	fn sequence(&mut self) -> Result<Vec<Node>, String> {
+		let mut nodes: Vec<Node> = Vec::new();
+
+		while let Some(c) = self.peek() {
+			if c == '|' || c == ')' {
+				break;
+			}
+			let atom = self.atom()?;
+			nodes.push(self.quantifier(atom)?);
+		}
+
+		Ok(nodes)
+	}
+
+	fn atom(&mut self) -> Result<Node, String> {
+		match self.bump() {
+			Some('(') => {
+				if self.chars[self.pos..].starts_with(&['?', ':']) {
+					self.pos += 2;
+				}
+				let alternatives = self.alternation()?;
+				if self.bump() != Some(')') {
+					return Err("unclosed group".to_string());
+				}
+				Ok(Node::Group(alternatives))
+			}
+			Some('[') => self.class(),
+			Some('.') => Ok(Node::Any),
+			Some('^') => Ok(Node::Start),
+			Some('$') => Ok(Node::End),
+			Some('\\') => match self.bump() {
+				Some(c) if shorthand_ranges(c.to_ascii_lowercase()).is_some() => Ok(Node::Class {
+					ranges: shorthand_ranges(c.to_ascii_lowercase()).unwrap(),
+					negated: c.is_ascii_uppercase(),
+				}),
+				Some(c) => Ok(Node::Char(escaped_char(c))),
+				None => Err("trailing backslash".to_string()),
+			},
+			Some(c @ ('*' | '+' | '?' | '{')) => Err(format!("nothing to repeat before `{}`", c)),
+			Some(c) => Ok(Node::Char(c)),
+			None => Err("unexpected end of pattern".to_string()),
+		}
+	}
+
+	/// Character class, after its opening bracket.
+	fn class(&mut self) -> Result<Node, String> {
+		let negated = self.peek() == Some('^');
+		if negated {
+			self.bump();
+		}
+
+		let mut ranges: Vec<(char, char)> = Vec::new();
+		let mut first = true;
+
+		loop {
+			let c = match self.bump() {
+				Some(']') if !first => return Ok(Node::Class { ranges, negated }),
+				Some(c) => c,
+				None => return Err("unclosed character class".to_string()),
+			};
+			first = false;
+
+			let start = if c == '\\' {
+				match self.bump() {
+					Some(c) if shorthand_ranges(c).is_some() => {
+						ranges.extend(shorthand_ranges(c).unwrap());
+						continue;
+					}
+					Some(c) => escaped_char(c),
+					None => return Err("unclosed character class".to_string()),
+				}
+			} else {
+				c
+			};
+
+			// A dash right before the closing bracket is literal
+			if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|&next| next != ']') {
+				self.bump();
+				let end = match self.bump() {
+					Some('\\') => self.bump().map(escaped_char),
+					end => end,
+				};
+				match end {
+					Some(end) if end >= start => ranges.push((start, end)),
+					Some(_) => return Err(format!("invalid range starting at `{}`", start)),
+					None => return Err("unclosed character class".to_string()),
+				}
+			} else {
+				ranges.push((start, start));
+			}
+		}
+	}
+
+	fn quantifier(&mut self, node: Node) -> Result<Node, String> {
+		let (min, max) = match self.peek() {
+			Some('*') => (0, None),
+			Some('+') => (1, None),
+			Some('?') => (0, Some(1)),
+			Some('{') => {
+				let close = self.chars[self.pos..].iter().position(|&c| c == '}')
+					.ok_or_else(|| "unclosed repetition".to_string())?;
+				let bounds: String = self.chars[self.pos + 1..self.pos + close].iter().collect();
+				self.pos += close;
+
+				let parse = |bound: &str| bound.trim().parse::<usize>().map_err(|_| format!("invalid repetition `{{{}}}`", bounds));
+				match bounds.split_once(',') {
+					None => { let n = parse(&bounds)?; (n, Some(n)) },
+					Some((min, "")) => (parse(min)?, None),
+					Some((min, max)) => (parse(min)?, Some(parse(max)?)),
+				}
+			}
+			_ => return Ok(node),
+		};
+		self.bump();
+
+		if max.is_some_and(|max| max < min) {
+			return Err("repetition maximum below its minimum".to_string());
+		}
+
+		// Lazy repetitions match the same texts as greedy ones, which is all that matters here
+		if self.peek() == Some('?') {
+			self.bump();
+		}
+
+		Ok(Node::Repeat { node: Box::new(node), min, max })
+	}
+
+}
+
+
+/// Compiles nodes into instructions, refusing patterns that grow too large.
+struct Compiler {
+	program: Vec<Inst>,
+}
+
+
+impl Compiler {
+
+	fn push(&mut self, inst: Inst) -> Result<usize, String> {
+		if self.program.len() >= MAX_PROGRAM {
+			return Err("pattern too large".to_string());
+		}
+		self.program.push(inst);
+		Ok(self.program.len() - 1)
+	}
+
+	/// Points the jump or split at `at` to the next instruction, on its second target for splits.
+	fn patch(&mut self, at: usize) {
+		let target = self.program.len();
+		match &mut self.program[at] {
+			Inst::Split(_, second) => *second = target,
+			Inst::Jump(first) => *first = target,
+			_ => unreachable!("only jumps and splits are patched"),
+		}
+	}
+
+	fn sequence(&mut self, nodes: &[Node]) -> Result<(), String> {
+		nodes.iter().try_for_each(|node| self.node(node))
+	}
+
+	fn node(&mut self, node: &Node) -> Result<(), String> {
+		match node {
+			Node::Char(c) => self.push(Inst::Char(*c)).map(drop),
+			Node::Any => self.push(Inst::Any).map(drop),
+			Node::Class { ranges, negated } => self.push(Inst::Class { ranges: ranges.clone(), negated: *negated }).map(drop),
+			Node::Start => self.push(Inst::Start).map(drop),
+			Node::End => self.push(Inst::End).map(drop),
+			Node::Group(alternatives) => {
+				let mut jumps = Vec::new();
+				for (i, nodes) in alternatives.iter().enumerate() {
+					if i + 1 == alternatives.len() {
+						self.sequence(nodes)?;
+						break;
+					}
+					let split = self.push(Inst::Split(self.program.len() + 1, 0))?;
+					self.sequence(nodes)?;
+					jumps.push(self.push(Inst::Jump(0))?);
+					self.patch(split);
+				}
+				jumps.into_iter().for_each(|jump| self.patch(jump));
+				Ok(())
+			}
+			Node::Repeat { node, min, max } => {
+				for _ in 0..*min {
+					self.node(node)?;
+				}
+				match max {
+					None => {
+						let split = self.push(Inst::Split(self.program.len() + 1, 0))?;
+						self.node(node)?;
+						self.push(Inst::Jump(split))?;
+						self.patch(split);
+					}
+					Some(max) => {
+						let mut splits = Vec::new();
+						for _ in *min..*max {
+							splits.push(self.push(Inst::Split(self.program.len() + 1, 0))?);
+							self.node(node)?;
+						}
+						splits.into_iter().for_each(|split| self.patch(split));
+					}
+				}
+				Ok(())
+			}
+		}
+	}
+
+}
+
+
+impl Regex {
+
+	/// Compiles a pattern, or describes why it is invalid.
+	pub(crate) fn new(pattern: &str) -> Result<Self, String> {
+		let mut parser = Parser { chars: pattern.chars().collect(), pos: 0 };
+		let alternatives = parser.alternation()?;
+
+		if parser.pos < parser.chars.len() {
+			return Err("unmatched closing parenthesis".to_string());
+		}
+
+		let mut compiler = Compiler { program: Vec::new() };
+		compiler.node(&Node::Group(alternatives))?;
+		compiler.push(Inst::Match)?;
+
+		Ok(Self { program: compiler.program })
+	}
+
+	/// Whether the pattern matches anywhere in `text`.
+	/// 
+	/// Every thread of the NFA advances one character at a time, and a new one starts at every position.
+	pub(crate) fn is_match(&self, text: &str) -> bool {
+		let input: Vec<char> = text.chars().collect();
+
+		let mut current = Threads::new(self.program.len());
+		let mut next = Threads::new(self.program.len());
+
+		for pos in 0..=input.len() {
+			self.add(&mut current, 0, pos, input.len());
+
+			for i in 0..current.pcs.len() {
+				let pc = current.pcs[i];
+				let advances = match (&self.program[pc], input.get(pos)) {
+					(Inst::Match, _) => return true,
+					(Inst::Char(expected), Some(c)) => c == expected,
+					(Inst::Any, Some(&c)) => c != '\n',
+					(Inst::Class { ranges, negated }, Some(c)) => ranges.iter().any(|&(start, end)| (start..=end).contains(c)) != *negated,
+					_ => false,
+				};
+				if advances {
+					self.add(&mut next, pc + 1, pos + 1, input.len());
+				}
+			}
+
+			std::mem::swap(&mut current, &mut next);
+			next.clear();
+		}
+
+		false
+	}
+
+	/// Adds the thread at `pc` to `threads`, following jumps, splits and anchors without consuming a character.
+	fn add(&self, threads: &mut Threads, pc: usize, pos: usize, len: usize) {
+		let mut stack = vec![pc];
+
+		while let Some(pc) = stack.pop() {
+			if std::mem::replace(&mut threads.seen[pc], true) {
+				continue;
+			}
+			match self.program[pc] {
+				Inst::Split(first, second) => stack.extend([second, first]),
+				Inst::Jump(target) => stack.push(target),
+				Inst::Start if pos == 0 => stack.push(pc + 1),
+				Inst::End if pos == len => stack.push(pc + 1),
+				Inst::Start | Inst::End => {},
+				_ => threads.pcs.push(pc),
+			}
+		}
+	}
+
+}
+
+
+/// Instructions the live threads of the NFA are at, each at most once.
+struct Threads {
+	pcs: Vec<usize>,
+	seen: Vec<bool>,
+}
+
+
+impl Threads {
+
+	fn new(len: usize) -> Self {
+		Self { pcs: Vec::new(), seen: vec![false; len] }
+	}
+
+	fn clear(&mut self) {
+		self.pcs.clear();
+		self.seen.iter_mut().for_each(|seen| *seen = false);
+	}
+
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::Regex;
+
+	#[test]
+	fn matches_like_a_backtracking_engine() {
+		let regex = Regex::new(r"^(ab|cd)+?x{2,3}$").unwrap();
+		assert!(regex.is_match("abcdxx"));
+		assert!(regex.is_match("abxxx"));
+		assert!(!regex.is_match("abxxxx"));
+		assert!(!regex.is_match("xx"));
+
+		assert!(Regex::new(r"\d+").unwrap().is_match("build 42"));
+		assert!(Regex::new(r"^$").unwrap().is_match(""));
+		assert!(Regex::new(r"(a*)*b").unwrap().is_match("aaab"));
+		assert!(!Regex::new(r"^[^a-c]$").unwrap().is_match("b"));
+	}
+
+	#[test]
+	fn long_input_does_not_overflow_the_stack() {
+		let input = "w".repeat(120_000);
+		assert!(Regex::new(r"^\w+$").unwrap().is_match(&input));
+		assert!(!Regex::new(r"^\w+$").unwrap().is_match(&format!("{}!", input)));
+	}
+
+	#[test]
+	fn nested_quantifiers_do_not_backtrack() {
+		let input = format!("{}!", "a".repeat(28));
+		let started = std::time::Instant::now();
+		assert!(!Regex::new(r"^(a+)+$").unwrap().is_match(&input));
+		assert!(started.elapsed() < std::time::Duration::from_secs(1));
+	}
+
+	#[test]
+	fn huge_repetitions_are_rejected() {
+		assert_eq!(Regex::new(r"(a{1000}){1000}").unwrap_err(), "pattern too large");
+	}
+
+}
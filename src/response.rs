@@ -0,0 +1,170 @@
+//! Expansion of arguments standing for more arguments: `@path` response files and the standard input marker.
+
+use std::io::Read;
+
+use crate::CLIError;
+
+
+/// How deep response files may reference other response files.
+const MAX_DEPTH: usize = 16;
+
+
+/// Replaces every `@path` argument with the contents of that file.
+pub(crate) fn expand<I: IntoIterator<Item = String>>(args: I) -> Result<Vec<String>, CLIError> {
+	let mut expanded: Vec<String> = Vec::new();
+
+	for argument in args {
+		expand_argument(argument, 0, &mut expanded)?;
+	}
+
+	Ok(expanded)
+}
+
+
+fn expand_argument(argument: String, depth: usize, expanded: &mut Vec<String>) -> Result<(), CLIError> {
+
+	let path = match argument.strip_prefix('@') {
+		Some(path) if !path.is_empty() => path,
+		_ => {
+			expanded.push(argument);
+			return Ok(());
+		}
+	};
+
+	if depth >= MAX_DEPTH {
+		return Err(CLIError::ResponseFile(path.to_string(), format!("nested more than {} levels deep", MAX_DEPTH)));
+	}
+
+	let contents = std::fs::read_to_string(path)
+		.map_err(|err| CLIError::ResponseFile(path.to_string(), err.to_string()))?;
+
+	for line in contents.lines() {
+		let line = line.trim();
+
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+
+		expand_argument(line.to_string(), depth + 1, expanded)?;
+	}
+
+	Ok(())
+}
+
+
+/// Replaces the first `marker` argument with the whitespace separated arguments read from `input`.
+/// 
+/// Later markers are dropped, since the input has been consumed already.
+pub(crate) fn read_input<R: Read>(args: Vec<String>, marker: &str, mut input: R) -> Result<Vec<String>, CLIError> {
+	if !args.iter().any(|arg| arg == marker) {
+		return Ok(args);
+	}
+
+	let mut contents = String::new();
+	input.read_to_string(&mut contents).map_err(|err| CLIError::Stdin(err.to_string()))?;
+
+	let mut expanded: Vec<String> = Vec::new();
+	let mut consumed = false;
+
+	for argument in args {
+		if argument != marker {
+			expanded.push(argument);
+		} else if !consumed {
+			expanded.extend(contents.split_whitespace().map(String::from));
+			consumed = true;
+		}
+	}
+
+	Ok(expanded)
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::{expand, read_input, MAX_DEPTH};
+	use crate::CLIError;
+
+	fn args(args: &[&str]) -> Vec<String> {
+		args.iter().map(|arg| arg.to_string()).collect()
+	}
+
+	/// Input that fails to read, as a closed or unreadable standard input would.
+	struct Broken;
+
+	impl std::io::Read for Broken {
+		fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+			Err(std::io::Error::other("broken pipe"))
+		}
+	}
+
+	#[test]
+	fn marker_is_replaced_by_input() {
+		let expanded = read_input(args(&["-v", "-", "last"]), "-", "--a=1\n  b \tc\n".as_bytes()).unwrap();
+		assert_eq!(expanded, args(&["-v", "--a=1", "b", "c", "last"]));
+	}
+
+	#[test]
+	fn later_markers_are_dropped() {
+		let expanded = read_input(args(&["-", "x", "-"]), "-", "a b".as_bytes()).unwrap();
+		assert_eq!(expanded, args(&["a", "b", "x"]));
+	}
+
+	#[test]
+	fn input_is_not_read_without_marker() {
+		let expanded = read_input(args(&["a", "--", "b"]), "-", Broken).unwrap();
+		assert_eq!(expanded, args(&["a", "--", "b"]));
+	}
+
+	#[test]
+	fn custom_marker() {
+		let expanded = read_input(args(&["-", "@stdin"]), "@stdin", "a".as_bytes()).unwrap();
+		assert_eq!(expanded, args(&["-", "a"]));
+	}
+
+	#[test]
+	fn unreadable_input() {
+		let error = read_input(args(&["-"]), "-", Broken).unwrap_err();
+		assert_eq!(error, CLIError::Stdin("broken pipe".to_string()));
+	}
+
+	#[test]
+	fn input_not_utf8() {
+		let error = read_input(args(&["-"]), "-", &b"a \xff"[..]).unwrap_err();
+		assert!(matches!(error, CLIError::Stdin(_)));
+	}
+
+	#[test]
+	fn response_files_nest() {
+		let dir = std::env::temp_dir().join("cliparser-response-nest-test");
+		std::fs::create_dir_all(&dir).unwrap();
+		let inner = dir.join("inner.txt");
+		let outer = dir.join("outer.txt");
+		std::fs::write(&inner, "--b=2\n").unwrap();
+		std::fs::write(&outer, format!("# comment\n\n  -a  \n@{}\nlast\n", inner.display())).unwrap();
+
+		let expanded = expand(args(&["first", &format!("@{}", outer.display()), "@"])).unwrap();
+		assert_eq!(expanded, args(&["first", "-a", "--b=2", "last", "@"]));
+	}
+
+	#[test]
+	fn response_files_too_deep() {
+		let dir = std::env::temp_dir().join("cliparser-response-depth-test");
+		std::fs::create_dir_all(&dir).unwrap();
+		let path = dir.join("self.txt");
+		std::fs::write(&path, format!("-a\n@{}\n", path.display())).unwrap();
+
+		let error = expand(args(&[&format!("@{}", path.display())])).unwrap_err();
+		assert_eq!(error, CLIError::ResponseFile(path.display().to_string(), format!("nested more than {} levels deep", MAX_DEPTH)));
+	}
+
+	#[test]
+	fn response_file_missing() {
+		let path = std::env::temp_dir().join("cliparser-response-missing-test.txt");
+		let _ = std::fs::remove_file(&path);
+
+		let error = expand(args(&[&format!("@{}", path.display())])).unwrap_err();
+		assert!(matches!(error, CLIError::ResponseFile(ref file, _) if *file == path.display().to_string()));
+	}
+
+}
@@ -0,0 +1,1270 @@
+//! Declarations of the arguments a program expects, for the syntaxes that cannot be told apart by dashes alone.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use crate::{CLIError, DuplicatePolicy};
+use crate::suggest;
+#[cfg(feature = "regex")]
+use crate::regex::Regex;
+
+
+/// **Argument schema**.
+///
+/// Arguments are declared one after the other. Modifier methods, such as [`Schema::short`],
+/// apply to the most recently declared argument. Apply it with [`CLIParser::schema`](crate::CLIParser::schema).
+///
+/// ```
+/// use cliparser::{CLIParser, Schema};
+///
+/// let schema = Schema::new()
+///     .flag("verbose").short("v")
+///     .pair("output").short("o");
+///
+/// let parser = CLIParser::new()
+///     .schema(schema)
+///     .parse_str("-v -o result.txt -x")
+///     .unwrap();
+///
+/// assert!(parser.flags.contains("verbose"));
+/// assert!(parser.flags.contains("x"));
+/// assert_eq!(parser.pairs["output"], "result.txt");
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Schema {
+	args: Vec<ArgSpec>,
+	exclusive: Vec<Vec<String>>,
+	types: HashMap<String, Validator>,
+	pub(crate) program: Option<String>,
+	pub(crate) about: Option<String>,
+	pub(crate) examples: Vec<String>,
+	pub(crate) after_help: Option<String>,
+	pub(crate) help_template: Option<String>,
+	pub(crate) commands: Vec<crate::commands::Command>,
+	pub(crate) default_command: Option<String>,
+	pub(crate) external_commands: bool,
+	pub(crate) multicall: bool,
+	pub(crate) version: Option<String>,
+	pub(crate) commit: Option<String>,
+}
+
+
+/// A single declared argument of a [`Schema`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArgSpec {
+	pub(crate) name: String,
+	pub(crate) kind: ArgKind,
+	pub(crate) short: Option<String>,
+	pub(crate) delimiter: Option<char>,
+	pub(crate) value_type: ValueType,
+	pub(crate) value_parser: Option<String>,
+	pub(crate) duplicates: Option<DuplicatePolicy>,
+	pub(crate) aliases: Vec<String>,
+	pub(crate) deprecated_aliases: Vec<String>,
+	pub(crate) required: bool,
+	pub(crate) default: Option<String>,
+	pub(crate) default_missing: Option<String>,
+	pub(crate) env: Option<String>,
+	pub(crate) choices: Vec<String>,
+	pub(crate) requires: Vec<String>,
+	pub(crate) conflicts: Vec<String>,
+	pub(crate) variadic: bool,
+	pub(crate) deprecated: bool,
+	pub(crate) hidden: bool,
+	pub(crate) global: bool,
+	pub(crate) help: Option<String>,
+	pub(crate) heading: Option<String>,
+	pub(crate) replacement: Option<String>,
+	pub(crate) validators: Vec<Validator>,
+	/// Pattern given to [`Schema::matches`] that does not compile, with the reason.
+	pub(crate) invalid_pattern: Option<(String, String)>,
+	pub(crate) range: Option<NumericRange>,
+	pub(crate) path_checks: Vec<PathCheck>,
+	pub(crate) required_if: Vec<String>,
+	pub(crate) required_unless: Vec<String>,
+}
+
+
+/// Custom check of a value, returning an error message for invalid ones.
+type Check = dyn Fn(&str) -> Result<(), String> + Send + Sync;
+
+
+/// Custom check of the value of a declared pair, see [`Schema::validate`].
+#[derive(Clone)]
+pub(crate) struct Validator(Arc<Check>);
+
+
+impl Validator {
+
+	pub(crate) fn check(&self, value: &str) -> Result<(), String> {
+		(self.0)(value)
+	}
+
+}
+
+
+impl std::fmt::Debug for Validator {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("Validator(..)")
+	}
+}
+
+
+// Validators are only equal to their own clones, there is no comparing closures
+impl PartialEq for Validator {
+	fn eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+}
+
+impl Eq for Validator {}
+
+
+/// Allowed values of a numeric pair, see [`Schema::range`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct NumericRange {
+	start: Bound<f64>,
+	end: Bound<f64>,
+}
+
+
+// Schema::range rejects NaN bounds, so equality is total
+impl Eq for NumericRange {}
+
+
+impl NumericRange {
+
+	pub(crate) fn contains(&self, number: f64) -> bool {
+		(self.start, self.end).contains(&number)
+	}
+
+}
+
+
+impl fmt::Display for NumericRange {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.start {
+			Bound::Included(start) | Bound::Excluded(start) => write!(f, "{}", start)?,
+			Bound::Unbounded => (),
+		}
+		match self.end {
+			Bound::Included(end) => write!(f, "..={}", end),
+			Bound::Excluded(end) => write!(f, "..{}", end),
+			Bound::Unbounded => f.write_str(".."),
+		}
+	}
+}
+
+
+/// File system requirement of a path pair, see [`Schema::must_exist`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum PathCheck {
+	Exists,
+	Dir,
+	ParentExists,
+}
+
+
+impl PathCheck {
+
+	/// Describes why `path` fails the check, if it does.
+	pub(crate) fn check(self, path: &Path) -> Result<(), &'static str> {
+		match self {
+			PathCheck::Exists if !path.exists() => Err("does not exist"),
+			PathCheck::Dir if !path.is_dir() => Err("is not a directory"),
+			PathCheck::ParentExists => match path.parent() {
+				Some(parent) if !parent.as_os_str().is_empty() && !parent.is_dir() => Err("has no existing parent directory"),
+				_ => Ok(()),
+			},
+			_ => Ok(()),
+		}
+	}
+
+}
+
+
+/// Which data structure a declared argument lands in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ArgKind {
+	/// Stored in [`CLIParser::flags`](crate::CLIParser::flags).
+	Flag,
+	/// Stored in [`CLIParser::pairs`](crate::CLIParser::pairs).
+	Pair,
+	/// Stored in [`CLIParser::posits`](crate::CLIParser::posits), in declaration order.
+	Positional,
+}
+
+
+/// What the value of a declared pair must look like.
+///
+/// Values are checked as they are stored, so a mistyped one fails the parsing with [`CLIError::InvalidValue`](crate::CLIError::InvalidValue).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ValueType {
+	/// Anything goes.
+	#[default]
+	String,
+	/// A signed 64-bit integer, also in hexadecimal, octal or binary. See [`CLIParser::get`](crate::CLIParser::get).
+	Int,
+	/// A 64-bit floating point number.
+	Float,
+	/// One of `true`/`false`, `yes`/`no`, `on`/`off` or `1`/`0`, in any case. See [`CLIParser::get_bool`](crate::CLIParser::get_bool).
+	Bool,
+	/// A duration, such as `500ms` or `2h30m`. See [`CLIParser::get_duration`](crate::CLIParser::get_duration).
+	Duration,
+	/// A number of bytes, such as `64K` or `1.5GB`. See [`CLIParser::get_size`](crate::CLIParser::get_size).
+	Size,
+	/// A file system path. A leading `~` is expanded to the home directory with [`ParserConfig::expand_tilde`](crate::ParserConfig::expand_tilde).
+	Path,
+}
+
+
+impl std::fmt::Display for ValueType {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ValueType::String => write!(f, "a string"),
+			ValueType::Int => write!(f, "an integer"),
+			ValueType::Float => write!(f, "a number"),
+			ValueType::Bool => write!(f, "a boolean: true/false, yes/no, on/off or 1/0"),
+			ValueType::Duration => write!(f, "a duration, such as `500ms`, `10s` or `2h30m`"),
+			ValueType::Size => write!(f, "a size in bytes, such as `512`, `64K`, `10MiB` or `1.5GB`"),
+			ValueType::Path => write!(f, "a path"),
+		}
+	}
+}
+
+
+impl ArgSpec {
+
+	pub(crate) fn new(name: &str, kind: ArgKind) -> Self {
+		Self {
+			name: name.to_string(),
+			kind,
+			short: None,
+			delimiter: None,
+			value_type: ValueType::String,
+			value_parser: None,
+			duplicates: None,
+			aliases: Vec::new(),
+			deprecated_aliases: Vec::new(),
+			required: false,
+			default: None,
+			default_missing: None,
+			env: None,
+			choices: Vec::new(),
+			requires: Vec::new(),
+			conflicts: Vec::new(),
+			variadic: false,
+			deprecated: false,
+			hidden: false,
+			global: false,
+			help: None,
+			heading: None,
+			replacement: None,
+			validators: Vec::new(),
+			invalid_pattern: None,
+			range: None,
+			path_checks: Vec::new(),
+			required_if: Vec::new(),
+			required_unless: Vec::new(),
+		}
+	}
+
+	/// Name the argument is stored under.
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Whether the argument is a flag, a key - value pair or a positional argument.
+	pub fn kind(&self) -> ArgKind {
+		self.kind
+	}
+
+	/// Single-dash name of the argument, if it has one.
+	pub fn short(&self) -> Option<&str> {
+		self.short.as_deref()
+	}
+
+	/// Delimiter the value of the argument is split on into a list, if it has one.
+	pub fn delimiter(&self) -> Option<char> {
+		self.delimiter
+	}
+
+	/// What the value of the argument must look like.
+	pub fn value_type(&self) -> ValueType {
+		self.value_type
+	}
+
+	/// Handling of the argument given more than once, if it overrides [`ParserConfig::duplicates`](crate::ParserConfig::duplicates).
+	pub fn duplicates(&self) -> Option<DuplicatePolicy> {
+		self.duplicates
+	}
+
+	/// Other names the argument can be given with.
+	pub fn aliases(&self) -> &[String] {
+		&self.aliases
+	}
+
+	/// Other names the argument can still be given with, at the cost of a warning.
+	pub fn deprecated_aliases(&self) -> &[String] {
+		&self.deprecated_aliases
+	}
+
+	/// Whether [validation](crate::CLIParser::validate) fails without the argument.
+	pub fn required(&self) -> bool {
+		self.required
+	}
+
+	/// Value of the pair when nothing else sets it, if it has one.
+	pub fn default_value(&self) -> Option<&str> {
+		self.default.as_deref()
+	}
+
+	/// Value of the pair when it is given without one, if it has one.
+	pub fn default_missing_value(&self) -> Option<&str> {
+		self.default_missing.as_deref()
+	}
+
+	/// Environment variable the argument falls back to, if it has one.
+	pub fn env(&self) -> Option<&str> {
+		self.env.as_deref()
+	}
+
+	/// Values the pair is restricted to, empty if anything goes.
+	pub fn choices(&self) -> &[String] {
+		&self.choices
+	}
+
+	/// Flags and pairs that must be present whenever the argument is given.
+	pub fn requirements(&self) -> &[String] {
+		&self.requires
+	}
+
+	/// Flags and pairs that cannot be given along with the argument.
+	pub fn conflicts(&self) -> &[String] {
+		&self.conflicts
+	}
+
+	/// Whether the positional argument takes any number of values.
+	pub fn variadic(&self) -> bool {
+		self.variadic
+	}
+
+	/// Whether using the argument emits a [`Warning::Deprecated`](crate::Warning::Deprecated).
+	pub fn deprecated(&self) -> bool {
+		self.deprecated
+	}
+
+	/// Argument to use instead of this deprecated one, if there is one.
+	pub fn replacement(&self) -> Option<&str> {
+		self.replacement.as_deref()
+	}
+
+	/// Whether the argument is left out of generated help, usage and completions.
+	pub fn hidden(&self) -> bool {
+		self.hidden
+	}
+
+	/// Whether the argument is also accepted after a subcommand, see [`Schema::global`].
+	pub fn global(&self) -> bool {
+		self.global
+	}
+
+	/// Description of the argument for generated help, if it has one.
+	pub fn help(&self) -> Option<&str> {
+		self.help.as_deref()
+	}
+
+	/// Heading the argument is listed under in generated help, if not the default one.
+	pub fn heading(&self) -> Option<&str> {
+		self.heading.as_deref()
+	}
+
+}
+
+
+impl Schema {
+
+	/// Creates a schema without any declared arguments.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Declares a flag.
+	///
+	/// Besides `-name`, declared flags can be turned on with `--name` and off with `--no-name`.
+	/// The last one given wins, and turning a flag off keeps it off even if the environment or a configuration file sets it.
+	pub fn flag(mut self, name: &str) -> Self {
+		self.args.push(ArgSpec::new(name, ArgKind::Flag));
+		self
+	}
+
+	/// Declares a key - value pair.
+	pub fn pair(mut self, name: &str) -> Self {
+		self.args.push(ArgSpec::new(name, ArgKind::Pair));
+		self
+	}
+
+	/// Declares a positional argument.
+	/// 
+	/// Positional arguments are matched to [`CLIParser::posits`](crate::CLIParser::posits) by position, in declaration order,
+	/// which lets [required](Schema::required) ones be reported by name when missing, and values be read
+	/// by name with [`CLIParser::positional`](crate::CLIParser::positional). Once any are declared, [validation](crate::CLIParser::validate)
+	/// also rejects extra positional arguments with [`CLIError::UnexpectedPositional`](crate::CLIError::UnexpectedPositional),
+	/// unless one is [variadic](Schema::variadic).
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .positional("input").required()
+	///     .positional("output").required()
+	///     .flag("verbose");
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("a.txt -verbose").unwrap();
+	/// assert_eq!(parser.validate(), Err(CLIError::MissingRequired(vec!["output".to_string()])));
+	/// ```
+	pub fn positional(mut self, name: &str) -> Self {
+		self.args.push(ArgSpec::new(name, ArgKind::Positional));
+		self
+	}
+
+	/// Declares an argument built with [`Arg`](crate::Arg). Modifier methods apply to it like to any other.
+	pub fn arg(mut self, arg: crate::Arg) -> Self {
+		self.args.push(arg.0);
+		self
+	}
+
+	/// Lets the last declared positional argument take any number of values, including none unless it is [required](Schema::required).
+	/// 
+	/// It takes whatever the positional arguments declared after it leave, so a last one swallows all the remaining
+	/// positional arguments. Options in between still parse as options. See [`CLIParser::positional_values`](crate::CLIParser::positional_values).
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("verbose")
+	///     .positional("command").required()
+	///     .positional("files").variadic();
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("compress a b -verbose c d").unwrap();
+	/// 
+	/// assert_eq!(parser.validate(), Ok(()));
+	/// assert_eq!(parser.positional("command"), Some("compress"));
+	/// assert_eq!(parser.positional_values("files"), ["a", "b", "c", "d"]);
+	/// assert!(parser.flags.contains("verbose"));
+	/// ```
+	pub fn variadic(mut self) -> Self {
+		self.last().variadic = true;
+		self
+	}
+
+	/// Gives the last declared argument a single-dash name.
+	///
+	/// Flags can then be given as `-v`, and pairs as either `-o=file` or `-o file`.
+	/// Pairs taking a number can also have it attached, make style, as in `-j8`. Both are stored under their full name.
+	///
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	///
+	/// let schema = Schema::new().pair("jobs").short("j").pair("O").short("O");
+	/// let parser = CLIParser::new().schema(schema).parse_str("-j8 -O2 -x8").unwrap();
+	///
+	/// assert_eq!(parser.pairs["jobs"], "8");
+	/// assert_eq!(parser.pairs["O"], "2");
+	/// assert!(parser.flags.contains("x8"));
+	/// ```
+	pub fn short(mut self, name: impl Into<String>) -> Self {
+		self.last().short = Some(name.into());
+		self
+	}
+
+	/// Sets the delimiter [`CLIParser::get_list`](crate::CLIParser::get_list) splits the value of the last declared pair on.
+	pub fn value_delimiter(mut self, delimiter: char) -> Self {
+		self.last().delimiter = Some(delimiter);
+		self
+	}
+
+	/// Sets what the value of the last declared pair must look like.
+	///
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema, ValueType};
+	///
+	/// let schema = Schema::new().pair("cache").value_type(ValueType::Bool);
+	///
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--cache=Off").unwrap();
+	/// assert_eq!(parser.get_bool("cache"), Ok(Some(false)));
+	///
+	/// let error = CLIParser::new().schema(schema).parse_str("--cache=maybe").unwrap_err();
+	/// assert!(matches!(error, CLIError::InvalidValue { .. }));
+	/// ```
+	pub fn value_type(mut self, value_type: ValueType) -> Self {
+		self.last().value_type = value_type;
+		self
+	}
+
+	/// Makes the value of the last declared pair go through the parser [registered](Schema::register_type) as `name`.
+	/// 
+	/// Values it rejects fail the parsing with [`CLIError::ValueParse`](crate::CLIError::ValueParse), naming the type.
+	/// Parsing fails with [`CLIError::UnregisteredType`](crate::CLIError::UnregisteredType) if nothing is registered under `name`
+	/// by then, which leaves room to register the types of a [spec file](Schema::from_toml_str) after reading it.
+	/// 
+	/// ```
+	/// use std::str::FromStr;
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// #[derive(Debug, PartialEq)]
+	/// enum Region { EuWest, UsEast }
+	/// 
+	/// impl FromStr for Region {
+	///     type Err = String;
+	///     fn from_str(s: &str) -> Result<Self, String> {
+	///         match s {
+	///             "eu-west" => Ok(Region::EuWest),
+	///             "us-east" => Ok(Region::UsEast),
+	///             _ => Err(format!("unknown region {}", s)),
+	///         }
+	///     }
+	/// }
+	/// 
+	/// let schema = Schema::new()
+	///     .register_type::<Region>("region")
+	///     .pair("home").value_parser("region")
+	///     .pair("backup").value_parser("region");
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--home=eu-west").unwrap();
+	/// assert_eq!(parser.get::<Region>("home"), Ok(Some(Region::EuWest)));
+	/// 
+	/// let error = CLIParser::new().schema(schema).parse_str("--backup=mars").unwrap_err();
+	/// assert_eq!(error, CLIError::ValueParse {
+	///     key: "backup".to_string(),
+	///     value: "mars".to_string(),
+	///     type_name: "region".to_string(),
+	///     reason: "unknown region mars".to_string(),
+	/// });
+	/// 
+	/// let error = CLIParser::new().schema(Schema::new().pair("home").value_parser("planet")).parse_str("").unwrap_err();
+	/// assert_eq!(error, CLIError::UnregisteredType { key: "home".to_string(), type_name: "planet".to_string() });
+	/// ```
+	pub fn value_parser(mut self, name: &str) -> Self {
+		self.last().value_parser = Some(name.to_string());
+		self
+	}
+
+	/// Registers `T` as a type of values under `name`, for [`Schema::value_parser`] and the `type` field of
+	/// [spec files](Schema::from_toml_str). Values fit when they parse with [`FromStr`].
+	/// 
+	/// Read them back with [`CLIParser::get`](crate::CLIParser::get).
+	pub fn register_type<T: FromStr>(mut self, name: &str) -> Self
+	where
+		T::Err: fmt::Display,
+	{
+		let check = |value: &str| value.parse::<T>().map(|_| ()).map_err(|error| error.to_string());
+		self.types.insert(name.to_string(), Validator(Arc::new(check)));
+		self
+	}
+
+	/// Sets how the last declared argument is handled when given more than once,
+	/// overriding [`ParserConfig::duplicates`](crate::ParserConfig::duplicates).
+	///
+	/// ```
+	/// use cliparser::{CLIParser, DuplicatePolicy, ParserConfig, Schema};
+	///
+	/// let config = ParserConfig { duplicates: DuplicatePolicy::Error, ..ParserConfig::default() };
+	/// let schema = Schema::new()
+	///     .flag("v").duplicates(DuplicatePolicy::KeepLast)
+	///     .pair("include").duplicates(DuplicatePolicy::Collect);
+	///
+	/// let parser = CLIParser::new()
+	///     .config(config.clone())
+	///     .schema(schema)
+	///     .parse_str("-v -v --include=a --include=b")
+	///     .unwrap();
+	///
+	/// assert_eq!(parser.count("v"), 2);
+	/// assert_eq!(parser.pairs_multi["include"], vec!["a", "b"]);
+	///
+	/// let error = CLIParser::new().config(config).parse_str("-q -q").unwrap_err();
+	/// assert_eq!(error, cliparser::CLIError::DuplicateArgument("q".to_string()));
+	/// ```
+	pub fn duplicates(mut self, policy: DuplicatePolicy) -> Self {
+		self.last().duplicates = Some(policy);
+		self
+	}
+
+	/// Gives the last declared argument another name, e.g. an alternative spelling.
+	/// 
+	/// Arguments given with an alias are stored under the declared name.
+	///
+	/// ```
+	/// use cliparser::{CLIParser, Schema, Warning};
+	///
+	/// let schema = Schema::new()
+	///     .pair("color").alias("colour")
+	///     .flag("dry-run").deprecated_alias("simulate");
+	///
+	/// let parser = CLIParser::new().schema(schema).parse_str("--colour=red --simulate").unwrap();
+	///
+	/// assert_eq!(parser.pairs["color"], "red");
+	/// assert!(parser.flags.contains("dry-run"));
+	/// assert_eq!(parser.warnings, vec![Warning::DeprecatedAlias { alias: "simulate".to_string(), name: "dry-run".to_string() }]);
+	/// ```
+	pub fn alias(mut self, alias: &str) -> Self {
+		self.last().aliases.push(alias.to_string());
+		self
+	}
+
+	/// Gives the last declared argument another name, which still works but emits a [`Warning::DeprecatedAlias`](crate::Warning::DeprecatedAlias).
+	/// 
+	/// See [`Schema::alias`].
+	pub fn deprecated_alias(mut self, alias: &str) -> Self {
+		self.last().deprecated_aliases.push(alias.to_string());
+		self
+	}
+
+	/// Adds a custom check of the value of the last declared pair, run while parsing.
+	/// 
+	/// The check returns an error message for invalid values, which fails the parsing with
+	/// [`CLIError::ValidationFailed`](crate::CLIError::ValidationFailed). Checks run in the order they were added,
+	/// after the [type](Schema::value_type) and [choices](Schema::one_of) checks.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .pair("port").validate(|v| v.parse::<u16>().map(|_| ()).map_err(|e| e.to_string()));
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--port=8080").unwrap();
+	/// assert_eq!(parser.pairs["port"], "8080");
+	/// 
+	/// let error = CLIParser::new().schema(schema).parse_str("--port=99999").unwrap_err();
+	/// assert_eq!(error, CLIError::ValidationFailed { key: "port".to_string(), message: "number too large to fit in target type".to_string() });
+	/// ```
+	pub fn validate<F: Fn(&str) -> Result<(), String> + Send + Sync + 'static>(mut self, check: F) -> Self {
+		self.last().validators.push(Validator(Arc::new(check)));
+		self
+	}
+
+	/// Requires the value of the last declared pair to match a regular expression, checked like a [custom check](Schema::validate).
+	/// 
+	/// The pattern may match anywhere in the value, so anchor it with `^` and `$` to match the whole value.
+	/// Supported are literals, `.`, character classes and the `\d`, `\w` and `\s` shorthands,
+	/// groups with `|` alternation, and the `*`, `+`, `?` and `{n,m}` quantifiers.
+	/// 
+	/// Parsing fails with [`CLIError::InvalidPattern`](crate::CLIError::InvalidPattern) if the pattern is invalid.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let error = CLIParser::new().schema(Schema::new().pair("id").matches("[0-9")).parse_str("").unwrap_err();
+	/// assert!(matches!(error, CLIError::InvalidPattern { ref key, ref pattern, .. } if key == "id" && pattern == "[0-9"));
+	/// 
+	/// let schema = Schema::new()
+	///     .pair("sha").matches(r"^[0-9a-f]{40}$");
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--sha=0123456789abcdef0123456789abcdef01234567").unwrap();
+	/// assert_eq!(parser.pairs["sha"].len(), 40);
+	/// 
+	/// let error = CLIParser::new().schema(schema).parse_str("--sha=HEAD").unwrap_err();
+	/// assert_eq!(error, CLIError::ValidationFailed {
+	///     key: "sha".to_string(),
+	///     message: "`HEAD` does not match `^[0-9a-f]{40}$`".to_string(),
+	/// });
+	/// ```
+	#[cfg(feature = "regex")]
+	pub fn matches(mut self, pattern: &str) -> Self {
+		let regex = match Regex::new(pattern) {
+			Ok(regex) => regex,
+			Err(reason) => {
+				self.last().invalid_pattern = Some((pattern.to_string(), reason));
+				return self;
+			}
+		};
+		let pattern = pattern.to_string();
+
+		self.validate(move |value| match regex.is_match(value) {
+			true => Ok(()),
+			false => Err(format!("`{}` does not match `{}`", value, pattern)),
+		})
+	}
+
+	/// Limits the value of the last declared pair to a range of numbers, such as `1..=256` or `0.0..1.0`.
+	/// 
+	/// Values outside of it fail the parsing with [`CLIError::OutOfRange`](crate::CLIError::OutOfRange), which includes the range.
+	/// Values that are no numbers at all fail with [`CLIError::InvalidValue`](crate::CLIError::InvalidValue).
+	/// Pair it with [`ValueType::Int`] or [`ValueType::Float`] to also fix the kind of number.
+	/// 
+	/// Panics if a bound is NaN.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema, ValueType};
+	/// 
+	/// let schema = Schema::new()
+	///     .pair("threads").value_type(ValueType::Int).range(1..=256);
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--threads=16").unwrap();
+	/// assert_eq!(parser.pairs["threads"], "16");
+	/// 
+	/// let error = CLIParser::new().schema(schema).parse_str("--threads=0").unwrap_err();
+	/// assert_eq!(error, CLIError::OutOfRange { key: "threads".to_string(), value: "0".to_string(), range: "1..=256".to_string() });
+	/// ```
+	pub fn range<T: Copy + Into<f64>, R: RangeBounds<T>>(mut self, range: R) -> Self {
+		let bound = |bound: Bound<&T>| match bound.map(|&n| n.into()) {
+			Bound::Included(n) | Bound::Excluded(n) if n.is_nan() => panic!("range bounds cannot be NaN"),
+			bound => bound,
+		};
+
+		self.last().range = Some(NumericRange { start: bound(range.start_bound()), end: bound(range.end_bound()) });
+		self
+	}
+
+	/// Requires the value of the last declared pair to be an existing path, checked while parsing.
+	/// 
+	/// Makes the pair a [`ValueType::Path`]. Missing paths fail the parsing with [`CLIError::InvalidPath`](crate::CLIError::InvalidPath),
+	/// up front instead of halfway through the run.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .pair("config").must_exist()
+	///     .pair("cache").must_be_dir()
+	///     .pair("output").parent_must_exist();
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--config=Cargo.toml --cache=src --output=src/new.rs").unwrap();
+	/// assert_eq!(parser.pairs["config"], "Cargo.toml");
+	/// 
+	/// let error = CLIParser::new().schema(schema).parse_str("--cache=Cargo.toml").unwrap_err();
+	/// assert_eq!(error, CLIError::InvalidPath {
+	///     key: "cache".to_string(),
+	///     path: "Cargo.toml".to_string(),
+	///     reason: "is not a directory".to_string(),
+	/// });
+	/// ```
+	pub fn must_exist(self) -> Self {
+		self.path_check(PathCheck::Exists)
+	}
+
+	/// Requires the value of the last declared pair to be an existing directory, see [`Schema::must_exist`].
+	pub fn must_be_dir(self) -> Self {
+		self.path_check(PathCheck::Dir)
+	}
+
+	/// Requires the value of the last declared pair to be a path in an existing directory, such as an output file
+	/// still to be created. See [`Schema::must_exist`].
+	pub fn parent_must_exist(self) -> Self {
+		self.path_check(PathCheck::ParentExists)
+	}
+
+	/// Marks the last declared flag or pair as deprecated.
+	/// 
+	/// It keeps working, but every use on the command line emits a [`Warning::Deprecated`](crate::Warning::Deprecated).
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema, Warning};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("legacy").deprecated()
+	///     .pair("out").replaced_by("output")
+	///     .pair("output");
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("-legacy --out=a.txt").unwrap();
+	/// 
+	/// assert!(parser.flags.contains("legacy"));
+	/// assert_eq!(parser.pairs["out"], "a.txt");
+	/// assert_eq!(parser.warnings, vec![
+	///     Warning::Deprecated { name: "legacy".to_string(), replacement: None },
+	///     Warning::Deprecated { name: "out".to_string(), replacement: Some("output".to_string()) },
+	/// ]);
+	/// ```
+	pub fn deprecated(mut self) -> Self {
+		self.last().deprecated = true;
+		self
+	}
+
+	/// Hides the last declared argument from generated help, usage and completions, for internal or debugging options.
+	/// 
+	/// It still parses and validates like any other.
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("verbose")
+	///     .flag("dump-internals").hidden();
+	/// 
+	/// assert!(schema.args()[1].hidden());
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("-dump-internals").unwrap();
+	/// assert!(parser.flags.contains("dump-internals"));
+	/// ```
+	pub fn hidden(mut self) -> Self {
+		self.last().hidden = true;
+		self
+	}
+
+	/// Makes the last declared flag or pair global, accepted both before and after a [subcommand](Schema::command).
+	/// 
+	/// Subcommands, nested ones included, declare it too, and their parsers see it whichever side of them it was given on.
+	/// Given on both sides, the value after the subcommand wins, as the one closest to it.
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("verbose").global()
+	///     .pair("config").global()
+	///     .command("build", Schema::new().flag("release"));
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("-verbose --config=a.toml build --config=b.toml").unwrap();
+	/// let build = parser.leaf();
+	/// 
+	/// assert!(build.flag("verbose"));
+	/// assert_eq!(build.pair("config"), Some("b.toml"));
+	/// assert_eq!(parser.pair("config"), Some("a.toml"));
+	/// assert_eq!(parser.validate(), Ok(()));
+	/// ```
+	pub fn global(mut self) -> Self {
+		self.last().global = true;
+		self
+	}
+
+	/// Describes the last declared argument, for generated help.
+	pub fn help(mut self, text: &str) -> Self {
+		self.last().help = Some(text.to_string());
+		self
+	}
+
+	/// Lists the last declared flag or pair under its own heading in generated help, instead of `Options`.
+	/// 
+	/// Headings follow one another in the order they are first used. See [`Schema::help_template`] for an example.
+	pub fn heading(mut self, heading: &str) -> Self {
+		self.last().heading = Some(heading.to_string());
+		self
+	}
+
+	/// Marks the last declared flag or pair as deprecated, in favor of the argument `replacement`.
+	/// 
+	/// See [`Schema::deprecated`].
+	pub fn replaced_by(mut self, replacement: &str) -> Self {
+		self.last().deprecated = true;
+		self.last().replacement = Some(replacement.to_string());
+		self
+	}
+
+	/// Makes the last declared argument mandatory, see [`CLIParser::validate`](crate::CLIParser::validate).
+	/// 
+	/// Values filled in by the environment or configuration files count as given.
+	pub fn required(mut self) -> Self {
+		self.last().required = true;
+		self
+	}
+
+	/// Sets the value of the last declared pair when nothing else sets it. See [`CLIParser::defaults`](crate::CLIParser::defaults).
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema, Source};
+	/// 
+	/// let schema = Schema::new().pair("debug_level").default_value("1");
+	/// let parser = CLIParser::new().schema(schema).parse_str("").unwrap();
+	/// 
+	/// assert_eq!(parser.pairs["debug_level"], "1");
+	/// assert_eq!(parser.source("debug_level"), Some(Source::Default));
+	/// ```
+	pub fn default_value(mut self, value: &str) -> Self {
+		self.last().default = Some(value.to_string());
+		self
+	}
+
+	/// Sets the value of the last declared pair for when its key is given alone, without an equal sign.
+	/// 
+	/// The pair then works like a flag as well, so the next argument is never taken as its value.
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .pair("color").default_value("never").default_missing_value("auto");
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--color file.txt").unwrap();
+	/// assert_eq!(parser.pairs["color"], "auto");
+	/// assert_eq!(parser.posits, vec!["file.txt"]);
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--color=always").unwrap();
+	/// assert_eq!(parser.pairs["color"], "always");
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("").unwrap();
+	/// assert_eq!(parser.pairs["color"], "never");
+	/// ```
+	pub fn default_missing_value(mut self, value: &str) -> Self {
+		self.last().default_missing = Some(value.to_string());
+		self
+	}
+
+	/// Fills in the last declared flag or pair from the environment variable `var`, when the command line does not give it.
+	/// 
+	/// Works on top of [`ParserConfig::env_prefix`](crate::ParserConfig::env_prefix), and takes precedence over it.
+	/// Flags read the value like a [boolean](crate::ValueType::Bool), and are left off when it is empty. The value is recorded as coming from [`Source::Environment`](crate::Source::Environment).
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Schema, Source};
+	/// 
+	/// std::env::set_var("MYAPP_TOKEN", "s3cr3t");
+	/// std::env::set_var("MYAPP_REGION", "eu-west-1");
+	/// 
+	/// let schema = Schema::new()
+	///     .pair("token").env("MYAPP_TOKEN")
+	///     .pair("region").env("MYAPP_REGION");
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("--region=us-east-2").unwrap();
+	/// 
+	/// assert_eq!(parser.pairs["token"], "s3cr3t");
+	/// assert_eq!(parser.source("token"), Some(Source::Environment));
+	/// assert_eq!(parser.pairs["region"], "us-east-2");
+	/// assert_eq!(parser.source("region"), Some(Source::CommandLine));
+	/// ```
+	pub fn env(mut self, var: &str) -> Self {
+		self.last().env = Some(var.to_string());
+		self
+	}
+
+	/// Restricts the value of the last declared pair to one of the given `choices`.
+	/// 
+	/// Any other value fails the parsing with [`CLIError::InvalidChoice`](crate::CLIError::InvalidChoice),
+	/// which lists the choices and suggests the closest one.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new().pair("format").one_of(["json", "yaml", "table"]);
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--format=yaml").unwrap();
+	/// assert_eq!(parser.pairs["format"], "yaml");
+	/// 
+	/// let error = CLIParser::new().schema(schema).parse_str("--format=jsno").unwrap_err();
+	/// assert!(matches!(error, CLIError::InvalidChoice { suggestion: Some(ref s), .. } if s == "json"));
+	/// ```
+	pub fn one_of<I, S>(mut self, choices: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		self.last().choices = choices.into_iter().map(Into::into).collect();
+		self
+	}
+
+	/// Makes the last declared argument depend on the flag or pair `other`, whenever it is given.
+	/// 
+	/// Checked by [`CLIParser::validate`](crate::CLIParser::validate), which throws a
+	/// [`CLIError::MissingDependency`](crate::CLIError::MissingDependency) error. A default value of `other` counts.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .pair("tls-cert").requires("tls-key")
+	///     .pair("tls-key")
+	///     .flag("offline").conflicts_with("remote-url")
+	///     .pair("remote-url");
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--tls-cert=cert.pem").unwrap();
+	/// assert_eq!(parser.validate(), Err(CLIError::MissingDependency("tls-cert".to_string(), "tls-key".to_string())));
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("--offline --remote-url=example.com").unwrap();
+	/// assert_eq!(parser.validate(), Err(CLIError::Conflict("offline".to_string(), "remote-url".to_string())));
+	/// ```
+	pub fn requires(mut self, other: &str) -> Self {
+		self.last().requires.push(other.to_string());
+		self
+	}
+
+	/// Makes the last declared argument required whenever the flag or pair `other` is given.
+	/// 
+	/// Checked by [`CLIParser::validate`](crate::CLIParser::validate), which throws a
+	/// [`CLIError::MissingDependency`](crate::CLIError::MissingDependency) error naming `other` first. Default values of `other` do not count.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("tls")
+	///     .pair("key-file").required_if("tls")
+	///     .pair("endpoint")
+	///     .pair("region").required_unless("endpoint");
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--endpoint=localhost:9000").unwrap();
+	/// assert_eq!(parser.validate(), Ok(()));
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--tls --region=eu").unwrap();
+	/// assert_eq!(parser.validate(), Err(CLIError::MissingDependency("tls".to_string(), "key-file".to_string())));
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("").unwrap();
+	/// assert_eq!(parser.validate(), Err(CLIError::MissingRequiredUnless("region".to_string(), vec!["endpoint".to_string()])));
+	/// ```
+	pub fn required_if(mut self, other: &str) -> Self {
+		self.last().required_if.push(other.to_string());
+		self
+	}
+
+	/// Makes the last declared argument required, unless the flag or pair `other` is present.
+	/// 
+	/// Given more than once, any one of the alternatives will do. Checked by [`CLIParser::validate`](crate::CLIParser::validate),
+	/// which throws a [`CLIError::MissingRequiredUnless`](crate::CLIError::MissingRequiredUnless) error. See [`Schema::required_if`] for an example.
+	pub fn required_unless(mut self, other: &str) -> Self {
+		self.last().required_unless.push(other.to_string());
+		self
+	}
+
+	/// Forbids giving the last declared argument along with the flag or pair `other`.
+	/// 
+	/// Checked by [`CLIParser::validate`](crate::CLIParser::validate), which throws a [`CLIError::Conflict`](crate::CLIError::Conflict) error.
+	/// Default values do not count. See [`Schema::requires`] for an example.
+	pub fn conflicts_with(mut self, other: &str) -> Self {
+		self.last().conflicts.push(other.to_string());
+		self
+	}
+
+	/// Declares a group of mutually exclusive flags and pairs, of which at most one may be given.
+	/// 
+	/// Checked by [`CLIParser::validate`](crate::CLIParser::validate), which throws a [`CLIError::Conflict`](crate::CLIError::Conflict)
+	/// error naming the first two offenders. Default values do not count.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("json").flag("yaml").flag("quiet").flag("verbose")
+	///     .exclusive(["json", "yaml"])
+	///     .exclusive(["quiet", "verbose"]);
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("--yaml --quiet --verbose").unwrap();
+	/// assert_eq!(parser.validate(), Err(CLIError::Conflict("quiet".to_string(), "verbose".to_string())));
+	/// ```
+	pub fn exclusive<I, S>(mut self, names: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		self.exclusive.push(names.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// Sets the name of the program, shown in the [usage](Schema::usage), help and version texts.
+	/// 
+	/// Without it they show the name the program was run as.
+	pub fn program(mut self, name: &str) -> Self {
+		self.program = Some(name.to_string());
+		self
+	}
+
+	/// Sets a short description of the program, shown at the top of the help text and in the man page.
+	pub fn about(mut self, text: &str) -> Self {
+		self.about = Some(text.to_string());
+		self
+	}
+
+	/// Adds an example command line, listed in the `Examples` section of the help text.
+	pub fn example(mut self, example: &str) -> Self {
+		self.examples.push(example.to_string());
+		self
+	}
+
+	/// Sets text shown at the end of the help text, e.g. where to report bugs.
+	pub fn after_help(mut self, text: &str) -> Self {
+		self.after_help = Some(text.to_string());
+		self
+	}
+
+	/// Replaces the layout of the help text with a template.
+	/// 
+	/// The placeholders `{name}`, `{version}`, `{about}`, `{usage}`, `{commands}`, `{positionals}`, `{options}`, `{examples}` and `{after-help}`
+	/// stand for the parts of the [default layout](Schema::render_help), or nothing when the schema has none.
+	/// `{options}` holds every [heading](Schema::heading) of flags and pairs.
+	/// 
+	/// ```
+	/// use cliparser::Schema;
+	/// 
+	/// let schema = Schema::new()
+	///     .program("mytool")
+	///     .version("2.0.1")
+	///     .flag("verbose").help("Print more details")
+	///     .pair("proxy").heading("Network").help("Proxy to connect through")
+	///     .example("mytool -verbose --proxy=localhost:3128")
+	///     .after_help("Report bugs to https://example.com/issues")
+	///     .help_template("{name} {version}\n{usage}\n\n{options}\n\n{examples}\n\n{after-help}");
+	/// 
+	/// assert_eq!(schema.render_help("mytool"), "\
+	/// mytool 2.0.1
+	/// Usage: mytool [OPTIONS]
+	///
+	/// Options:
+	///   -verbose         Print more details
+	///   -h, --help       Print help
+	///   -V, --version    Print version
+	///
+	/// Network:
+	///   --proxy=<value>  Proxy to connect through
+	///
+	/// Examples:
+	///   mytool -verbose --proxy=localhost:3128
+	///
+	/// Report bugs to https://example.com/issues
+	/// ");
+	/// ```
+	pub fn help_template(mut self, template: &str) -> Self {
+		self.help_template = Some(template.to_string());
+		self
+	}
+
+	/// Sets the version of the program, usually `env!("CARGO_PKG_VERSION")`.
+	/// 
+	/// Parsing `--version`, `-version` or `-V` then stops with a [`CLIError::VersionRequested`](crate::CLIError::VersionRequested) error
+	/// carrying the program name and version, unless the schema declares arguments of these names.
+	/// Like [`CLIError::HelpRequested`](crate::CLIError::HelpRequested), it is [no failure](crate::CLIError::is_early_exit).
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .version("1.4.0")
+	///     .commit_hash("3f9c2e1")
+	///     .flag("verbose");
+	/// 
+	/// assert_eq!(schema.render_version("mytool"), Some("mytool 1.4.0\ncommit 3f9c2e1\n".to_string()));
+	/// 
+	/// let error = CLIParser::new().schema(schema).parse_str("-verbose -V").unwrap_err();
+	/// assert!(matches!(error, CLIError::VersionRequested(_)));
+	/// assert!(error.is_early_exit());
+	/// ```
+	pub fn version(mut self, version: &str) -> Self {
+		self.version = Some(version.to_string());
+		self
+	}
+
+	/// Sets the commit the program was built from, shown on a line after the [version](Schema::version).
+	pub fn commit_hash(mut self, hash: &str) -> Self {
+		self.commit = Some(hash.to_string());
+		self
+	}
+
+	/// Groups of mutually exclusive flags and pairs, see [`Schema::exclusive`].
+	pub fn exclusive_groups(&self) -> &[Vec<String>] {
+		&self.exclusive
+	}
+
+	/// All declared arguments, in declaration order.
+	pub fn args(&self) -> &[ArgSpec] {
+		&self.args
+	}
+
+	/// Check of the values of the type registered as `name`, see [`Schema::register_type`].
+	pub(crate) fn find_type(&self, name: &str) -> Option<&Validator> {
+		self.types.get(name)
+	}
+
+	/// Declared flags and pairs, leaving out positional arguments.
+	pub(crate) fn options(&self) -> impl Iterator<Item = &ArgSpec> {
+		self.args.iter().filter(|arg| arg.kind != ArgKind::Positional)
+	}
+
+	/// Declared positional arguments, in order.
+	pub(crate) fn positionals(&self) -> impl Iterator<Item = &ArgSpec> {
+		self.args.iter().filter(|arg| arg.kind == ArgKind::Positional)
+	}
+
+	/// Finds a declared flag or pair by its name or one of its aliases.
+	pub(crate) fn find(&self, name: &str) -> Option<&ArgSpec> {
+		self.options().find(|arg| arg.name == name)
+			.or_else(|| self.find_alias(name).map(|(arg, _)| arg))
+	}
+
+	/// Finds a declared flag or pair by one of its aliases, telling if that alias is deprecated.
+	pub(crate) fn find_alias(&self, alias: &str) -> Option<(&ArgSpec, bool)> {
+		self.options().find_map(|arg| {
+			if arg.aliases.iter().any(|a| a == alias) {
+				Some((arg, false))
+			} else if arg.deprecated_aliases.iter().any(|a| a == alias) {
+				Some((arg, true))
+			} else {
+				None
+			}
+		})
+	}
+
+	/// Names of the declared flags and pairs starting with `prefix`, optionally only of the given `kind`.
+	pub(crate) fn complete(&self, prefix: &str, kind: Option<ArgKind>) -> Vec<&str> {
+		self.options()
+			.filter(|arg| kind.is_none_or(|kind| arg.kind == kind) && arg.name.starts_with(prefix))
+			.map(|arg| arg.name.as_str())
+			.collect()
+	}
+
+	/// Names of the visible declared flags and pairs that the undeclared `name` is likely a misspelling of, closest first.
+	pub(crate) fn similar_names(&self, name: &str) -> Vec<String> {
+		let names = self.options().filter(|arg| !arg.hidden).map(|arg| arg.name.as_str());
+		suggest::similar(name, names).into_iter().map(String::from).collect()
+	}
+
+	/// Finds a declared flag or pair by its single-dash name.
+	pub(crate) fn find_short(&self, short: &str) -> Option<&ArgSpec> {
+		self.options().find(|arg| arg.short.as_deref() == Some(short))
+	}
+
+	/// Finds a declared pair whose single-dash name is followed directly by digits in `body`, e.g. `j8`.
+	/// 
+	/// Returns the pair along with the digits.
+	pub(crate) fn find_numeric_short<'b>(&self, body: &'b str) -> Option<(&ArgSpec, &'b str)> {
+		self.args.iter()
+			.filter(|arg| arg.kind == ArgKind::Pair)
+			.find_map(|arg| {
+				let digits = body.strip_prefix(arg.short.as_deref()?)?;
+				let numeric = !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit());
+				numeric.then_some((arg, digits))
+			})
+	}
+
+	/// Checks that every [value parser](Schema::value_parser), in the subcommands too, names a [registered](Schema::register_type) type,
+	/// and that every [pattern](Schema::matches) compiles.
+	pub(crate) fn check_types(&self) -> Result<(), CLIError> {
+		for spec in &self.args {
+			if let Some((pattern, reason)) = &spec.invalid_pattern {
+				return Err(CLIError::InvalidPattern { key: spec.name.clone(), pattern: pattern.clone(), reason: reason.clone() });
+			}
+			if let Some(name) = spec.value_parser.as_ref().filter(|name| !self.types.contains_key(*name)) {
+				return Err(CLIError::UnregisteredType { key: spec.name.clone(), type_name: name.clone() });
+			}
+		}
+		self.commands.iter().try_for_each(|command| command.schema.check_types())
+	}
+
+	/// Declares the [global](Schema::global) arguments of `parent` too, unless already declared, with the value types they use.
+	pub(crate) fn inherit_globals(&mut self, parent: &Schema) {
+		for spec in parent.args.iter().filter(|spec| spec.global && spec.kind != ArgKind::Positional) {
+			if self.find(&spec.name).is_some() {
+				continue;
+			}
+			if let Some(name) = &spec.value_parser {
+				if let Some(validator) = parent.types.get(name) {
+					self.types.entry(name.clone()).or_insert_with(|| validator.clone());
+				}
+			}
+			self.args.push(spec.clone());
+		}
+	}
+
+	/// The argument modifier methods apply to.
+	fn last(&mut self) -> &mut ArgSpec {
+		self.args.last_mut().expect("schema modifiers must follow an argument declaration")
+	}
+
+	fn path_check(mut self, check: PathCheck) -> Self {
+		let spec = self.last();
+		spec.value_type = ValueType::Path;
+		spec.path_checks.push(check);
+		self
+	}
+
+}
@@ -0,0 +1,94 @@
+//! Minimal shell-style tokenizer, used by [`CLIParser::parse_str`](crate::CLIParser::parse_str).
+//!
+//! Follows the POSIX shell quoting rules, without any of the expansions:
+//! - Unquoted whitespace separates arguments.
+//! - Single quotes preserve everything up to the next single quote.
+//! - Double quotes preserve everything, except for `\` escaping `\`, `"`, `$`, `` ` `` and line breaks.
+//! - An unquoted `\` preserves the next character.
+
+use crate::CLIError;
+
+
+/// Splits a string into arguments, the way a shell would.
+///
+/// Will throw error if a quote is left open, or if the string ends with an unquoted `\`.
+///
+/// ```
+/// let args = cliparser::shlex::split(r#"--name="John Smith" -v 'my file.txt' a\ b"#).unwrap();
+///
+/// assert_eq!(args, vec!["--name=John Smith", "-v", "my file.txt", "a b"]);
+/// ```
+pub fn split(input: &str) -> Result<Vec<String>, CLIError> {
+
+	let mut args: Vec<String> = Vec::new();
+	let mut current = String::new();
+
+	// Distinguishes an empty quoted argument (`""`) from no argument at all
+	let mut in_argument = false;
+	let mut chars = input.chars();
+
+	while let Some(c) = chars.next() {
+		match c {
+			c if c.is_whitespace() => {
+				if in_argument {
+					args.push(std::mem::take(&mut current));
+					in_argument = false;
+				}
+			}
+
+			'\\' => {
+				match chars.next() {
+					// Line continuation
+					Some('\n') => {},
+					Some(escaped) => {
+						current.push(escaped);
+						in_argument = true;
+					}
+					None => return Err(CLIError::EscapeUnterminated(input.to_string())),
+				}
+			}
+
+			'\'' => {
+				in_argument = true;
+				loop {
+					match chars.next() {
+						Some('\'') => break,
+						Some(quoted) => current.push(quoted),
+						None => return Err(CLIError::QuoteUnterminated(input.to_string())),
+					}
+				}
+			}
+
+			'"' => {
+				in_argument = true;
+				loop {
+					match chars.next() {
+						Some('"') => break,
+						Some('\\') => match chars.next() {
+							Some('\n') => {},
+							Some(escaped @ ('\\' | '"' | '$' | '`')) => current.push(escaped),
+							Some(other) => {
+								current.push('\\');
+								current.push(other);
+							}
+							None => return Err(CLIError::QuoteUnterminated(input.to_string())),
+						},
+						Some(quoted) => current.push(quoted),
+						None => return Err(CLIError::QuoteUnterminated(input.to_string())),
+					}
+				}
+			}
+
+			other => {
+				current.push(other);
+				in_argument = true;
+			}
+		}
+	}
+
+	if in_argument {
+		args.push(current);
+	}
+
+	Ok(args)
+}
@@ -0,0 +1,156 @@
+//! Shared rules for layering secondary sources (environment, configuration files) under the command line.
+
+use crate::{CLIParser, CLIError};
+
+
+/// A value read from a secondary source, before it gets flattened into flags and pairs.
+#[cfg(any(feature = "toml", feature = "json", feature = "ini"))]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ConfigValue {
+	/// Booleans become flags, present only when `true`.
+	Bool(bool),
+	/// Strings, numbers, dates. Anything stored as is in a pair.
+	Text(String),
+	/// Lists have no place in the flat data structures.
+	#[cfg_attr(not(any(feature = "toml", feature = "json")), allow(dead_code))]
+	Array(Vec<ConfigValue>),
+	/// Nested tables flatten into dotted keys, e.g. `db.host`.
+	Table(Vec<(String, ConfigValue)>),
+}
+
+
+/// **Source** of a flag or pair, see [`CLIParser::source`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Source {
+	/// Given on the command line, or pushed later with [`CLIParser::push_arg`].
+	CommandLine,
+	/// Filled in from an environment variable.
+	Environment,
+	/// Filled in from a configuration file.
+	ConfigFile,
+	/// Declared with [`CLIParser::defaults`] or [`Schema::default_value`](crate::Schema::default_value), and not overridden.
+	Default,
+}
+
+
+/// Where a value ends up, once flattened.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum SourceValue {
+	Flag(bool),
+	Pair(String),
+}
+
+
+/// Flattens nested tables into dotted keys, checking that every value fits the flat data structures.
+#[cfg(any(feature = "toml", feature = "json", feature = "ini"))]
+fn flatten(key: String, value: ConfigValue, flat: &mut Vec<(String, SourceValue)>) -> Result<(), CLIError> {
+	match value {
+		ConfigValue::Bool(on) => flat.push((key, SourceValue::Flag(on))),
+		ConfigValue::Text(text) => flat.push((key, SourceValue::Pair(text))),
+		ConfigValue::Array(_) => return Err(CLIError::ConfigMismatch(key, "arrays cannot be stored as a single value".to_string())),
+		ConfigValue::Table(entries) => {
+			for (sub_key, sub_value) in entries {
+				flatten(format!("{}.{}", key, sub_key), sub_value, flat)?;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+
+impl CLIParser {
+
+	/// Stores `value` under `key` as coming from `source`, unless a higher priority source already set that key.
+	/// 
+	/// Only defaults can be overridden this way. Returns whether anything was stored.
+	/// Will throw error if the value does not fit its declared [type](crate::ValueType).
+	pub(crate) fn fill_missing(&mut self, key: String, value: SourceValue, source: Source) -> Result<bool, CLIError> {
+		let key = self.canonical_key(key);
+		let overridable = self.is_default(&key) && source != Source::Default;
+		if (self.flags.contains(&key) || self.pairs.contains_key(&key) || self.negated.contains(&key)) && !overridable {
+			return Ok(false);
+		}
+
+		// Cannot be a duplicate, the key is known to be missing or a default
+		let stored = match value {
+			SourceValue::Flag(true) => self.store_flag(key.clone()).map(|_| true)?,
+			SourceValue::Flag(false) => false,
+			SourceValue::Pair(value) => self.store_pair(key.clone(), value).map(|_| true)?,
+		};
+
+		if stored {
+			self.sources.insert(key, source);
+		}
+		Ok(stored)
+	}
+
+	/// Whether the pair `key` holds a default value, which anything else overrides.
+	pub(crate) fn is_default(&self, key: &str) -> bool {
+		self.sources.get(key) == Some(&Source::Default)
+	}
+
+	/// Sets default values for pairs, used unless the command line, the environment or a configuration file sets them.
+	/// 
+	/// Defaults are stored right away, and can be told apart from given values with [`CLIParser::source`].
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Source};
+	/// 
+	/// let parser = CLIParser::new()
+	///     .defaults([("debug_level", "1"), ("name", "app")])
+	///     .parse_str("--debug_level=3")
+	///     .unwrap();
+	/// 
+	/// assert_eq!(parser.pairs["debug_level"], "3");
+	/// assert_eq!(parser.pairs["name"], "app");
+	/// assert_eq!(parser.source("debug_level"), Some(Source::CommandLine));
+	/// assert_eq!(parser.source("name"), Some(Source::Default));
+	/// assert_eq!(parser.source("missing"), None);
+	/// ```
+	pub fn defaults<I, K, V>(mut self, defaults: I) -> Self
+	where
+		I: IntoIterator<Item = (K, V)>,
+		K: Into<String>,
+		V: Into<String>,
+	{
+		for (key, value) in defaults {
+			self.set_default(key.into(), value.into());
+		}
+		self
+	}
+
+	/// Stores a default value for the pair `key`, unless it is already set.
+	pub(crate) fn set_default(&mut self, key: String, value: String) {
+		let key = self.normalize_key(key);
+		if self.pairs.contains_key(&key) && !self.is_default(&key) {
+			return;
+		}
+		self.pairs.insert(key.clone(), value);
+		self.sources.insert(key, Source::Default);
+	}
+
+	/// Where the flag or pair `key` came from, or `None` if it is not set.
+	pub fn source(&self, key: &str) -> Option<Source> {
+		self.sources.get(key).copied()
+	}
+
+	/// Fills in missing flags and pairs from the top level entries of a configuration file.
+	/// 
+	/// Nothing is stored if any of the values does not fit.
+	#[cfg(any(feature = "toml", feature = "json", feature = "ini"))]
+	pub(crate) fn merge_config(&mut self, entries: Vec<(String, ConfigValue)>) -> Result<(), CLIError> {
+		let mut flat: Vec<(String, SourceValue)> = Vec::new();
+
+		for (key, value) in entries {
+			flatten(key, value, &mut flat)?;
+		}
+
+		for (key, value) in flat {
+			self.fill_missing(key, value, Source::ConfigFile)?;
+		}
+
+		Ok(())
+	}
+
+}
@@ -0,0 +1,208 @@
+//! Argument schemas declared in a TOML or JSON spec, for [`Schema::from_toml_str`] and [`Schema::from_json_str`].
+
+use crate::{CLIError, Schema, ValueType};
+use crate::sources::ConfigValue;
+
+
+/// Fields of one declared argument, in spec order.
+type Fields = Vec<(String, ConfigValue)>;
+
+
+/// Throws the error for a misfit `field` of the spec.
+fn mismatch<T>(field: &str, reason: &str) -> Result<T, CLIError> {
+	Err(CLIError::ConfigMalformed(field.to_string(), reason.to_string()))
+}
+
+
+fn read_text(field: &str, value: ConfigValue) -> Result<String, CLIError> {
+	match value {
+		ConfigValue::Text(text) => Ok(text),
+		_ => mismatch(field, "expected a string"),
+	}
+}
+
+
+fn read_bool(field: &str, value: ConfigValue) -> Result<bool, CLIError> {
+	match value {
+		ConfigValue::Bool(on) => Ok(on),
+		_ => mismatch(field, "expected a boolean"),
+	}
+}
+
+
+fn read_list(field: &str, value: ConfigValue) -> Result<Vec<String>, CLIError> {
+	match value {
+		ConfigValue::Array(items) => items.into_iter().map(|item| read_text(field, item)).collect(),
+		_ => mismatch(field, "expected an array of strings"),
+	}
+}
+
+
+/// Reads a built-in type, or failing that the name of a [registered](Schema::register_type) one.
+fn read_value_type(field: &str, value: ConfigValue) -> Result<Result<ValueType, String>, CLIError> {
+	let name = read_text(field, value)?;
+	Ok(match name.as_str() {
+		"string" => Ok(ValueType::String),
+		"int" => Ok(ValueType::Int),
+		"float" => Ok(ValueType::Float),
+		"bool" => Ok(ValueType::Bool),
+		"duration" => Ok(ValueType::Duration),
+		"size" => Ok(ValueType::Size),
+		"path" => Ok(ValueType::Path),
+		_ => Err(name),
+	})
+}
+
+
+impl Schema {
+
+	/// Reads a schema from a TOML spec, with a table per argument in declaration order.
+	///
+	/// Every table needs a `kind` of `flag`, `pair` or `positional`. The optional fields mirror the modifier methods:
+	/// `short`, `help`, `type` (`string`, `int`, `float`, `bool`, `duration`, `size`, `path` or a [registered](Schema::register_type) one),
+	/// `default`, `default_missing`, `env`, `delimiter`, `replaced_by`, the booleans `required`, `variadic`, `hidden` and `deprecated`,
+	/// and the string arrays `choices`, `aliases`, `requires`, `required_if`, `required_unless` and `conflicts_with`.
+	///
+	/// Will throw a [`CLIError::ConfigMalformed`] error if the spec is not valid TOML, or has unknown or mistyped fields.
+	/// Types that are not built in can be registered after reading the spec, and fail the parsing
+	/// with [`CLIError::UnregisteredType`] if they are not.
+	///
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	///
+	/// let spec = r#"
+	///     [verbose]
+	///     kind = "flag"
+	///     short = "v"
+	///     help = "Print more details"
+	///
+	///     [threads]
+	///     kind = "pair"
+	///     type = "int"
+	///     default = 4
+	///
+	///     [input]
+	///     kind = "positional"
+	///     required = true
+	/// "#;
+	///
+	/// let schema = Schema::from_toml_str(spec).unwrap();
+	/// assert_eq!(schema.args()[0].help(), Some("Print more details"));
+	///
+	/// let parser = CLIParser::new().schema(schema).parse_str("-v data.csv").unwrap();
+	/// assert!(parser.flags.contains("verbose"));
+	/// assert_eq!(parser.pairs["threads"], "4");
+	/// assert_eq!(parser.positional("input"), Some("data.csv"));
+	/// ```
+	#[cfg(feature = "toml")]
+	pub fn from_toml_str(spec: &str) -> Result<Self, CLIError> {
+		let entries = crate::toml::parse(spec)
+			.map_err(|(line, reason)| CLIError::ConfigMalformed(format!("TOML spec line {}", line), reason))?;
+
+		// Tables arrive flattened into `<argument>.<field>` keys
+		let mut args: Vec<(String, Fields)> = Vec::new();
+		for (key, value) in entries {
+			let (name, field) = match key.rsplit_once('.') {
+				Some(split) => split,
+				None => return mismatch(&key, "expected a table per argument"),
+			};
+			match args.iter_mut().find(|(arg, _)| arg == name) {
+				Some((_, fields)) => fields.push((field.to_string(), value)),
+				None => args.push((name.to_string(), vec![(field.to_string(), value)])),
+			}
+		}
+
+		Self::from_spec(args)
+	}
+
+	/// Reads a schema from a JSON spec, with an object per argument in declaration order.
+	///
+	/// The fields are those of [`Schema::from_toml_str`].
+	/// Will throw a [`CLIError::ConfigMalformed`] error if the spec is not a valid JSON object, or has unknown or mistyped fields.
+	///
+	/// ```
+	/// use cliparser::{CLIParser, Schema};
+	///
+	/// let spec = r#"{
+	///     "format": { "kind": "pair", "choices": ["json", "yaml"], "default": "json" },
+	///     "dry-run": { "kind": "flag", "aliases": ["simulate"] }
+	/// }"#;
+	///
+	/// let parser = CLIParser::new().schema(Schema::from_json_str(spec).unwrap()).parse_str("--simulate").unwrap();
+	/// assert!(parser.flags.contains("dry-run"));
+	/// assert_eq!(parser.pairs["format"], "json");
+	/// ```
+	#[cfg(feature = "json")]
+	pub fn from_json_str(spec: &str) -> Result<Self, CLIError> {
+		let entries = crate::json::parse(spec)
+			.map_err(|(line, reason)| CLIError::ConfigMalformed(format!("JSON spec line {}", line), reason))?;
+
+		let args = entries.into_iter()
+			.map(|(name, value)| match value {
+				ConfigValue::Table(fields) => Ok((name, fields)),
+				_ => mismatch(&name, "expected an object per argument"),
+			})
+			.collect::<Result<Vec<(String, Fields)>, CLIError>>()?;
+
+		Self::from_spec(args)
+	}
+
+	/// Declares the arguments of a spec, one by one.
+	fn from_spec(args: Vec<(String, Fields)>) -> Result<Self, CLIError> {
+		let mut schema = Schema::new();
+
+		for (name, fields) in args {
+			let kind = match fields.iter().find(|(field, _)| field == "kind") {
+				Some((_, value)) => read_text(&format!("{}.kind", name), value.clone())?,
+				None => return mismatch(&name, "missing `kind`"),
+			};
+
+			schema = match kind.as_str() {
+				"flag" => schema.flag(&name),
+				"pair" => schema.pair(&name),
+				"positional" => schema.positional(&name),
+				_ => return mismatch(&format!("{}.kind", name), "expected one of `flag`, `pair` or `positional`"),
+			};
+
+			for (field, value) in fields {
+				let key = format!("{}.{}", name, field);
+				schema = match field.as_str() {
+					"kind" => schema,
+					"short" => schema.short(read_text(&key, value)?),
+					"help" => schema.help(&read_text(&key, value)?),
+					"type" => match read_value_type(&key, value)? {
+						Ok(value_type) => schema.value_type(value_type),
+						Err(name) => schema.value_parser(&name),
+					},
+					"default" => schema.default_value(&read_text(&key, value)?),
+					"default_missing" => schema.default_missing_value(&read_text(&key, value)?),
+					"env" => schema.env(&read_text(&key, value)?),
+					"replaced_by" => schema.replaced_by(&read_text(&key, value)?),
+					"delimiter" => {
+						let text = read_text(&key, value)?;
+						let mut chars = text.chars();
+						match (chars.next(), chars.next()) {
+							(Some(delimiter), None) => schema.value_delimiter(delimiter),
+							_ => return mismatch(&key, "expected a single character"),
+						}
+					}
+					"required" if read_bool(&key, value.clone())? => schema.required(),
+					"variadic" if read_bool(&key, value.clone())? => schema.variadic(),
+					"hidden" if read_bool(&key, value.clone())? => schema.hidden(),
+					"deprecated" if read_bool(&key, value.clone())? => schema.deprecated(),
+					"required" | "variadic" | "hidden" | "deprecated" => schema,
+					"choices" => schema.one_of(read_list(&key, value)?),
+					"aliases" => read_list(&key, value)?.iter().fold(schema, |schema, alias| schema.alias(alias)),
+					"requires" => read_list(&key, value)?.iter().fold(schema, |schema, other| schema.requires(other)),
+					"required_if" => read_list(&key, value)?.iter().fold(schema, |schema, other| schema.required_if(other)),
+					"required_unless" => read_list(&key, value)?.iter().fold(schema, |schema, other| schema.required_unless(other)),
+					"conflicts_with" => read_list(&key, value)?.iter().fold(schema, |schema, other| schema.conflicts_with(other)),
+					_ => return mismatch(&key, "unknown field"),
+				};
+			}
+		}
+
+		Ok(schema)
+	}
+
+}
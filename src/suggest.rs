@@ -0,0 +1,52 @@
+//! "Did you mean" suggestions for misspelled names and values.
+
+
+/// Number of single character insertions, deletions, substitutions and swaps of neighbors turning `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+	let a: Vec<char> = a.chars().collect();
+	let b: Vec<char> = b.chars().collect();
+
+	// distances[i][j] is the distance between the first i characters of `a` and the first j ones of `b`
+	let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+	for (i, row) in distances.iter_mut().enumerate() {
+		row[0] = i;
+	}
+	distances[0] = (0..=b.len()).collect();
+
+	for i in 1..=a.len() {
+		for j in 1..=b.len() {
+			let cost = usize::from(a[i - 1] != b[j - 1]);
+			let mut distance = (distances[i - 1][j] + 1)
+				.min(distances[i][j - 1] + 1)
+				.min(distances[i - 1][j - 1] + cost);
+			if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+				distance = distance.min(distances[i - 2][j - 2] + 1);
+			}
+			distances[i][j] = distance;
+		}
+	}
+
+	distances[a.len()][b.len()]
+}
+
+
+/// The candidate closest to `given`, if any is close enough to be a likely misspelling. See [`similar`].
+pub(crate) fn closest<'a, I: IntoIterator<Item = &'a str>>(given: &str, candidates: I) -> Option<&'a str> {
+	similar(given, candidates).into_iter().next()
+}
+
+
+/// Every candidate close enough to `given` to be a likely misspelling, closest first, then in the order given.
+/// 
+/// Up to one edit is tolerated for every three characters, and at least one.
+pub(crate) fn similar<'a, I: IntoIterator<Item = &'a str>>(given: &str, candidates: I) -> Vec<&'a str> {
+	let tolerance = (given.chars().count() / 3).max(1);
+
+	let mut close: Vec<(usize, &str)> = candidates.into_iter()
+		.map(|candidate| (edit_distance(given, candidate), candidate))
+		.filter(|(distance, _)| *distance <= tolerance)
+		.collect();
+	close.sort_by_key(|(distance, _)| *distance);
+
+	close.into_iter().map(|(_, candidate)| candidate).collect()
+}
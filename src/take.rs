@@ -0,0 +1,117 @@
+//! Consuming accessors, which catch leftover arguments without declaring a schema.
+
+use crate::{CLIParser, CLIError, Source, Token};
+
+
+impl CLIParser {
+
+	/// Removes the pair `key`, returning its value if it had one.
+	/// 
+	/// Once the program took every argument it knows, [`CLIParser::finish`] rejects the rest.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser};
+	/// 
+	/// let mut parser = CLIParser::new().parse_str("-verbose --output=out.txt --ouptut=typo.txt").unwrap();
+	/// 
+	/// assert!(parser.take_flag("verbose"));
+	/// assert_eq!(parser.take_pair("output"), Some("out.txt".to_string()));
+	/// assert_eq!(parser.take_pair("input"), None);
+	/// assert_eq!(parser.finish(), Err(CLIError::UnusedArguments(vec!["ouptut".to_string()])));
+	/// ```
+	pub fn take_pair(&mut self, key: &str) -> Option<String> {
+		self.pairs_multi.remove(key);
+		self.sources.remove(key);
+		self.pairs.remove(key)
+	}
+
+	/// Removes the flag `name`, returning whether it was given. See [`CLIParser::take_pair`].
+	pub fn take_flag(&mut self, name: &str) -> bool {
+		self.counts.remove(name);
+		self.sources.remove(name);
+		self.flags.remove(name)
+	}
+
+	/// Removes the first of the positional arguments left, if any. See [`CLIParser::take_pair`].
+	/// 
+	/// The program path kept with [`ParserConfig::program_in_posits`](crate::ParserConfig::program_in_posits) is never taken.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser};
+	/// 
+	/// let mut parser = CLIParser::new().parse_str("copy a.txt b.txt extra.txt").unwrap();
+	/// 
+	/// assert_eq!(parser.take_posit(), Some("copy".to_string()));
+	/// assert_eq!(parser.take_posit(), Some("a.txt".to_string()));
+	/// assert_eq!(parser.take_posit(), Some("b.txt".to_string()));
+	/// assert_eq!(parser.finish(), Err(CLIError::UnusedArguments(vec!["extra.txt".to_string()])));
+	/// 
+	/// assert_eq!(parser.take_posits(), vec!["extra.txt"]);
+	/// assert_eq!(parser.take_posit(), None);
+	/// assert_eq!(parser.finish(), Ok(()));
+	/// ```
+	pub fn take_posit(&mut self) -> Option<String> {
+		let first = self.posits.len() - self.operands().len();
+		match self.operands().is_empty() {
+			true => None,
+			false => Some(self.posits.remove(first)),
+		}
+	}
+
+	/// Removes every positional argument left, in order. See [`CLIParser::take_posit`].
+	pub fn take_posits(&mut self) -> Vec<String> {
+		let first = self.posits.len() - self.operands().len();
+		self.posits.split_off(first)
+	}
+
+	/// Checks that every flag, pair and positional argument given on the command line was taken.
+	/// 
+	/// Will throw a [`CLIError::UnusedArguments`] error listing the rest: the flags and pairs in the order they were given,
+	/// then the positional arguments left. Values from defaults, the environment or configuration files are not expected to be taken.
+	/// See [`CLIParser::take_pair`] and [`CLIParser::take_posit`].
+	pub fn finish(&self) -> Result<(), CLIError> {
+		let mut unused: Vec<String> = Vec::new();
+
+		for token in &self.tokens {
+			let name = match token {
+				Token::Flag(name) | Token::Pair(name, _) => name,
+				Token::Negated(_) | Token::Positional(_) => continue,
+			};
+			let present = self.flags.contains(name) || self.pairs.contains_key(name);
+			if present && self.sources.get(name) == Some(&Source::CommandLine) && !unused.contains(name) {
+				unused.push(name.clone());
+			}
+		}
+		unused.extend(self.operands().iter().cloned());
+
+		if !unused.is_empty() {
+			return Err(CLIError::UnusedArguments(unused));
+		}
+
+		Ok(())
+	}
+
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use crate::{CLIError, CLIParser, ParserConfig};
+
+	#[test]
+	fn program_path_is_not_taken() {
+		let config = ParserConfig { program_in_posits: true, ..ParserConfig::default() };
+		let mut parser = CLIParser::new().config(config);
+		let args = parser.take_program(["/bin/mytool", "copy", "a.txt", "b.txt"].map(String::from), true);
+		let mut parser = parser.parse_from(args).unwrap();
+
+		assert_eq!(parser.take_posit(), Some("copy".to_string()));
+		assert_eq!(parser.finish(), Err(CLIError::UnusedArguments(vec!["a.txt".to_string(), "b.txt".to_string()])));
+		assert_eq!(parser.take_posits(), vec!["a.txt", "b.txt"]);
+		assert_eq!(parser.take_posit(), None);
+		assert_eq!(parser.posits, vec!["/bin/mytool"]);
+		assert_eq!(parser.finish(), Ok(()));
+	}
+
+}
@@ -0,0 +1,52 @@
+//! What the standard output is connected to, for [help styles](crate::HelpStyle).
+
+use std::io::IsTerminal;
+
+use crate::HelpStyle;
+
+
+/// Width help is wrapped to without `COLUMNS`, nor a terminal to ask.
+const DEFAULT_WIDTH: usize = 80;
+
+
+/// The `COLUMNS` variable when set, or else the width of the terminal the standard output goes to if `detect` is set, or else a fixed width.
+pub(crate) fn width(detect: bool) -> usize {
+	let columns = std::env::var("COLUMNS").ok().and_then(|columns| columns.trim().parse().ok()).filter(|&columns| columns > 0);
+	columns
+		.or_else(|| (detect && std::io::stdout().is_terminal()).then(stty_width).flatten())
+		.unwrap_or(DEFAULT_WIDTH)
+}
+
+
+/// Asks `stty` about the controlling terminal, which the shell does not pass in `COLUMNS` to programs.
+#[cfg(unix)]
+fn stty_width() -> Option<usize> {
+	let tty = std::fs::File::open("/dev/tty").ok()?;
+	let output = std::process::Command::new("stty").arg("size").stdin(tty).output().ok()?;
+
+	// rows columns
+	let size = String::from_utf8(output.stdout).ok()?;
+	size.split_whitespace().nth(1)?.parse().ok().filter(|&columns| columns > 0)
+}
+
+
+#[cfg(not(unix))]
+fn stty_width() -> Option<usize> {
+	None
+}
+
+
+/// Whether help in the given style is colored.
+/// 
+/// In the automatic style, `CLICOLOR_FORCE` turns colors on and `NO_COLOR` off, before looking at the standard output.
+pub(crate) fn colored(style: HelpStyle) -> bool {
+	let set = |var: &str| std::env::var_os(var).is_some_and(|value| !value.is_empty() && value != "0");
+
+	match style {
+		HelpStyle::Colored => true,
+		HelpStyle::Plain => false,
+		HelpStyle::Auto if set("CLICOLOR_FORCE") => true,
+		HelpStyle::Auto if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) => false,
+		HelpStyle::Auto => std::io::stdout().is_terminal(),
+	}
+}
@@ -0,0 +1,32 @@
+//! The classified arguments in their original order.
+
+
+/// A single classified argument, see [`CLIParser::tokens`](crate::CLIParser::tokens).
+/// 
+/// Names are stored the way they land in the data structures: declared single-dash names
+/// and abbreviations are resolved to their full name, and clustered flags are split apart.
+/// 
+/// ```
+/// use cliparser::{CLIParser, Token};
+/// 
+/// let parser = CLIParser::new().parse_str("--file=a.txt pos1 -v --file=b.txt pos2").unwrap();
+/// 
+/// assert_eq!(parser.tokens, vec![
+///     Token::Pair("file".to_string(), "a.txt".to_string()),
+///     Token::Positional("pos1".to_string()),
+///     Token::Flag("v".to_string()),
+///     Token::Pair("file".to_string(), "b.txt".to_string()),
+///     Token::Positional("pos2".to_string()),
+/// ]);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Token {
+	/// Stored in [`CLIParser::posits`](crate::CLIParser::posits).
+	Positional(String),
+	/// Stored in [`CLIParser::flags`](crate::CLIParser::flags).
+	Flag(String),
+	/// Stored in [`CLIParser::pairs`](crate::CLIParser::pairs), unless the [duplicates policy](crate::DuplicatePolicy) kept another value.
+	Pair(String, String),
+	/// A flag turned off with `--no-<flag>` or a negation prefix.
+	Negated(String),
+}
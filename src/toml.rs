@@ -0,0 +1,648 @@
+//! Minimal TOML reader, for [`CLIParser::merge_toml`].
+//!
+//! Covers the subset of TOML that maps onto flags and pairs: tables, dotted and quoted keys,
+//! all four string flavors, numbers, booleans, dates and inline tables.
+//! Arrays are recognized, but rejected when merging.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::{CLIParser, CLIError};
+use crate::sources::ConfigValue;
+
+
+/// How deep arrays and inline tables may nest, so that hostile input cannot exhaust the stack.
+const MAX_DEPTH: usize = 64;
+
+
+/// Character cursor over the TOML text, tracking the line number for error messages.
+struct Cursor {
+	chars: Vec<char>,
+	pos: usize,
+	line: usize,
+	/// Arrays and inline tables the cursor is in.
+	depth: usize,
+}
+
+
+type Malformed = (usize, String);
+
+
+impl Cursor {
+
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.pos).copied()
+	}
+
+	fn peek_at(&self, offset: usize) -> Option<char> {
+		self.chars.get(self.pos + offset).copied()
+	}
+
+	fn starts_with(&self, pattern: &str) -> bool {
+		pattern.chars().enumerate().all(|(i, c)| self.peek_at(i) == Some(c))
+	}
+
+	fn bump(&mut self) -> Option<char> {
+		let c = self.peek()?;
+		self.pos += 1;
+		if c == '\n' {
+			self.line += 1;
+		}
+		Some(c)
+	}
+
+	fn error<T>(&self, reason: &str) -> Result<T, Malformed> {
+		Err((self.line, reason.to_string()))
+	}
+
+	fn expect(&mut self, expected: char) -> Result<(), Malformed> {
+		match self.bump() {
+			Some(c) if c == expected => Ok(()),
+			_ => self.error(&format!("expected `{}`", expected)),
+		}
+	}
+
+	/// Skips spaces and tabs.
+	fn skip_blank(&mut self) {
+		while matches!(self.peek(), Some(' ' | '\t')) {
+			self.bump();
+		}
+	}
+
+	/// Skips spaces, tabs, line breaks and comments.
+	fn skip_blank_lines(&mut self) {
+		loop {
+			match self.peek() {
+				Some(' ' | '\t' | '\r' | '\n') => { self.bump(); },
+				Some('#') => self.skip_comment(),
+				_ => break,
+			}
+		}
+	}
+
+	fn skip_comment(&mut self) {
+		while !matches!(self.peek(), None | Some('\n')) {
+			self.bump();
+		}
+	}
+
+	/// Makes sure nothing but a comment follows on the current line.
+	fn end_of_line(&mut self) -> Result<(), Malformed> {
+		self.skip_blank();
+		if self.peek() == Some('#') {
+			self.skip_comment();
+		}
+		match self.peek() {
+			None | Some('\n') => Ok(()),
+			Some('\r') if self.peek_at(1) == Some('\n') => Ok(()),
+			_ => self.error("expected a line break"),
+		}
+	}
+
+	/// Parses a possibly dotted key, e.g. `server."host name".port`.
+	fn key(&mut self) -> Result<Vec<String>, Malformed> {
+		let mut parts: Vec<String> = Vec::new();
+
+		loop {
+			self.skip_blank();
+			let part = match self.peek() {
+				Some('"') => self.basic_string()?,
+				Some('\'') => self.literal_string()?,
+				_ => {
+					let mut bare = String::new();
+					while let Some(c @ ('A'..='Z' | 'a'..='z' | '0'..='9' | '_' | '-')) = self.peek() {
+						bare.push(c);
+						self.bump();
+					}
+					if bare.is_empty() {
+						return self.error("expected a key");
+					}
+					bare
+				}
+			};
+			parts.push(part);
+
+			self.skip_blank();
+			if self.peek() != Some('.') {
+				return Ok(parts);
+			}
+			self.bump();
+		}
+	}
+
+	fn value(&mut self) -> Result<ConfigValue, Malformed> {
+		match self.peek() {
+			Some('"') if self.starts_with("\"\"\"") => self.multiline_basic_string().map(ConfigValue::Text),
+			Some('"') => self.basic_string().map(ConfigValue::Text),
+			Some('\'') if self.starts_with("'''") => self.multiline_literal_string().map(ConfigValue::Text),
+			Some('\'') => self.literal_string().map(ConfigValue::Text),
+			Some(c @ ('[' | '{')) => {
+				if self.depth >= MAX_DEPTH {
+					return self.error(&format!("nested more than {} levels deep", MAX_DEPTH));
+				}
+				self.depth += 1;
+				let value = if c == '[' { self.array() } else { self.inline_table() };
+				self.depth -= 1;
+				value
+			}
+			_ => self.scalar(),
+		}
+	}
+
+	fn escape(&mut self, string: &mut String) -> Result<(), Malformed> {
+		let escaped = match self.bump() {
+			Some('b') => '\u{8}',
+			Some('t') => '\t',
+			Some('n') => '\n',
+			Some('f') => '\u{c}',
+			Some('r') => '\r',
+			Some('e') => '\u{1b}',
+			Some('"') => '"',
+			Some('\\') => '\\',
+			Some(u @ ('u' | 'U')) => {
+				let digits = if u == 'u' { 4 } else { 8 };
+				let mut code = String::new();
+				for _ in 0..digits {
+					match self.bump() {
+						Some(c) => code.push(c),
+						None => return self.error("unterminated unicode escape"),
+					}
+				}
+				match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+					Some(c) => c,
+					None => return self.error("invalid unicode escape"),
+				}
+			}
+			_ => return self.error("invalid escape sequence"),
+		};
+
+		string.push(escaped);
+		Ok(())
+	}
+
+	fn basic_string(&mut self) -> Result<String, Malformed> {
+		self.expect('"')?;
+		let mut string = String::new();
+
+		loop {
+			match self.bump() {
+				Some('"') => return Ok(string),
+				Some('\\') => self.escape(&mut string)?,
+				Some('\n') | None => return self.error("unterminated string"),
+				Some(c) => string.push(c),
+			}
+		}
+	}
+
+	fn literal_string(&mut self) -> Result<String, Malformed> {
+		self.expect('\'')?;
+		let mut string = String::new();
+
+		loop {
+			match self.bump() {
+				Some('\'') => return Ok(string),
+				Some('\n') | None => return self.error("unterminated string"),
+				Some(c) => string.push(c),
+			}
+		}
+	}
+
+	/// Skips the line break right after the opening delimiter of multi-line strings.
+	fn skip_first_line_break(&mut self) {
+		if self.starts_with("\r\n") {
+			self.bump();
+		}
+		if self.peek() == Some('\n') {
+			self.bump();
+		}
+	}
+
+	fn multiline_basic_string(&mut self) -> Result<String, Malformed> {
+		self.pos += 3;
+		self.skip_first_line_break();
+		let mut string = String::new();
+
+		loop {
+			if self.starts_with("\"\"\"") && !self.starts_with("\"\"\"\"") {
+				self.pos += 3;
+				return Ok(string);
+			}
+
+			match self.bump() {
+				// Line ending backslash trims all whitespace up to the next visible character
+				Some('\\') if matches!(self.peek(), Some(' ' | '\t' | '\r' | '\n')) => {
+					while matches!(self.peek(), Some(' ' | '\t' | '\r' | '\n')) {
+						self.bump();
+					}
+				}
+				Some('\\') => self.escape(&mut string)?,
+				Some(c) => string.push(c),
+				None => return self.error("unterminated string"),
+			}
+		}
+	}
+
+	fn multiline_literal_string(&mut self) -> Result<String, Malformed> {
+		self.pos += 3;
+		self.skip_first_line_break();
+		let mut string = String::new();
+
+		loop {
+			if self.starts_with("'''") && !self.starts_with("''''") {
+				self.pos += 3;
+				return Ok(string);
+			}
+
+			match self.bump() {
+				Some(c) => string.push(c),
+				None => return self.error("unterminated string"),
+			}
+		}
+	}
+
+	fn array(&mut self) -> Result<ConfigValue, Malformed> {
+		self.expect('[')?;
+		let mut items: Vec<ConfigValue> = Vec::new();
+
+		loop {
+			self.skip_blank_lines();
+			if self.peek() == Some(']') {
+				self.bump();
+				return Ok(ConfigValue::Array(items));
+			}
+
+			items.push(self.value()?);
+			self.skip_blank_lines();
+
+			match self.bump() {
+				Some(',') => {},
+				Some(']') => return Ok(ConfigValue::Array(items)),
+				_ => return self.error("expected `,` or `]` in array"),
+			}
+		}
+	}
+
+	fn inline_table(&mut self) -> Result<ConfigValue, Malformed> {
+		self.expect('{')?;
+		let mut entries: Vec<(String, ConfigValue)> = Vec::new();
+		let mut keys = Keys::default();
+
+		self.skip_blank();
+		if self.peek() == Some('}') {
+			self.bump();
+			return Ok(ConfigValue::Table(entries));
+		}
+
+		loop {
+			let key = self.key()?;
+			if let Err(reason) = keys.define(&key) {
+				return self.error(&reason);
+			}
+			self.expect('=')?;
+			self.skip_blank();
+			entries.push((key.join("."), self.value()?));
+			self.skip_blank();
+
+			match self.bump() {
+				Some(',') => {},
+				Some('}') => return Ok(ConfigValue::Table(entries)),
+				_ => return self.error("expected `,` or `}` in inline table"),
+			}
+		}
+	}
+
+	/// Booleans, numbers and dates.
+	fn scalar(&mut self) -> Result<ConfigValue, Malformed> {
+		let mut token = String::new();
+		while let Some(c) = self.peek() {
+			if matches!(c, ',' | ']' | '}' | '#' | '\r' | '\n') {
+				break;
+			}
+			token.push(c);
+			self.bump();
+		}
+		let token = token.trim_end();
+
+		match token {
+			"true" => return Ok(ConfigValue::Bool(true)),
+			"false" => return Ok(ConfigValue::Bool(false)),
+			"inf" | "+inf" | "-inf" | "nan" | "+nan" | "-nan" => return Ok(ConfigValue::Text(token.to_string())),
+			_ => {},
+		}
+
+		// Underscores are digit separators in numbers
+		if is_integer(token) || is_float(token) {
+			Ok(ConfigValue::Text(token.replace('_', "")))
+		} else if is_datetime(token) {
+			Ok(ConfigValue::Text(token.to_string()))
+		} else {
+			self.error(&format!("invalid value `{}`", token))
+		}
+	}
+
+}
+
+
+/// Digits in the given `radix`, possibly separated by single underscores.
+fn is_digits(digits: &str, radix: u32) -> bool {
+	!digits.is_empty()
+		&& !digits.starts_with('_') && !digits.ends_with('_') && !digits.contains("__")
+		&& digits.chars().all(|c| c == '_' || c.is_digit(radix))
+}
+
+
+/// Decimal digits without leading zeros, possibly separated by single underscores.
+fn is_decimal(digits: &str) -> bool {
+	is_digits(digits, 10) && (digits == "0" || !digits.starts_with('0'))
+}
+
+
+/// A decimal integer with an optional sign, or an unsigned hexadecimal, octal or binary one.
+fn is_integer(token: &str) -> bool {
+	let radix = match token.get(..2) {
+		Some("0x") => Some(16),
+		Some("0o") => Some(8),
+		Some("0b") => Some(2),
+		_ => None,
+	};
+	match radix {
+		Some(radix) => is_digits(&token[2..], radix),
+		None => is_decimal(token.strip_prefix(['+', '-']).unwrap_or(token)),
+	}
+}
+
+
+/// A decimal number with a fraction, an exponent or both.
+fn is_float(token: &str) -> bool {
+	let unsigned = token.strip_prefix(['+', '-']).unwrap_or(token);
+	let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+		Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+		None => (unsigned, None),
+	};
+	let (integer, fraction) = match mantissa.split_once('.') {
+		Some((integer, fraction)) => (integer, Some(fraction)),
+		None => (mantissa, None),
+	};
+
+	(fraction.is_some() || exponent.is_some())
+		&& is_decimal(integer)
+		&& fraction.is_none_or(|fraction| is_digits(fraction, 10))
+		&& exponent.is_none_or(|exponent| is_digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent), 10))
+}
+
+
+/// Exactly `count` ASCII digits.
+fn is_fixed_digits(text: &str, count: usize) -> bool {
+	text.len() == count && text.bytes().all(|b| b.is_ascii_digit())
+}
+
+
+/// A date such as `1979-05-27`.
+fn is_date(text: &str) -> bool {
+	let parts: Vec<&str> = text.split('-').collect();
+	matches!(parts[..], [year, month, day] if is_fixed_digits(year, 4) && is_fixed_digits(month, 2) && is_fixed_digits(day, 2))
+}
+
+
+/// A time such as `07:32`, `07:32:00` or `07:32:00.999`, with an optional offset such as `Z` or `-07:00` if `offset` is set.
+fn is_time(text: &str, offset: bool) -> bool {
+	let text = match text.strip_suffix(['Z', 'z']) {
+		Some(time) if offset => time,
+		_ if offset && text.len() > 6 && text[text.len() - 6..].starts_with(['+', '-']) => {
+			if !is_time(&text[text.len() - 5..], false) {
+				return false;
+			}
+			&text[..text.len() - 6]
+		}
+		_ => text,
+	};
+
+	let (time, fraction) = match text.split_once('.') {
+		Some((time, fraction)) => (time, Some(fraction)),
+		None => (text, None),
+	};
+	let parts: Vec<&str> = time.split(':').collect();
+
+	parts.len() >= 2 && parts.len() <= 3
+		&& parts.iter().all(|part| is_fixed_digits(part, 2))
+		&& fraction.is_none_or(|fraction| parts.len() == 3 && is_fixed_digits(fraction, fraction.len()) && !fraction.is_empty())
+}
+
+
+/// A date, a time, or both with an optional offset, as in `1979-05-27T07:32:00Z`.
+fn is_datetime(token: &str) -> bool {
+	if !token.is_ascii() {
+		return false;
+	}
+	if is_date(token) || is_time(token, false) {
+		return true;
+	}
+	token.len() > 11
+		&& is_date(&token[..10])
+		&& matches!(&token[10..11], "T" | "t" | " ")
+		&& is_time(&token[11..], true)
+}
+
+
+/// Keys defined so far in a table, to reject duplicates.
+#[derive(Default)]
+struct Keys {
+	/// Keys holding values.
+	values: HashSet<Vec<String>>,
+	/// Tables declared with a header.
+	headers: HashSet<Vec<String>>,
+	/// Tables declared with a header or implied by dotted keys.
+	tables: HashSet<Vec<String>>,
+}
+
+
+impl Keys {
+
+	/// Whether a table at any level of `key` already holds a value instead.
+	fn check_parents(&self, key: &[String]) -> Result<(), String> {
+		match (1..key.len()).find(|&len| self.values.contains(&key[..len])) {
+			Some(len) => Err(format!("duplicate key `{}`", key[..len].join("."))),
+			None => Ok(()),
+		}
+	}
+
+	fn imply_parents(&mut self, key: &[String]) {
+		for len in 1..key.len() {
+			self.tables.insert(key[..len].to_vec());
+		}
+	}
+
+	/// Records the key of a value, rejecting one that is already defined.
+	fn define(&mut self, key: &[String]) -> Result<(), String> {
+		self.check_parents(key)?;
+		if self.values.contains(key) || self.tables.contains(key) {
+			return Err(format!("duplicate key `{}`", key.join(".")));
+		}
+		self.imply_parents(key);
+		self.values.insert(key.to_vec());
+		Ok(())
+	}
+
+	/// Records a table header, rejecting the same table declared twice or a key already holding a value.
+	fn declare_table(&mut self, key: &[String]) -> Result<(), String> {
+		self.check_parents(key)?;
+		if self.values.contains(key) || !self.headers.insert(key.to_vec()) {
+			return Err(format!("duplicate table `{}`", key.join(".")));
+		}
+		self.imply_parents(key);
+		self.tables.insert(key.to_vec());
+		Ok(())
+	}
+
+}
+
+
+/// Parses a TOML document into its top level entries, keyed by their dotted table path.
+/// 
+/// Keys and tables defined twice are rejected, except for arrays of tables.
+pub(crate) fn parse(text: &str) -> Result<Vec<(String, ConfigValue)>, Malformed> {
+	let mut cursor = Cursor { chars: text.chars().collect(), pos: 0, line: 1, depth: 0 };
+	let mut entries: Vec<(String, ConfigValue)> = Vec::new();
+	let mut table: Vec<String> = Vec::new();
+	let mut keys = Keys::default();
+
+	loop {
+		cursor.skip_blank_lines();
+
+		match cursor.peek() {
+			None => return Ok(entries),
+
+			Some('[') if cursor.starts_with("[[") => {
+				cursor.pos += 2;
+				let key = cursor.key()?;
+				cursor.expect(']')?;
+				cursor.expect(']')?;
+				cursor.end_of_line()?;
+
+				// Arrays of tables cannot be flattened, let the merging reject them
+				entries.push((key.join("."), ConfigValue::Array(Vec::new())));
+				table = key;
+			}
+
+			Some('[') => {
+				cursor.bump();
+				table = cursor.key()?;
+				if let Err(reason) = keys.declare_table(&table) {
+					return cursor.error(&reason);
+				}
+				cursor.expect(']')?;
+				cursor.end_of_line()?;
+			}
+
+			Some(_) => {
+				let mut key = table.clone();
+				key.extend(cursor.key()?);
+				if let Err(reason) = keys.define(&key) {
+					return cursor.error(&reason);
+				}
+				cursor.expect('=')?;
+				cursor.skip_blank();
+				let value = cursor.value()?;
+				cursor.end_of_line()?;
+
+				entries.push((key.join("."), value));
+			}
+		}
+	}
+}
+
+
+impl CLIParser {
+
+	/// Fills in any flags and pairs missing from the command line, with the contents of a TOML file.
+	///
+	/// Booleans become flags (only when `true`), other values become pairs, and tables flatten into dotted keys.
+	/// Will throw error if the file cannot be read, is not valid TOML, or contains arrays.
+	///
+	/// ```
+	/// let path = std::env::temp_dir().join("cliparser_merge_toml.toml");
+	/// std::fs::write(&path, "verbose = true\ndebug_level = 2\n\n[db]\nhost = \"localhost\"\n").unwrap();
+	///
+	/// let parser = cliparser::CLIParser::new()
+	///     .parse_from(vec!["--debug_level=3".to_string()])
+	///     .unwrap()
+	///     .merge_toml(&path)
+	///     .unwrap();
+	///
+	/// assert!(parser.flags.contains("verbose"));
+	/// assert_eq!(parser.pairs["debug_level"], "3");
+	/// assert_eq!(parser.pairs["db.host"], "localhost");
+	/// ```
+	pub fn merge_toml<P: AsRef<Path>>(mut self, path: P) -> Result<Self, CLIError> {
+		let path = path.as_ref();
+
+		let text = std::fs::read_to_string(path)
+			.map_err(|err| CLIError::ConfigFile(path.display().to_string(), err.to_string()))?;
+
+		let entries = parse(&text)
+			.map_err(|(line, reason)| CLIError::ConfigMalformed(format!("{}:{}", path.display(), line), reason))?;
+
+		self.merge_config(entries)?;
+		Ok(self)
+	}
+
+}
+
+
+#[cfg(test)]
+mod tests {
+
+	use super::{parse, MAX_DEPTH};
+	use crate::sources::ConfigValue;
+
+	fn error(text: &str) -> String {
+		parse(text).unwrap_err().1
+	}
+
+	#[test]
+	fn deep_nesting_is_rejected() {
+		let text = format!("a = {}1{}", "{a = ".repeat(100_000), "}".repeat(100_000));
+		assert_eq!(error(&text), format!("nested more than {} levels deep", MAX_DEPTH));
+
+		let text = format!("a = {}{}", "[".repeat(100_000), "]".repeat(100_000));
+		assert_eq!(error(&text), format!("nested more than {} levels deep", MAX_DEPTH));
+
+		let text = format!("a = {}1{}", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+		assert!(parse(&text).is_ok());
+	}
+
+	#[test]
+	fn duplicate_keys_are_rejected() {
+		assert_eq!(error("a = 1\na = 2\n"), "duplicate key `a`");
+		assert_eq!(error("[db]\nhost = 'x'\n[db]\nport = 1\n"), "duplicate table `db`");
+		assert_eq!(error("db = 1\n[db]\n"), "duplicate table `db`");
+		assert_eq!(error("db.host = 'x'\ndb = 1\n"), "duplicate key `db`");
+		assert_eq!(error("db = 1\ndb.host = 'x'\n"), "duplicate key `db`");
+		assert_eq!(error("[db]\nhost = 'x'\n[other]\n[db.replica]\nhost.name = 'y'\n[db]\n"), "duplicate table `db`");
+		assert_eq!(error("a = { b = 1, b = 2 }\n"), "duplicate key `b`");
+
+		assert!(parse("[db]\nhost = 'x'\n[db.replica]\nhost = 'y'\n[[servers]]\n[[servers]]\n").is_ok());
+	}
+
+	#[test]
+	fn malformed_scalars_are_rejected() {
+		for value in ["1 2", "12abc", "01", "1__0", "_1", "1_", "0x", "+0x1f", "1.", ".5", "1e", "1979-05-2", "07:32:0", "1979-05-27T07:32:00+0700", "yes"] {
+			assert_eq!(error(&format!("a = {}\n", value)), format!("invalid value `{}`", value), "{}", value);
+		}
+	}
+
+	#[test]
+	fn well_formed_scalars_are_read() {
+		let values = [
+			("+99", "+99"), ("1_000", "1000"), ("0xdead_beef", "0xdeadbeef"), ("0o755", "0o755"), ("0b1101", "0b1101"),
+			("-3.14", "-3.14"), ("6.626e-34", "6.626e-34"), ("5E+22", "5E+22"), ("1e1_0", "1e10"),
+			("1979-05-27", "1979-05-27"), ("07:32:00.999", "07:32:00.999"), ("1979-05-27 07:32:00Z", "1979-05-27 07:32:00Z"),
+			("1979-05-27T00:32:00.999999-07:00", "1979-05-27T00:32:00.999999-07:00"),
+		];
+		for (value, read) in values {
+			let entries = parse(&format!("a = {}\n", value)).unwrap();
+			assert_eq!(entries, [("a".to_string(), ConfigValue::Text(read.to_string()))], "{}", value);
+		}
+	}
+
+}
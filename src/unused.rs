@@ -0,0 +1,114 @@
+//! Which arguments the program read, to point out the ones it ignored without declaring a schema.
+
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::{Mutex, PoisonError};
+
+use crate::{CLIParser, Source, Token};
+
+
+/// Names of the flags and pairs, and indices of the positional arguments, read through the accessor methods.
+#[derive(Clone, Default)]
+struct Accessed {
+	names: HashSet<String>,
+	posits: HashSet<usize>,
+}
+
+
+/// Record of reads, kept behind a lock since the accessors only borrow the parser.
+#[derive(Default)]
+pub(crate) struct AccessLog(Mutex<Accessed>);
+
+
+impl AccessLog {
+
+	fn with<R>(&self, f: impl FnOnce(&mut Accessed) -> R) -> R {
+		f(&mut self.0.lock().unwrap_or_else(PoisonError::into_inner))
+	}
+
+}
+
+
+impl Clone for AccessLog {
+	fn clone(&self) -> Self {
+		Self(Mutex::new(self.with(|accessed| accessed.clone())))
+	}
+}
+
+
+impl std::fmt::Debug for AccessLog {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("AccessLog(..)")
+	}
+}
+
+
+// Reading arguments does not change what a parser holds
+impl PartialEq for AccessLog {
+	fn eq(&self, _: &Self) -> bool {
+		true
+	}
+}
+
+impl Eq for AccessLog {}
+
+
+impl CLIParser {
+
+	pub(crate) fn mark_used(&self, name: &str) {
+		self.accessed.with(|accessed| accessed.names.insert(name.to_string()));
+	}
+
+	pub(crate) fn mark_posits_used(&self, range: Range<usize>) {
+		self.accessed.with(|accessed| accessed.posits.extend(range));
+	}
+
+	/// Flags and pairs given on the command line that were never read, in the order they were given.
+	/// 
+	/// Reading means calling an accessor method, such as [`CLIParser::flag`], [`CLIParser::pair`], [`CLIParser::get`]
+	/// or a [namespace](CLIParser::namespace) lookup; the public fields are not tracked.
+	/// Values from defaults, the environment or configuration files are not expected to be read.
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new().parse_str("-verbose --level=3 --colour=always build x").unwrap();
+	/// 
+	/// assert!(parser.flag("verbose"));
+	/// assert_eq!(parser.get::<u8>("level"), Ok(Some(3)));
+	/// assert_eq!(parser.first_posit(), Some("build"));
+	/// 
+	/// assert_eq!(parser.unused(), ["colour"]);
+	/// assert_eq!(parser.remaining(), ["x"]);
+	/// ```
+	pub fn unused(&self) -> Vec<&str> {
+		let mut unused: Vec<&str> = Vec::new();
+
+		self.accessed.with(|accessed| {
+			for token in &self.tokens {
+				let name = match token {
+					Token::Flag(name) | Token::Pair(name, _) => name.as_str(),
+					Token::Negated(_) | Token::Positional(_) => continue,
+				};
+				let present = self.flags.contains(name) || self.pairs.contains_key(name);
+				let given = self.sources.get(name) == Some(&Source::CommandLine);
+				if present && given && !accessed.names.contains(name) && !unused.contains(&name) {
+					unused.push(name);
+				}
+			}
+		});
+
+		unused
+	}
+
+	/// Positional arguments that were never read, in order. See [`CLIParser::unused`].
+	/// 
+	/// Reading means calling [`CLIParser::posit`] and its variants, or [`CLIParser::positional`] and its variants.
+	pub fn remaining(&self) -> Vec<&str> {
+		self.accessed.with(|accessed| {
+			self.operands().iter().enumerate()
+				.filter(|(i, _)| !accessed.posits.contains(i))
+				.map(|(_, posit)| posit.as_str())
+				.collect()
+		})
+	}
+
+}
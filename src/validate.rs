@@ -0,0 +1,142 @@
+//! Checks of the parsed arguments against the [`Schema`](crate::Schema), beyond what parsing alone catches.
+
+use crate::{ArgKind, CLIParser, CLIError, Token};
+
+
+impl CLIParser {
+
+	/// Checks the parsed arguments against the declared ones.
+	/// 
+	/// Every flag and pair given on the command line must be declared, every [required](crate::Schema::required)
+	/// argument must be present, and [mutually exclusive](crate::Schema::exclusive) ones cannot be given together.
+	/// With [declared positional arguments](crate::Schema::positional), there cannot be more of them than declared.
+	/// [Dependencies](crate::Schema::requires) and [conflicts](crate::Schema::conflicts_with) between arguments are checked next,
+	/// and [conditional requirements](crate::Schema::required_if) next. The arguments of a [subcommand](crate::Schema::command) are checked last,
+	/// against its own schema.
+	/// Missing positional arguments are listed first. Values of the wrong [type](crate::ValueType) are already rejected while parsing.
+	/// Does nothing without a schema.
+	/// 
+	/// Will throw a [`CLIError::UnknownArgument`] error for the first undeclared argument, suggesting declared names it may be a misspelling of,
+	/// a [`CLIError::MissingRequired`] error listing every missing one, a [`CLIError::UnexpectedPositional`] error,
+	/// a [`CLIError::Conflict`] error, a [`CLIError::MissingDependency`] error, a [`CLIError::MissingRequiredUnless`] error
+	/// or a [`CLIError::UnknownCommand`] error.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema};
+	/// 
+	/// let schema = Schema::new()
+	///     .flag("verbose")
+	///     .pair("input").required()
+	///     .pair("output").required();
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--input=a.txt --output=b.txt").unwrap();
+	/// assert_eq!(parser.validate(), Ok(()));
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("-verbose").unwrap();
+	/// assert_eq!(parser.validate(), Err(CLIError::MissingRequired(vec!["input".to_string(), "output".to_string()])));
+	/// 
+	/// let parser = CLIParser::new().schema(schema).parse_str("--input=a.txt --ouput=b.txt").unwrap();
+	/// assert_eq!(parser.validate(), Err(CLIError::UnknownArgument { given: "ouput".to_string(), suggestions: vec!["output".to_string()] }));
+	/// assert_eq!(parser.validate().unwrap_err().to_string(), "Unknown argument: `ouput`\nDid you mean `output`?");
+	/// ```
+	pub fn validate(&self) -> Result<(), CLIError> {
+		let schema = match &self.schema {
+			Some(schema) => schema,
+			None => return Ok(()),
+		};
+
+		let unknown = self.tokens.iter()
+			.filter_map(|token| match token {
+				Token::Flag(name) | Token::Pair(name, _) | Token::Negated(name) => Some(name),
+				Token::Positional(_) => None,
+			})
+			.find(|name| schema.find(name).is_none());
+
+		if let Some(name) = unknown {
+			return Err(CLIError::UnknownArgument { given: name.clone(), suggestions: schema.similar_names(name) });
+		}
+
+		let positionals = self.positional_ranges();
+		let missing_positionals = positionals.iter()
+			.filter(|(spec, range)| spec.required && range.is_empty())
+			.map(|(spec, _)| *spec);
+		let missing_options = schema.args().iter()
+			.filter(|spec| spec.kind != ArgKind::Positional && spec.required)
+			.filter(|spec| !self.is_present(&spec.name));
+
+		let missing: Vec<String> = missing_positionals.chain(missing_options)
+			.map(|spec| spec.name.clone())
+			.collect();
+
+		if !missing.is_empty() {
+			return Err(CLIError::MissingRequired(missing));
+		}
+
+		if let Some((_, last)) = positionals.last() {
+			if let Some(extra) = self.operands().get(last.end) {
+				return Err(CLIError::UnexpectedPositional(extra.clone()));
+			}
+		}
+
+		for group in schema.exclusive_groups() {
+			let mut given = group.iter().filter(|name| self.is_given(name));
+			if let (Some(first), Some(second)) = (given.next(), given.next()) {
+				return Err(CLIError::Conflict(first.clone(), second.clone()));
+			}
+		}
+
+		for spec in schema.args().iter().filter(|spec| self.is_given(&spec.name)) {
+			if let Some(required) = spec.requires.iter().find(|name| !self.is_present(name)) {
+				return Err(CLIError::MissingDependency(spec.name.clone(), required.clone()));
+			}
+			if let Some(conflict) = spec.conflicts.iter().find(|name| self.is_given(name)) {
+				return Err(CLIError::Conflict(spec.name.clone(), conflict.clone()));
+			}
+		}
+
+		for spec in schema.args().iter().filter(|spec| !self.is_present(&spec.name)) {
+			if let Some(condition) = spec.required_if.iter().find(|name| self.is_given(name)) {
+				return Err(CLIError::MissingDependency(condition.clone(), spec.name.clone()));
+			}
+			if !spec.required_unless.is_empty() && !spec.required_unless.iter().any(|name| self.is_present(name)) {
+				return Err(CLIError::MissingRequiredUnless(spec.name.clone(), spec.required_unless.clone()));
+			}
+		}
+
+		self.validate_command()
+	}
+
+	/// Whether the flag or pair `name` is set, to anything but a default value.
+	fn is_given(&self, name: &str) -> bool {
+		self.flags.contains(name) || (self.pairs.contains_key(name) && !self.is_default(name))
+	}
+
+	/// Whether the flag or pair `name` is set, if only to a default value.
+	fn is_present(&self, name: &str) -> bool {
+		self.flags.contains(name) || self.pairs.contains_key(name)
+	}
+
+	/// Checks that every one of the flags and pairs `names` is present, whether a schema declares them or not.
+	/// 
+	/// Will throw a [`CLIError::MissingRequired`] error listing the missing ones.
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new().parse_str("--input=a.txt -verbose").unwrap();
+	/// 
+	/// assert_eq!(parser.require(&["input", "verbose"]), Ok(()));
+	/// assert_eq!(parser.require(&["input", "output", "mode"]), Err(cliparser::CLIError::MissingRequired(vec!["output".to_string(), "mode".to_string()])));
+	/// ```
+	pub fn require(&self, names: &[&str]) -> Result<(), CLIError> {
+		let missing: Vec<String> = names.iter()
+			.filter(|name| !self.flags.contains(**name) && !self.pairs.contains_key(**name))
+			.map(|name| name.to_string())
+			.collect();
+
+		if !missing.is_empty() {
+			return Err(CLIError::MissingRequired(missing));
+		}
+
+		Ok(())
+	}
+
+}
@@ -0,0 +1,95 @@
+//! Pairs read as user enums, out of a fixed set of values.
+
+use crate::{CLIParser, CLIError};
+use crate::suggest;
+
+
+/// **Enum value**, one of a fixed set of strings, read with [`CLIParser::get_enum`].
+///
+/// Usually derived with the `derive` feature, for enums whose variants have no fields.
+/// Variant names are written in kebab case, `JsonLines` as `json-lines`, unless renamed with `#[cli(name = "...")]`.
+///
+#[cfg_attr(feature = "derive", doc = "```")]
+#[cfg_attr(not(feature = "derive"), doc = "```ignore")]
+/// use cliparser::{CLIError, CLIParser, FromCliValue};
+///
+/// #[derive(Debug, PartialEq, FromCliValue)]
+/// enum OutputFormat {
+///     Json,
+///     JsonLines,
+///     #[cli(name = "yml")]
+///     Yaml,
+/// }
+///
+/// assert_eq!(OutputFormat::variants(), ["json", "json-lines", "yml"]);
+///
+/// let parser = CLIParser::new().parse_str("--format=json-lines --style=yaml").unwrap();
+/// assert_eq!(parser.get_enum::<OutputFormat>("format"), Ok(Some(OutputFormat::JsonLines)));
+/// assert!(matches!(parser.get_enum::<OutputFormat>("style"), Err(CLIError::InvalidChoice { .. })));
+/// ```
+pub trait FromCliValue: Sized {
+
+	/// Every accepted value, in declaration order.
+	fn variants() -> &'static [&'static str];
+
+	/// Reads one of the [`FromCliValue::variants`], `None` for any other value.
+	fn from_cli_value(value: &str) -> Option<Self>;
+
+}
+
+
+impl CLIParser {
+
+	/// Reads the pair `key` as an enum, see [`FromCliValue`].
+	/// 
+	/// Will throw a [`CLIError::InvalidChoice`] error listing the variants if the value is none of them.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, FromCliValue};
+	/// 
+	/// #[derive(Debug, PartialEq)]
+	/// enum Level { Low, High }
+	/// 
+	/// impl FromCliValue for Level {
+	///     fn variants() -> &'static [&'static str] {
+	///         &["low", "high"]
+	///     }
+	/// 
+	///     fn from_cli_value(value: &str) -> Option<Self> {
+	///         match value {
+	///             "low" => Some(Level::Low),
+	///             "high" => Some(Level::High),
+	///             _ => None,
+	///         }
+	///     }
+	/// }
+	/// 
+	/// let parser = CLIParser::new().parse_str("--level=high --priority=hihg").unwrap();
+	/// 
+	/// assert_eq!(parser.get_enum::<Level>("level"), Ok(Some(Level::High)));
+	/// assert_eq!(parser.get_enum::<Level>("missing"), Ok(None));
+	/// assert_eq!(parser.get_enum::<Level>("priority"), Err(CLIError::InvalidChoice {
+	///     key: "priority".to_string(),
+	///     value: "hihg".to_string(),
+	///     choices: vec!["low".to_string(), "high".to_string()],
+	///     suggestion: Some("high".to_string()),
+	/// }));
+	/// ```
+	pub fn get_enum<T: FromCliValue>(&self, key: &str) -> Result<Option<T>, CLIError> {
+		let value = match self.pair(key) {
+			Some(value) => value,
+			None => return Ok(None),
+		};
+
+		match T::from_cli_value(value) {
+			Some(variant) => Ok(Some(variant)),
+			None => Err(CLIError::InvalidChoice {
+				key: key.to_string(),
+				value: value.to_string(),
+				choices: T::variants().iter().map(|variant| variant.to_string()).collect(),
+				suggestion: suggest::closest(value, T::variants().iter().copied()).map(String::from),
+			}),
+		}
+	}
+
+}
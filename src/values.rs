@@ -0,0 +1,597 @@
+//! Accessors interpreting the values of key - value pairs.
+
+use std::fmt::Display;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{CLIParser, CLIError, Interpolation, ValueType};
+use crate::{home, interpolate, suggest};
+
+
+/// Splits a list value on `delimiter`, where `\` followed by the delimiter or another `\` stands for that character.
+fn split_list(value: &str, delimiter: char) -> Vec<String> {
+	let mut items: Vec<String> = Vec::new();
+	let mut current = String::new();
+	let mut chars = value.chars().peekable();
+
+	while let Some(c) = chars.next() {
+		match c {
+			'\\' if chars.peek().is_some_and(|&next| next == delimiter || next == '\\') => current.push(chars.next().unwrap()),
+			c if c == delimiter => items.push(std::mem::take(&mut current)),
+			c => current.push(c),
+		}
+	}
+
+	items.push(current);
+	items
+}
+
+
+/// Rewrites an integer literal with a `0x`, `0o` or `0b` prefix, optionally signed, in decimal.
+/// 
+/// Returns `None` for anything else.
+fn radix_to_decimal(value: &str) -> Option<String> {
+	let (sign, number) = match value.strip_prefix('-') {
+		Some(number) => ("-", number),
+		None => ("", value.strip_prefix('+').unwrap_or(value)),
+	};
+
+	let radix = match number.get(..2)? {
+		"0x" | "0X" => 16,
+		"0o" | "0O" => 8,
+		"0b" | "0B" => 2,
+		_ => return None,
+	};
+
+	let digits = number[2..].replace('_', "");
+	if digits.starts_with(['+', '-']) {
+		return None;
+	}
+
+	let number = u128::from_str_radix(&digits, radix).ok()?;
+	Some(format!("{}{}", sign, number))
+}
+
+
+/// Name of the type `T` as shown to users, without the module paths, e.g. `Vec<String>`.
+pub(crate) fn short_type_name<T: ?Sized>() -> String {
+	let full = std::any::type_name::<T>();
+	let mut short = String::new();
+
+	// Every part is a path, followed by the punctuation ending it
+	for part in full.split_inclusive(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':')) {
+		let end = part.trim_end_matches(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'));
+		short.push_str(end.rsplit("::").next().unwrap_or(end));
+		short.push_str(&part[end.len()..]);
+	}
+	short
+}
+
+
+/// Reads `value` of the pair `key` as `T`, or else as an integer with a radix prefix rewritten in decimal.
+/// 
+/// Errors keep the message of the first attempt.
+fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, CLIError>
+where
+	T::Err: Display,
+{
+	let error = match value.parse::<T>() {
+		Ok(parsed) => return Ok(parsed),
+		Err(error) => error,
+	};
+
+	radix_to_decimal(value)
+		.and_then(|decimal| decimal.parse::<T>().ok())
+		.ok_or_else(|| CLIError::ValueParse {
+			key: key.to_string(),
+			value: value.to_string(),
+			type_name: short_type_name::<T>(),
+			reason: error.to_string(),
+		})
+}
+
+
+/// Reads a duration written as a sequence of numbers with units, e.g. `500ms`, `1.5s` or `2h30m`.
+/// 
+/// Units go from `ns`, `us` (or `µs`) and `ms`, through `s`, `m` and `h`, up to `d`. A bare `0` is allowed too.
+fn parse_duration(value: &str) -> Option<Duration> {
+	match value {
+		"" => return None,
+		"0" => return Some(Duration::ZERO),
+		_ => {},
+	}
+
+	let mut total = Duration::ZERO;
+	let mut rest = value;
+
+	while !rest.is_empty() {
+		let number_end = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+		let (number, after) = rest.split_at(number_end);
+		let unit_end = after.find(|c: char| c.is_ascii_digit() || c == '.').unwrap_or(after.len());
+		let (unit, after) = after.split_at(unit_end);
+
+		let seconds = match unit {
+			"ns" => 1e-9,
+			"us" | "µs" => 1e-6,
+			"ms" => 1e-3,
+			"s" => 1.0,
+			"m" => 60.0,
+			"h" => 3600.0,
+			"d" => 86400.0,
+			_ => return None,
+		};
+
+		let number: f64 = number.parse().ok()?;
+		total = total.checked_add(Duration::try_from_secs_f64(number * seconds).ok()?)?;
+		rest = after;
+	}
+
+	Some(total)
+}
+
+
+/// Reads a byte size written as a number with an optional unit, e.g. `512`, `64K`, `10MiB` or `1.5GB`.
+/// 
+/// SI units (`KB`, `MB`, ...) are powers of 1000, while IEC units (`KiB`, `MiB`, ...) and bare letters (`K`, `M`, ...)
+/// are powers of 1024. Units are case insensitive, and may be separated from the number by spaces.
+fn parse_size(value: &str) -> Option<u64> {
+	let number_end = value.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(value.len());
+	let (number, unit) = value.split_at(number_end);
+	let unit = unit.trim_start().to_lowercase();
+
+	let (prefix, base) = match unit.strip_suffix("ib") {
+		Some(prefix) => (prefix, 1024u64),
+		None => match unit.strip_suffix('b') {
+			Some(prefix) => (prefix, 1000),
+			None => (unit.as_str(), 1024),
+		},
+	};
+	let power = match prefix {
+		"" if unit != "ib" => 0,
+		"k" => 1,
+		"m" => 2,
+		"g" => 3,
+		"t" => 4,
+		"p" => 5,
+		"e" => 6,
+		_ => return None,
+	};
+	let multiplier = base.checked_pow(power)?;
+
+	if !number.contains('.') {
+		return number.parse::<u64>().ok()?.checked_mul(multiplier);
+	}
+
+	let bytes = number.parse::<f64>().ok()? * multiplier as f64;
+	if bytes.fract() != 0.0 || bytes >= u64::MAX as f64 {
+		return None;
+	}
+	Some(bytes as u64)
+}
+
+
+/// **Typed value** of a pair, read according to its declared [`ValueType`]. See [`CLIParser::get_typed`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+	/// For [`ValueType::String`], and undeclared pairs.
+	String(String),
+	/// For [`ValueType::Int`].
+	Int(i64),
+	/// For [`ValueType::Float`].
+	Float(f64),
+	/// For [`ValueType::Bool`].
+	Bool(bool),
+	/// For [`ValueType::Duration`].
+	Duration(Duration),
+	/// For [`ValueType::Size`], in bytes.
+	Size(u64),
+	/// For [`ValueType::Path`], after any tilde expansion.
+	Path(PathBuf),
+}
+
+
+/// Reads `value` as the given type, `None` if it does not fit.
+fn read_value(value: &str, value_type: ValueType) -> Option<Value> {
+	match value_type {
+		ValueType::String => Some(Value::String(value.to_string())),
+		ValueType::Int => value.parse().ok()
+			.or_else(|| radix_to_decimal(value)?.parse().ok())
+			.map(Value::Int),
+		ValueType::Float => value.parse().ok().map(Value::Float),
+		ValueType::Bool => parse_bool(value).map(Value::Bool),
+		ValueType::Duration => parse_duration(value).map(Value::Duration),
+		ValueType::Size => parse_size(value).map(Value::Size),
+		ValueType::Path => Some(Value::Path(PathBuf::from(value))),
+	}
+}
+
+
+/// Reads a boolean written as `true`/`false`, `yes`/`no`, `on`/`off` or `1`/`0`, in any case.
+pub(crate) fn parse_bool(value: &str) -> Option<bool> {
+	match value.to_lowercase().as_str() {
+		"true" | "yes" | "on" | "1" => Some(true),
+		"false" | "no" | "off" | "0" => Some(false),
+		_ => None,
+	}
+}
+
+
+impl CLIParser {
+
+	/// Expands `value` and checks that it fits the [type](ValueType), [value parser](crate::Schema::value_parser), [range](crate::Schema::range), [path checks](crate::Schema::must_exist), [choices](crate::Schema::one_of)
+	/// and [custom checks](crate::Schema::validate) declared for the pair `key`, if any.
+	pub(crate) fn prepare_value(&self, key: &str, value: String) -> Result<String, CLIError> {
+		let value = match self.config.interpolation {
+			Interpolation::Off => value,
+			interpolation => interpolate::interpolate(&value, interpolation == Interpolation::Strict)
+				.map_err(|name| CLIError::UnknownVariable(name, value))?,
+		};
+
+		let spec = match self.schema.as_ref().and_then(|schema| schema.find(key)) {
+			Some(spec) => spec,
+			None => return Ok(value),
+		};
+		let value_type = spec.value_type;
+
+		let value = match value_type {
+			ValueType::Path if self.config.expand_tilde => home::expand_tilde(&value).unwrap_or(value),
+			_ => value,
+		};
+
+		let typed = match read_value(&value, value_type) {
+			Some(typed) => typed,
+			None => return Err(CLIError::InvalidValue { key: key.to_string(), value, expected: value_type }),
+		};
+
+		if let Some(name) = &spec.value_parser {
+			let parser = self.schema.as_ref().and_then(|schema| schema.find_type(name))
+				.ok_or_else(|| CLIError::UnregisteredType { key: key.to_string(), type_name: name.clone() })?;
+			if let Err(reason) = parser.check(&value) {
+				return Err(CLIError::ValueParse { key: key.to_string(), value, type_name: name.clone(), reason });
+			}
+		}
+
+		if let Some(range) = spec.range {
+			let number = match typed {
+				Value::Int(n) => Some(n as f64),
+				Value::Float(n) => Some(n),
+				Value::Size(n) => Some(n as f64),
+				_ => value.parse().ok(),
+			};
+			match number {
+				Some(number) if range.contains(number) => (),
+				Some(_) => return Err(CLIError::OutOfRange { key: key.to_string(), value, range: range.to_string() }),
+				None => return Err(CLIError::InvalidValue { key: key.to_string(), value, expected: ValueType::Float }),
+			}
+		}
+
+		if let Value::Path(path) = &typed {
+			for check in &spec.path_checks {
+				check.check(path).map_err(|reason| CLIError::InvalidPath { key: key.to_string(), path: value.clone(), reason: reason.to_string() })?;
+			}
+		}
+
+		if !spec.choices.is_empty() && !spec.choices.contains(&value) {
+			return Err(CLIError::InvalidChoice {
+				key: key.to_string(),
+				suggestion: suggest::closest(&value, spec.choices.iter().map(String::as_str)).map(String::from),
+				value,
+				choices: spec.choices.clone(),
+			});
+		}
+
+		for validator in &spec.validators {
+			validator.check(&value).map_err(|message| CLIError::ValidationFailed { key: key.to_string(), message })?;
+		}
+
+		Ok(value)
+	}
+
+	/// Reads the pair `key` according to its declared [type](crate::Schema::value_type), a string if it has none.
+	/// 
+	/// Will throw error if the value does not fit, which only happens if [`CLIParser::pairs`] was modified after parsing.
+	/// 
+	/// ```
+	/// use cliparser::{CLIError, CLIParser, Schema, Value, ValueType};
+	/// 
+	/// let schema = Schema::new()
+	///     .pair("id").value_type(ValueType::Int)
+	///     .pair("ratio").value_type(ValueType::Float);
+	/// 
+	/// let parser = CLIParser::new().schema(schema.clone()).parse_str("--id=0x2A --ratio=0.5 --name=app").unwrap();
+	/// assert_eq!(parser.get_typed("id"), Ok(Some(Value::Int(42))));
+	/// assert_eq!(parser.get_typed("ratio"), Ok(Some(Value::Float(0.5))));
+	/// assert_eq!(parser.get_typed("name"), Ok(Some(Value::String("app".to_string()))));
+	/// 
+	/// let error = CLIParser::new().schema(schema).parse_str("--id=abc").unwrap_err();
+	/// assert!(matches!(error, CLIError::InvalidValue { expected: ValueType::Int, .. }));
+	/// ```
+	pub fn get_typed(&self, key: &str) -> Result<Option<Value>, CLIError> {
+		self.mark_used(key);
+		let value = match self.pairs.get(key) {
+			Some(value) => value,
+			None => return Ok(None),
+		};
+
+		let value_type = self.schema.as_ref()
+			.and_then(|schema| schema.find(key))
+			.map_or(ValueType::String, |spec| spec.value_type);
+
+		match read_value(value, value_type) {
+			Some(typed) => Ok(Some(typed)),
+			None => Err(CLIError::InvalidValue { key: key.to_string(), value: value.clone(), expected: value_type }),
+		}
+	}
+
+	/// Reads the pair `key` as a boolean.
+	/// 
+	/// Accepts `true`/`false`, `yes`/`no`, `on`/`off` and `1`/`0`, in any case. A flag of the same name reads as `true`,
+	/// and a flag turned off with `--no-<flag>` as `false`. Will throw error if the value is none of these.
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new()
+	///     .parse_str("--cache=YES --color=off --mode=fast -verbose")
+	///     .unwrap();
+	/// 
+	/// assert_eq!(parser.get_bool("cache"), Ok(Some(true)));
+	/// assert_eq!(parser.get_bool("color"), Ok(Some(false)));
+	/// assert_eq!(parser.get_bool("verbose"), Ok(Some(true)));
+	/// assert_eq!(parser.get_bool("missing"), Ok(None));
+	/// assert!(parser.get_bool("mode").is_err());
+	/// ```
+	pub fn get_bool(&self, key: &str) -> Result<Option<bool>, CLIError> {
+		self.mark_used(key);
+		if let Some(value) = self.pairs.get(key) {
+			return match parse_bool(value) {
+				Some(on) => Ok(Some(on)),
+				None => Err(CLIError::InvalidValue { key: key.to_string(), value: value.clone(), expected: ValueType::Bool }),
+			};
+		}
+
+		if self.flags.contains(key) {
+			Ok(Some(true))
+		} else if self.negated.contains(key) {
+			Ok(Some(false))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Reads the pair `key` as a duration, e.g. `500ms`, `10s`, `5m` or `2h30m`.
+	/// 
+	/// Numbers may have a fractional part, and units go from `ns`, `us` and `ms`, through `s`, `m` and `h`, up to `d`.
+	/// Will throw error if the value is not a duration.
+	/// 
+	/// ```
+	/// use std::time::Duration;
+	/// 
+	/// let parser = cliparser::CLIParser::new()
+	///     .parse_str("--timeout=2h30m --poll=500ms --retry=1.5s --delay=soon")
+	///     .unwrap();
+	/// 
+	/// assert_eq!(parser.get_duration("timeout"), Ok(Some(Duration::from_secs(9000))));
+	/// assert_eq!(parser.get_duration("poll"), Ok(Some(Duration::from_millis(500))));
+	/// assert_eq!(parser.get_duration("retry"), Ok(Some(Duration::from_millis(1500))));
+	/// assert_eq!(parser.get_duration("missing"), Ok(None));
+	/// assert!(parser.get_duration("delay").is_err());
+	/// ```
+	pub fn get_duration(&self, key: &str) -> Result<Option<Duration>, CLIError> {
+		self.mark_used(key);
+		match self.pairs.get(key) {
+			Some(value) => parse_duration(value)
+				.map(Some)
+				.ok_or_else(|| CLIError::InvalidValue { key: key.to_string(), value: value.clone(), expected: ValueType::Duration }),
+			None => Ok(None),
+		}
+	}
+
+	/// Reads the pair `key` as a number of bytes, e.g. `512`, `64K`, `10MiB` or `1.5GB`.
+	/// 
+	/// SI units (`KB`, `MB`, `GB`, ...) are powers of 1000, while IEC units (`KiB`, `MiB`, `GiB`, ...)
+	/// and bare letters (`K`, `M`, `G`, ...) are powers of 1024. Will throw error if the value is not a whole number of bytes.
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new()
+	///     .parse_str("--block=512 --cache=64K --limit=10MiB --disk=1.5GB --quota=lots")
+	///     .unwrap();
+	/// 
+	/// assert_eq!(parser.get_size("block"), Ok(Some(512)));
+	/// assert_eq!(parser.get_size("cache"), Ok(Some(64 * 1024)));
+	/// assert_eq!(parser.get_size("limit"), Ok(Some(10 * 1024 * 1024)));
+	/// assert_eq!(parser.get_size("disk"), Ok(Some(1_500_000_000)));
+	/// assert_eq!(parser.get_size("missing"), Ok(None));
+	/// assert!(parser.get_size("quota").is_err());
+	/// ```
+	pub fn get_size(&self, key: &str) -> Result<Option<u64>, CLIError> {
+		self.mark_used(key);
+		match self.pairs.get(key) {
+			Some(value) => parse_size(value)
+				.map(Some)
+				.ok_or_else(|| CLIError::InvalidValue { key: key.to_string(), value: value.clone(), expected: ValueType::Size }),
+			None => Ok(None),
+		}
+	}
+
+	/// Reads the pair `key` as a path, with a leading `~` expanded when [configured](crate::ParserConfig::expand_tilde).
+	/// 
+	/// See [`CLIParser::get_canonical_path`] for an absolute path without `.`, `..` or symbolic links.
+	/// 
+	/// ```
+	/// use std::path::PathBuf;
+	/// 
+	/// let parser = cliparser::CLIParser::new().parse_str("--out=target/out.txt").unwrap();
+	/// 
+	/// assert_eq!(parser.get_path("out"), Some(PathBuf::from("target/out.txt")));
+	/// assert_eq!(parser.get_path("missing"), None);
+	/// ```
+	pub fn get_path(&self, key: &str) -> Option<PathBuf> {
+		let value = self.pair(key)?;
+		let value = match self.config.expand_tilde {
+			true => home::expand_tilde(value).unwrap_or_else(|| value.to_string()),
+			false => value.to_string(),
+		};
+		Some(PathBuf::from(value))
+	}
+
+	/// Reads the pair `key` as a path like [`CLIParser::get_path`], made absolute with [`std::fs::canonicalize`].
+	/// 
+	/// Will throw a [`CLIError::InvalidPath`] error if the path does not exist.
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new().parse_str("--src=./src/../src --gone=no/such/dir").unwrap();
+	/// 
+	/// let src = parser.get_canonical_path("src").unwrap().unwrap();
+	/// assert!(src.is_absolute());
+	/// assert!(src.ends_with("src"));
+	/// assert!(parser.get_canonical_path("gone").is_err());
+	/// ```
+	pub fn get_canonical_path(&self, key: &str) -> Result<Option<PathBuf>, CLIError> {
+		let path = match self.get_path(key) {
+			Some(path) => path,
+			None => return Ok(None),
+		};
+
+		path.canonicalize().map(Some).map_err(|_| CLIError::InvalidPath {
+			key: key.to_string(),
+			path: path.display().to_string(),
+			reason: "does not exist".to_string(),
+		})
+	}
+
+	/// Reads the pair `key` as an IPv4 or IPv6 address.
+	/// 
+	/// Will throw a [`CLIError::ValueParse`] error if the value is not an address.
+	/// 
+	/// ```
+	/// use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+	/// 
+	/// let parser = cliparser::CLIParser::new().parse_str("--bind=127.0.0.1 --peer=::1 --host=localhost").unwrap();
+	/// 
+	/// assert_eq!(parser.get_ip("bind"), Ok(Some(IpAddr::V4(Ipv4Addr::LOCALHOST))));
+	/// assert_eq!(parser.get_ip("peer"), Ok(Some(IpAddr::V6(Ipv6Addr::LOCALHOST))));
+	/// assert!(parser.get_ip("host").is_err());
+	/// ```
+	pub fn get_ip(&self, key: &str) -> Result<Option<IpAddr>, CLIError> {
+		self.get(key)
+	}
+
+	/// Reads the pair `key` as an address and port, e.g. `127.0.0.1:8080` or `[::1]:443`.
+	/// 
+	/// Host names are not resolved. Will throw a [`CLIError::ValueParse`] error if the value is not a socket address.
+	/// 
+	/// ```
+	/// use std::net::SocketAddr;
+	/// 
+	/// let parser = cliparser::CLIParser::new().parse_str("--listen=0.0.0.0:8080 --upstream=[::1]:443 --port=80").unwrap();
+	/// 
+	/// assert_eq!(parser.get_socket_addr("listen"), Ok(Some(SocketAddr::from(([0, 0, 0, 0], 8080)))));
+	/// assert_eq!(parser.get_socket_addr("upstream").unwrap().map(|addr| addr.port()), Some(443));
+	/// assert!(parser.get_socket_addr("port").is_err());
+	/// ```
+	pub fn get_socket_addr(&self, key: &str) -> Result<Option<SocketAddr>, CLIError> {
+		self.get(key)
+	}
+
+	/// Reads the pair `key` as any type implementing [`FromStr`].
+	/// 
+	/// Integers may also be written in hexadecimal, octal or binary, with a `0x`, `0o` or `0b` prefix,
+	/// and underscores between digits. The stored value is left untouched.
+	/// Will throw a [`CLIError::ValueParse`] error if the value cannot be read as `T`, naming the type without its module path
+	/// and keeping the message of the parse error.
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new()
+	///     .parse_str("--port=8080 --mask=0xFF --mode=0o755 --flags=0b1010 --name=app")
+	///     .unwrap();
+	/// 
+	/// assert_eq!(parser.get::<u16>("port"), Ok(Some(8080)));
+	/// assert_eq!(parser.get::<u32>("mask"), Ok(Some(255)));
+	/// assert_eq!(parser.get::<u32>("mode"), Ok(Some(0o755)));
+	/// assert_eq!(parser.get::<u8>("flags"), Ok(Some(10)));
+	/// assert_eq!(parser.get::<u32>("missing"), Ok(None));
+	/// assert_eq!(parser.get::<u32>("name").unwrap_err().to_string(), "Cannot read the value of `name` as `u32`: `app`\ninvalid digit found in string");
+	/// assert_eq!(parser.get::<std::net::Ipv4Addr>("name").unwrap_err().to_string(), "Cannot read the value of `name` as `Ipv4Addr`: `app`\ninvalid IPv4 address syntax");
+	/// assert_eq!(parser.pairs["mask"], "0xFF");
+	/// ```
+	pub fn get<T: FromStr>(&self, key: &str) -> Result<Option<T>, CLIError>
+	where
+		T::Err: Display,
+	{
+		self.mark_used(key);
+		self.pairs.get(key).map(|value| parse_value(key, value)).transpose()
+	}
+
+	/// Reads the pair `key` like [`CLIParser::get`], falling back to `default` when it is missing.
+	/// 
+	/// A value that cannot be read as `T` is still an error, rather than silently replaced by the default.
+	/// 
+	/// ```
+	/// let parser = cliparser::CLIParser::new().parse_str("--port=8080 --name=app").unwrap();
+	/// 
+	/// assert_eq!(parser.get_or::<u16>("port", 80), Ok(8080));
+	/// assert_eq!(parser.get_or::<u32>("retries", 3), Ok(3));
+	/// assert!(parser.get_or::<u32>("name", 0).is_err());
+	/// ```
+	pub fn get_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, CLIError>
+	where
+		T::Err: Display,
+	{
+		Ok(self.get(key)?.unwrap_or(default))
+	}
+
+	/// Splits the value of the pair `key` into a list, reading each item as any type implementing [`FromStr`], like [`CLIParser::get`].
+	/// 
+	/// The delimiter is the one declared with [`Schema::value_delimiter`](crate::Schema::value_delimiter), or a comma otherwise.
+	/// A delimiter preceded by `\` is kept literally. When [collecting duplicates](crate::DuplicatePolicy::Collect),
+	/// the items of every occurrence are concatenated.
+	/// Will throw a [`CLIError::ValueParse`] error with the first item that cannot be read as `T`.
+	/// 
+	/// ```
+	/// use cliparser::CLIError;
+	/// 
+	/// let parser = cliparser::CLIParser::new()
+	///     .parse_str(r"--ports=80,443,0x1F90 '--names=a\,b,c' --sizes=1,two,3")
+	///     .unwrap();
+	/// 
+	/// assert_eq!(parser.get_list::<u16>("ports"), Ok(Some(vec![80, 443, 8080])));
+	/// assert_eq!(parser.get_list::<String>("names").unwrap().unwrap(), vec!["a,b", "c"]);
+	/// assert_eq!(parser.get_list::<String>("missing"), Ok(None));
+	/// assert_eq!(parser.get_list::<u8>("sizes"), Err(CLIError::ValueParse {
+	///     key: "sizes".to_string(),
+	///     value: "two".to_string(),
+	///     type_name: "u8".to_string(),
+	///     reason: "invalid digit found in string".to_string(),
+	/// }));
+	/// ```
+	pub fn get_list<T: FromStr>(&self, key: &str) -> Result<Option<Vec<T>>, CLIError>
+	where
+		T::Err: Display,
+	{
+		let delimiter = self.schema.as_ref()
+			.and_then(|schema| schema.find(key))
+			.and_then(|spec| spec.delimiter)
+			.unwrap_or(',');
+
+		self.get_list_with(key, delimiter)
+	}
+
+	/// Splits the value of the pair `key` into a list, on the given `delimiter`.
+	/// 
+	/// See [`CLIParser::get_list`].
+	pub fn get_list_with<T: FromStr>(&self, key: &str, delimiter: char) -> Result<Option<Vec<T>>, CLIError>
+	where
+		T::Err: Display,
+	{
+		self.mark_used(key);
+		let items: Vec<String> = match (self.pairs_multi.get(key), self.pairs.get(key)) {
+			(Some(values), _) => values.iter().flat_map(|value| split_list(value, delimiter)).collect(),
+			(None, Some(value)) => split_list(value, delimiter),
+			(None, None) => return Ok(None),
+		};
+
+		items.iter().map(|item| parse_value(key, item)).collect::<Result<Vec<T>, CLIError>>().map(Some)
+	}
+
+}
@@ -0,0 +1,104 @@
+//! Non-fatal notices about the arguments, gathered while parsing.
+
+use std::sync::Arc;
+
+use crate::CLIParser;
+
+
+/// **Warnings** about arguments that were accepted, but maybe not the way the user meant.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Warning {
+	/// Unicode dashes at the start of an argument were replaced with ASCII ones.
+	DashesNormalized { original: String, normalized: String },
+	/// An argument was given with a [deprecated alias](crate::Schema::deprecated_alias) of its declared `name`.
+	DeprecatedAlias { alias: String, name: String },
+	/// A [deprecated](crate::Schema::deprecated) argument was given, which may have a `replacement`.
+	Deprecated { name: String, replacement: Option<String> },
+	/// A pair was given more than once with different values, and the [duplicates policy](crate::DuplicatePolicy) kept only one of them.
+	/// 
+	/// ```
+	/// use cliparser::{CLIParser, Warning};
+	/// 
+	/// let parser = CLIParser::new().parse_str("--level=1 --level=2").unwrap();
+	/// 
+	/// assert_eq!(parser.pairs["level"], "2");
+	/// assert_eq!(parser.warnings, vec![
+	///     Warning::DuplicateDiscarded { key: "level".to_string(), kept: "2".to_string(), discarded: "1".to_string() },
+	/// ]);
+	/// ```
+	DuplicateDiscarded { key: String, kept: String, discarded: String },
+}
+
+
+impl std::fmt::Display for Warning {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match *self {
+			Warning::DashesNormalized { ref original, ref normalized } => write!(f, "Replaced Unicode dashes in `{0}`, read as `{1}`", original, normalized),
+			Warning::DeprecatedAlias { ref alias, ref name } => write!(f, "`{0}` is deprecated, use `{1}` instead", alias, name),
+			Warning::Deprecated { ref name, replacement: Some(ref replacement) } => write!(f, "`{0}` is deprecated, use `{1}` instead", name, replacement),
+			Warning::Deprecated { ref name, replacement: None } => write!(f, "`{0}` is deprecated", name),
+			Warning::DuplicateDiscarded { ref key, ref kept, ref discarded } => write!(f, "`{0}` given more than once, kept `{1}` over `{2}`", key, kept, discarded),
+		}
+	}
+}
+
+
+/// Callback invoked with every [`Warning`] as soon as it occurs, see [`ParserConfig::on_warning`](crate::ParserConfig::on_warning).
+/// 
+/// ```
+/// use cliparser::{CLIParser, ParserConfig, WarningHook};
+/// 
+/// let parser = CLIParser::new()
+///     .config(ParserConfig {
+///         normalize_dashes: true,
+///         on_warning: Some(WarningHook::new(|warning| eprintln!("warning: {}", warning))),
+///         ..ParserConfig::default()
+///     })
+///     .parse_str("–verbose")
+///     .unwrap();
+/// 
+/// assert!(parser.flags.contains("verbose"));
+/// assert_eq!(parser.warnings.len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct WarningHook(Arc<dyn Fn(&Warning) + Send + Sync>);
+
+
+impl WarningHook {
+
+	/// Wraps a callback.
+	pub fn new<F: Fn(&Warning) + Send + Sync + 'static>(callback: F) -> Self {
+		Self(Arc::new(callback))
+	}
+
+}
+
+
+impl std::fmt::Debug for WarningHook {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("WarningHook(..)")
+	}
+}
+
+
+// Hooks are only equal to their own clones, there is no comparing closures
+impl PartialEq for WarningHook {
+	fn eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.0, &other.0)
+	}
+}
+
+impl Eq for WarningHook {}
+
+
+impl CLIParser {
+
+	/// Records a warning, and passes it to the hook if there is one.
+	pub(crate) fn warn(&mut self, warning: Warning) {
+		if let Some(hook) = &self.config.on_warning {
+			(hook.0)(&warning);
+		}
+		self.warnings.push(warning);
+	}
+
+}